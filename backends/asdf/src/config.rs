@@ -2,8 +2,10 @@
 
 use extism_pdk::*;
 use proto_pdk::*;
+use rustc_hash::FxHashMap;
 use schematic::Schematic;
 use serde::{Deserialize, Serialize};
+use starbase_utils::fs;
 
 #[host_fn]
 extern "ExtismHost" {
@@ -27,6 +29,10 @@ pub struct AsdfToolConfig {
     /// Custom Git repository to resolve from.
     #[serde(alias = "asdf-repository")]
     pub repository: Option<String>,
+
+    /// Git branch, tag, or commit to checkout the repository at.
+    #[serde(rename = "ref", alias = "commit")]
+    pub git_ref: Option<String>,
 }
 
 // https://asdf-vm.com/manage/plugins.html
@@ -51,6 +57,100 @@ impl AsdfToolConfig {
             .map(|path| path.join("bin").join(script))
     }
 
+    pub fn get_lock_metadata(&self) -> AnyResult<FxHashMap<String, String>> {
+        let mut metadata = FxHashMap::default();
+
+        if let Some(git_ref) = &self.git_ref {
+            metadata.insert("asdf-ref".into(), self.resolve_git_commit(git_ref)?);
+
+            if let Some(repo_url) = &self.repository {
+                metadata.insert("asdf-repository".into(), repo_url.into());
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// Resolve the ref to a commit, as branches and tags can be moved. The
+    /// repository is cloned after the tool is registered, so the remote is
+    /// queried if the clone doesn't contain the ref yet.
+    pub fn resolve_git_commit(&self, git_ref: &str) -> AnyResult<String> {
+        let backend_path = self.get_backend_path()?;
+
+        // The clone may predate the ref, so resolve the ref itself, not `HEAD`
+        if backend_path.join(".git").exists() {
+            let mut command = ExecCommandInput::pipe(
+                "git",
+                [
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("{git_ref}^{{commit}}"),
+                ],
+            );
+            command.cwd = Some(backend_path.clone());
+
+            let result = exec(command)?;
+
+            if result.exit_code == 0 {
+                return Ok(result.stdout.trim().into());
+            }
+        }
+
+        if git_ref.len() == 40 && git_ref.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Ok(git_ref.into());
+        }
+
+        // Tools are registered on every invocation, so avoid querying
+        // the remote each time until the repository has been cloned
+        let cache_path = VirtualPath::create(format!(
+            "/proto/backends/asdf/{}.ref",
+            self.get_backend_id()?
+        ))?;
+        let cache_key = format!(
+            "{}\t{git_ref}\t",
+            self.repository.as_deref().unwrap_or_default()
+        );
+
+        if let Ok(cache) = fs::read_file(&cache_path)
+            && let Some(commit) = cache.trim().strip_prefix(&cache_key)
+        {
+            return Ok(commit.into());
+        }
+
+        // Annotated tags are peeled with `^{}` to the commit they point to
+        let peeled_ref = format!("{git_ref}^{{}}");
+        let result = exec_captured(
+            "git",
+            ["ls-remote", &self.get_repo_url()?, git_ref, &peeled_ref],
+        )?;
+        let mut commit = None;
+
+        if result.exit_code == 0 {
+            for line in result.stdout.lines() {
+                let Some((hash, name)) = line.split_once('\t') else {
+                    continue;
+                };
+
+                if name.ends_with("^{}") {
+                    commit = Some(hash);
+                    break;
+                }
+
+                commit.get_or_insert(hash);
+            }
+        }
+
+        // Abbreviated commits can't be resolved without a clone
+        let Some(commit) = commit else {
+            return Ok(git_ref.into());
+        };
+
+        fs::write_file(&cache_path, format!("{cache_key}{commit}"))?;
+
+        Ok(commit.into())
+    }
+
     pub fn get_repo_url(&self) -> AnyResult<String> {
         if let Some(repo_url) = &self.repository {
             return Ok(repo_url.into());
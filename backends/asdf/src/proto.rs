@@ -126,6 +126,60 @@ fn create_script(
     Ok(input)
 }
 
+// https://asdf-vm.com/plugins/create.html#bin-help-scripts
+fn load_help(config: &AsdfToolConfig, context: &PluginContext) -> AnyResult<String> {
+    let mut sections = vec![];
+
+    for name in ["help.overview", "help.deps", "help.config", "help.links"] {
+        let script_path = config.get_script_path(name)?;
+
+        if !script_path.exists() {
+            continue;
+        }
+
+        // Help is best-effort, so don't fail if a script fails
+        if let Ok(data) = exec_script(create_script_from_context(&script_path, context)?) {
+            let data = data.trim();
+
+            if !data.is_empty() {
+                sections.push(data.to_owned());
+            }
+        }
+    }
+
+    Ok(sections.join("\n\n"))
+}
+
+// asdf runs this script when the plugin is added, but since proto clones
+// the repository itself, we run it once before the first script is executed.
+// https://asdf-vm.com/plugins/create.html#bin-post-plugin-add
+fn run_post_plugin_add(config: &AsdfToolConfig) -> AnyResult<()> {
+    let script_path = config.get_script_path("post-plugin-add")?;
+    let marker_path = config.get_backend_path()?.join(".proto-post-plugin-add");
+
+    if !script_path.exists() || marker_path.exists() {
+        return Ok(());
+    }
+
+    let mut script = create_script(&script_path, None, None, None)?;
+
+    if let Some(dir) = config.get_backend_path()?.to_real_path()? {
+        script
+            .env
+            .insert("ASDF_PLUGIN_PATH".into(), dir.to_string());
+    }
+
+    script
+        .env
+        .insert("ASDF_PLUGIN_SOURCE_URL".into(), config.get_repo_url()?);
+
+    exec_script(script)?;
+
+    fs::write_file(&marker_path, "")?;
+
+    Ok(())
+}
+
 fn exec_script(input: ExecCommandInput) -> AnyResult<String> {
     let script_path = input.args[0].clone();
     let result = exec(input)?;
@@ -133,6 +187,22 @@ fn exec_script(input: ExecCommandInput) -> AnyResult<String> {
     handle_exec_result(script_path, result)
 }
 
+fn exec_install_script(
+    input: ExecCommandInput,
+    config: &AsdfToolConfig,
+    context: &PluginContext,
+) -> AnyResult<String> {
+    exec_script(input).or_else(|error| {
+        let help = load_help(config, context)?;
+
+        if help.is_empty() {
+            Err(error)
+        } else {
+            Err(PluginError::Message(format!("{error}\n\n{help}")).into())
+        }
+    })
+}
+
 fn handle_exec_result(script_path: String, result: ExecCommandOutput) -> AnyResult<String> {
     if result.exit_code != 0 {
         return Err(PluginError::Message(format!(
@@ -149,6 +219,8 @@ fn handle_exec_result(script_path: String, result: ExecCommandOutput) -> AnyResu
 pub fn register_tool(Json(input): Json<RegisterToolInput>) -> FnResult<Json<RegisterToolOutput>> {
     enable_tracing();
 
+    let config = get_tool_config::<AsdfToolConfig>()?;
+
     Ok(Json(RegisterToolOutput {
         name: if input.id == "asdf" {
             input.id.to_string()
@@ -160,8 +232,10 @@ pub fn register_tool(Json(input): Json<RegisterToolInput>) -> FnResult<Json<Regi
         } else {
             PluginType::Language
         },
+        // Only record when pinned, as scripts may change between installs otherwise
         lock_options: ToolLockOptions {
-            no_record: true,
+            metadata: config.get_lock_metadata()?,
+            no_record: config.git_ref.is_none(),
             ..Default::default()
         },
         minimum_proto_version: Some(Version::new(0, 60, 0)),
@@ -197,16 +271,23 @@ pub fn register_backend(
         exes: vec![
             "bin/download".into(),
             "bin/exec-env".into(),
+            "bin/exec-path".into(),
+            "bin/help.config".into(),
+            "bin/help.deps".into(),
+            "bin/help.links".into(),
+            "bin/help.overview".into(),
             "bin/install".into(),
             "bin/latest-stable".into(),
             "bin/list-all".into(),
             "bin/list-bin-paths".into(),
             "bin/list-legacy-filenames".into(),
             "bin/parse-legacy-file".into(),
+            "bin/post-plugin-add".into(),
             "bin/uninstall".into(),
         ],
         source: Some(SourceLocation::Git(GitSource {
             url: config.get_repo_url()?,
+            reference: config.git_ref.clone(),
             ..Default::default()
         })),
     }))
@@ -312,6 +393,8 @@ pub fn native_install(
 
     let config = get_tool_config::<AsdfToolConfig>()?;
 
+    run_post_plugin_add(&config)?;
+
     // In older versions of asdf there may not be a 'download' script,
     // instead both download and install were done in the 'install' script.
     // However, in newer versions, there's two separate 'download' and 'install' scripts.
@@ -320,17 +403,18 @@ pub fn native_install(
 
     // https://asdf-vm.com/plugins/create.html#bin-download
     if download_script_path.exists() {
-        exec_script(create_script_from_context(
-            &download_script_path,
+        exec_install_script(
+            create_script_from_context(&download_script_path, &input.context)?,
+            &config,
             &input.context,
-        )?)?;
+        )?;
     }
 
     // https://asdf-vm.com/plugins/create.html#bin-install
     let mut script = create_script_from_context(&install_script_path, &input.context)?;
     script.env.insert("ASDF_CONCURRENCY".into(), cpu_cores()?);
 
-    exec_script(script)?;
+    exec_install_script(script, &config, &input.context)?;
 
     Ok(Json(NativeInstallOutput {
         installed: true,
@@ -382,7 +466,7 @@ pub fn locate_executables(
 
     let id = get_plugin_id()?;
 
-    if let Some(exes) = config.exes {
+    if let Some(exes) = &config.exes {
         for exe in exes {
             output.exes.insert(
                 exe.clone(),
@@ -443,6 +527,36 @@ pub fn locate_executables(
         );
     }
 
+    let script_path = config.get_script_path("exec-path")?;
+
+    // https://asdf-vm.com/plugins/create.html#bin-exec-path
+    if script_path.exists() {
+        let install_dir = input
+            .install_dir
+            .to_real_path()?
+            .expect("Invalid install directory!")
+            .to_string();
+
+        for (name, exe) in output.exes.iter_mut() {
+            let mut script = create_script_from_context(&script_path, &input.context)?;
+            script.args.push(install_dir.clone());
+            script.args.push(name.clone());
+            script.args.push(
+                exe.exe_path
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            );
+
+            let data = exec_script(script)?;
+            let path = data.trim();
+
+            if !path.is_empty() {
+                exe.exe_path = Some(path.into());
+            }
+        }
+    }
+
     Ok(Json(output))
 }
 
@@ -455,6 +569,9 @@ pub fn load_versions(Json(input): Json<LoadVersionsInput>) -> FnResult<Json<Load
     }
 
     let config = get_tool_config::<AsdfToolConfig>()?;
+
+    run_post_plugin_add(&config)?;
+
     let script_path = config.get_script_path("list-all")?;
 
     // https://asdf-vm.com/plugins/create.html#bin-list-all
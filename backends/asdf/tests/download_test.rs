@@ -1,6 +1,52 @@
 #[cfg(not(windows))]
 mod asdf_backend {
     use proto_pdk_test_utils::*;
+    use std::collections::HashMap;
 
     generate_native_install_tests!("asdf:zig", "0.13.0");
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Requires a C compiler")]
+    async fn includes_help_when_install_fails() {
+        let sandbox = create_empty_proto_sandbox();
+        sandbox.create_file("install/.gitkeep", "");
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/install",
+            "#!/usr/bin/env bash\necho \"Compiler not found\" >&2\nexit 1\n",
+        );
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/help.overview",
+            "#!/usr/bin/env bash\necho \"Zig is a programming language\"\n",
+        );
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/help.deps",
+            "#!/usr/bin/env bash\necho \"Requires a C compiler\"\n",
+        );
+        // Help is best-effort, so failing scripts are skipped
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/help.links",
+            "#!/usr/bin/env bash\nexit 1\n",
+        );
+
+        let plugin = sandbox
+            .create_plugin_with_config("zig", |cfg| {
+                cfg.tool_config(HashMap::from([(
+                    "repository",
+                    "https://github.com/asdf-community/asdf-zig",
+                )]));
+            })
+            .await;
+
+        plugin
+            .native_install(NativeInstallInput {
+                context: PluginContext {
+                    version: VersionSpec::parse("0.13.0").unwrap(),
+                    tool_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+                    ..Default::default()
+                },
+                install_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+                ..Default::default()
+            })
+            .await;
+    }
 }
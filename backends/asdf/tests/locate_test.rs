@@ -0,0 +1,76 @@
+#[cfg(not(windows))]
+mod asdf_backend {
+    use proto_pdk_test_utils::*;
+    use std::collections::HashMap;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn locates_exes_with_exec_path_script() {
+        let sandbox = create_empty_proto_sandbox();
+        sandbox.create_file("install/bin/zig", "");
+        sandbox.create_file("install/libexec/zig", "");
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/exec-path",
+            "#!/usr/bin/env bash\n[ -d \"$1\" ] && [ \"$3\" = \"bin/$2\" ] && echo \"libexec/$2\"\n",
+        );
+
+        let plugin = sandbox
+            .create_plugin_with_config("zig", |cfg| {
+                cfg.tool_config(HashMap::from([(
+                    "repository",
+                    "https://github.com/asdf-community/asdf-zig",
+                )]));
+            })
+            .await;
+
+        let output = plugin
+            .locate_executables(LocateExecutablesInput {
+                context: PluginContext {
+                    version: VersionSpec::parse("0.13.0").unwrap(),
+                    tool_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+                    ..Default::default()
+                },
+                install_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+            })
+            .await;
+
+        assert_eq!(
+            output.exes.get("zig").unwrap().exe_path,
+            Some("libexec/zig".into())
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn keeps_exe_path_if_exec_path_script_is_empty() {
+        let sandbox = create_empty_proto_sandbox();
+        sandbox.create_file("install/bin/zig", "");
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/exec-path",
+            "#!/usr/bin/env bash\n",
+        );
+
+        let plugin = sandbox
+            .create_plugin_with_config("zig", |cfg| {
+                cfg.tool_config(HashMap::from([(
+                    "repository",
+                    "https://github.com/asdf-community/asdf-zig",
+                )]));
+            })
+            .await;
+
+        let output = plugin
+            .locate_executables(LocateExecutablesInput {
+                context: PluginContext {
+                    version: VersionSpec::parse("0.13.0").unwrap(),
+                    tool_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+                    ..Default::default()
+                },
+                install_dir: plugin.tool.to_virtual_path(sandbox.path().join("install")),
+            })
+            .await;
+
+        assert_eq!(
+            output.exes.get("zig").unwrap().exe_path,
+            Some("bin/zig".into())
+        );
+    }
+}
@@ -36,4 +36,240 @@ mod asdf_backend {
             assert_eq!(git.url, "https://github.com/cheetah/asdf-zig");
         }
     }
+
+    mod with_ref {
+        use super::*;
+        use std::collections::HashMap;
+        use std::fs;
+        use std::path::Path;
+        use std::process::Command;
+
+        fn create_config() -> HashMap<&'static str, &'static str> {
+            HashMap::from([
+                ("repository", "https://github.com/asdf-community/asdf-zig"),
+                ("ref", "v1.0.0"),
+            ])
+        }
+
+        fn git(dir: &Path, args: &[&str]) -> String {
+            let output = Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=proto",
+                    "-c",
+                    "user.email=proto@moonrepo.dev",
+                ])
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+
+            assert!(output.status.success());
+
+            String::from_utf8(output.stdout).unwrap().trim().to_owned()
+        }
+
+        // Tags `v1.0.0` and then moves `main` past it
+        fn create_repository(sandbox: &ProtoWasmSandbox) -> (String, String) {
+            let repo_dir = sandbox.path().join("asdf-zig");
+
+            fs::create_dir_all(&repo_dir).unwrap();
+            git(&repo_dir, &["init", "--initial-branch", "main"]);
+            git(&repo_dir, &["commit", "--allow-empty", "-m", "First"]);
+            git(&repo_dir, &["tag", "--annotate", "v1.0.0", "-m", "v1.0.0"]);
+            git(&repo_dir, &["commit", "--allow-empty", "-m", "Second"]);
+
+            let commit = git(&repo_dir, &["rev-parse", "v1.0.0^{commit}"]);
+
+            (repo_dir.to_string_lossy().to_string(), commit)
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn records_lock_metadata() {
+            let sandbox = create_empty_proto_sandbox();
+            let (repo_url, commit) = create_repository(&sandbox);
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(HashMap::from([
+                        ("repository", repo_url.as_str()),
+                        ("ref", "v1.0.0"),
+                    ]));
+                })
+                .await;
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert!(!metadata.lock_options.no_record);
+            assert_eq!(
+                metadata.lock_options.metadata.get("asdf-ref").unwrap(),
+                &commit
+            );
+            assert_eq!(
+                metadata
+                    .lock_options
+                    .metadata
+                    .get("asdf-repository")
+                    .unwrap(),
+                &repo_url
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn records_commit_of_ref_in_cloned_repository() {
+            let sandbox = create_empty_proto_sandbox();
+            let (repo_url, _) = create_repository(&sandbox);
+            let clone_dir = sandbox.path().join(".proto/backends/asdf");
+
+            fs::create_dir_all(&clone_dir).unwrap();
+            git(&clone_dir, &["clone", &repo_url, "zig"]);
+            git(&clone_dir.join("zig"), &["checkout", "v1.0.0"]);
+
+            // The clone is checked out elsewhere, so `HEAD` isn't the ref
+            let commit = git(&clone_dir.join("zig"), &["rev-parse", "main"]);
+
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(HashMap::from([
+                        ("repository", repo_url.as_str()),
+                        ("ref", "main"),
+                    ]));
+                })
+                .await;
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert_eq!(
+                metadata.lock_options.metadata.get("asdf-ref").unwrap(),
+                &commit
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn queries_remote_if_ref_is_newer_than_clone() {
+            let sandbox = create_empty_proto_sandbox();
+            let (repo_url, _) = create_repository(&sandbox);
+            let clone_dir = sandbox.path().join(".proto/backends/asdf");
+
+            fs::create_dir_all(&clone_dir).unwrap();
+            git(&clone_dir, &["clone", &repo_url, "zig"]);
+
+            let repo_dir = Path::new(&repo_url);
+            git(repo_dir, &["commit", "--allow-empty", "-m", "Third"]);
+            git(repo_dir, &["tag", "v2.0.0"]);
+
+            let commit = git(repo_dir, &["rev-parse", "v2.0.0^{commit}"]);
+
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(HashMap::from([
+                        ("repository", repo_url.as_str()),
+                        ("ref", "v2.0.0"),
+                    ]));
+                })
+                .await;
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert_eq!(
+                metadata.lock_options.metadata.get("asdf-ref").unwrap(),
+                &commit
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn caches_commit_from_remote_until_cloned() {
+            let sandbox = create_empty_proto_sandbox();
+            let (repo_url, commit) = create_repository(&sandbox);
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(HashMap::from([
+                        ("repository", repo_url.as_str()),
+                        ("ref", "v1.0.0"),
+                    ]));
+                })
+                .await;
+
+            plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            // The remote is no longer available, so the commit must be cached
+            fs::remove_dir_all(&repo_url).unwrap();
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert_eq!(
+                metadata.lock_options.metadata.get("asdf-ref").unwrap(),
+                &commit
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn records_full_commit_as_is() {
+            let sandbox = create_empty_proto_sandbox();
+            let commit = "0123456789abcdef0123456789abcdef01234567";
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(HashMap::from([
+                        ("repository", "https://github.com/asdf-community/asdf-zig"),
+                        ("ref", commit),
+                    ]));
+                })
+                .await;
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert_eq!(
+                metadata.lock_options.metadata.get("asdf-ref").unwrap(),
+                commit
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_record_without_ref() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("zig").await;
+
+            let metadata = plugin
+                .register_tool(RegisterToolInput { id: Id::raw("zig") })
+                .await;
+
+            assert!(metadata.lock_options.no_record);
+            assert!(metadata.lock_options.metadata.is_empty());
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn checks_out_ref() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("zig", |cfg| {
+                    cfg.tool_config(create_config());
+                })
+                .await;
+
+            let metadata = plugin
+                .register_backend(RegisterBackendInput {
+                    id: Id::raw("zig"),
+                    ..Default::default()
+                })
+                .await;
+
+            let SourceLocation::Git(git) = metadata.source.unwrap() else {
+                panic!("Expected a Git source!");
+            };
+
+            assert_eq!(git.url, "https://github.com/asdf-community/asdf-zig");
+            assert_eq!(git.reference.unwrap(), "v1.0.0");
+        }
+    }
 }
@@ -1,6 +1,8 @@
 #[cfg(not(windows))]
 mod asdf_backend {
     use proto_pdk_test_utils::*;
+    use std::collections::HashMap;
+    use std::fs;
 
     generate_resolve_versions_tests!("asdf:zig", {
         "0.12" => "0.12.1",
@@ -16,4 +18,42 @@ mod asdf_backend {
 
         assert!(!output.versions.is_empty());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn runs_post_plugin_add_once() {
+        let sandbox = create_empty_proto_sandbox();
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/list-all",
+            "#!/usr/bin/env bash\necho \"1.0.0 2.0.0\"\n",
+        );
+        sandbox.create_file(
+            ".proto/backends/asdf/zig/bin/post-plugin-add",
+            "#!/usr/bin/env bash\necho \"$ASDF_PLUGIN_SOURCE_URL\" >> \"$ASDF_PLUGIN_PATH/added.log\"\n",
+        );
+
+        let plugin = sandbox
+            .create_plugin_with_config("zig", |cfg| {
+                cfg.tool_config(HashMap::from([(
+                    "repository",
+                    "https://github.com/asdf-community/asdf-zig",
+                )]));
+            })
+            .await;
+
+        plugin.load_versions(LoadVersionsInput::default()).await;
+
+        let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+        assert_eq!(
+            output.versions,
+            [
+                VersionSpec::parse("1.0.0").unwrap(),
+                VersionSpec::parse("2.0.0").unwrap()
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".proto/backends/asdf/zig/added.log")).unwrap(),
+            "https://github.com/asdf-community/asdf-zig\n"
+        );
+    }
 }
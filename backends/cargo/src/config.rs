@@ -22,18 +22,24 @@ pub struct CargoToolConfig {
     /// The explicit binary within the package to install.
     pub bin: Option<String>,
 
+    /// Git branch to install the package from. Requires `git-url`.
+    pub branch: Option<String>,
+
     /// List of features to enable for the package.
     pub features: Vec<String>,
 
+    /// Custom Git URL to install the package from, instead of a registry.
+    pub git_url: Option<String>,
+
     /// Install using locked versions.
     pub locked: bool,
 
-    /// Custom Git URL to the package.
-    // pub git_url: Option<String>,
-
     /// Disable the `default` feature of the package.
     pub no_default_features: bool,
 
     /// Custom crate registry to resolve packages from.
     pub registry: Option<String>,
+
+    /// Git commit to install the package from. Requires `git-url`.
+    pub rev: Option<String>,
 }
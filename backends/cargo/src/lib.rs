@@ -1,6 +1,8 @@
 mod config;
 #[cfg(feature = "wasm")]
 mod proto;
+#[cfg(feature = "wasm")]
+mod registry;
 
 pub use config::*;
 #[cfg(feature = "wasm")]
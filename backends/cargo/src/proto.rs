@@ -1,10 +1,10 @@
 use crate::config::{CargoBackendConfig, CargoToolConfig};
+use crate::registry::Registry;
 use backend_common::enable_tracing;
 use extism_pdk::*;
 use lang_rust_common::get_cargo_home;
 use proto_pdk::*;
 use schematic::SchemaBuilder;
-use serde::Deserialize;
 use starbase_utils::fs;
 
 #[host_fn]
@@ -74,6 +74,7 @@ pub fn native_install(
         .join(env.os.get_exe_name("cargo-binstall"));
 
     let use_binstall = !backend_config.no_binstall
+        && tool_config.git_url.is_none()
         && tool_config.bin.is_none()
         && tool_config.features.is_empty()
         && !tool_config.no_default_features
@@ -91,24 +92,42 @@ pub fn native_install(
     }
 
     // What to install
-    command.args.push(format!("{id}@{}", input.context.version));
+    if let Some(git) = &tool_config.git_url {
+        command.args.push("--git".into());
+        command.args.push(git.into());
+
+        // A branch or commit determines the version itself
+        if let Some(branch) = &tool_config.branch {
+            command.args.push(id.to_string());
+            command.args.push("--branch".into());
+            command.args.push(branch.into());
+        } else if let Some(rev) = &tool_config.rev {
+            command.args.push(id.to_string());
+            command.args.push("--rev".into());
+            command.args.push(rev.into());
+        } else {
+            command.args.push(format!("{id}@{}", input.context.version));
 
-    if tool_config.locked || backend_config.locked {
-        command.args.push("--locked".into());
-    }
+            if let Some(tag) = find_git_tag(&id, git, &input.context.version.to_string())? {
+                command.args.push("--tag".into());
+                command.args.push(tag);
+            }
+        }
+    } else {
+        command.args.push(format!("{id}@{}", input.context.version));
 
-    // if let Some(git) = &tool_config.git_url {
-    //     command.args.push("--git".into());
-    //     command.args.push(git.into());
-    // }
+        if let Some(registry) = tool_config
+            .registry
+            .as_ref()
+            .or(backend_config.registry.as_ref())
+        {
+            command.args.push("--registry".into());
+            command.args.push(registry.into());
+        }
+    }
 
-    if let Some(registry) = tool_config
-        .registry
-        .as_ref()
-        .or(backend_config.registry.as_ref())
-    {
-        command.args.push("--registry".into());
-        command.args.push(registry.into());
+    if tool_config.locked || backend_config.locked {
+        command.args.push("--locked".into());
     }
 
     // Where to install
@@ -158,6 +177,41 @@ pub fn native_install(
     }))
 }
 
+#[plugin_fn]
+pub fn native_uninstall(
+    Json(input): Json<NativeUninstallInput>,
+) -> FnResult<Json<NativeUninstallOutput>> {
+    let id = get_plugin_id()?;
+
+    let mut command = ExecCommandInput::pipe(
+        "cargo",
+        [
+            "uninstall".to_string(),
+            id.to_string(),
+            "--root".into(),
+            input
+                .uninstall_dir
+                .to_real_path()?
+                .expect("Invalid uninstall directory!")
+                .to_string(),
+        ],
+    );
+    command.cwd = Some(input.uninstall_dir.clone());
+    command.env.insert("PROTO_RUST_VERSION?".into(), "*".into());
+
+    let result = exec(command)?;
+
+    Ok(Json(NativeUninstallOutput {
+        uninstalled: result.exit_code == 0,
+        error: if result.exit_code == 0 || result.stderr.is_empty() {
+            None
+        } else {
+            Some(result.stderr)
+        },
+        ..Default::default()
+    }))
+}
+
 #[plugin_fn]
 pub fn locate_executables(
     Json(input): Json<LocateExecutablesInput>,
@@ -205,35 +259,63 @@ pub fn locate_executables(
     Ok(Json(output))
 }
 
-#[derive(Deserialize)]
-struct VersionItem {
-    #[serde(alias = "vers")]
-    num: String,
-    yanked: bool,
-}
+// Tags are commonly `v1.2.3`, or `name-v1.2.3` and `name@1.2.3` in workspaces
+fn parse_git_tag_version(id: &str, tag: &str) -> Option<String> {
+    let version = tag
+        .strip_prefix(&format!("{id}-v"))
+        .or_else(|| tag.strip_prefix(&format!("{id}-")))
+        .or_else(|| tag.strip_prefix(&format!("{id}@")))
+        .unwrap_or(tag);
+    let version = version.strip_prefix('v').unwrap_or(version);
 
-// #[derive(Deserialize)]
-// struct VersionsMeta {
-// }
+    Version::parse(version).ok().map(|_| version.to_owned())
+}
 
-#[derive(Deserialize)]
-struct VersionsResponse {
-    versions: Vec<VersionItem>,
-    // Handle next page?
-    // meta: VersionsMeta,
+fn find_git_tag(id: &str, git_url: &str, version: &str) -> AnyResult<Option<String>> {
+    Ok(load_git_tags(git_url)?
+        .into_iter()
+        .find(|tag| parse_git_tag_version(id, tag).is_some_and(|inner| inner == version)))
 }
 
 #[plugin_fn]
-pub fn load_versions(Json(_input): Json<LoadVersionsInput>) -> FnResult<Json<LoadVersionsOutput>> {
+pub fn load_versions(Json(input): Json<LoadVersionsInput>) -> FnResult<Json<LoadVersionsOutput>> {
     let mut output = LoadVersionsOutput::default();
     let id = get_plugin_id()?;
+    let backend_config = get_backend_config::<CargoBackendConfig>()?;
+    let tool_config = get_tool_config::<CargoToolConfig>()?;
+
+    if let Some(git) = &tool_config.git_url {
+        for tag in load_git_tags(git)? {
+            if let Some(version) = parse_git_tag_version(&id, &tag) {
+                output.versions.push(VersionSpec::parse(version)?);
+            }
+        }
+
+        // A branch or commit determines the version itself, so a repository
+        // without version tags can still be installed from a placeholder
+        if output.versions.is_empty() && (tool_config.branch.is_some() || tool_config.rev.is_some())
+        {
+            let version = VersionSpec::parse("0.0.0")?;
+
+            output.latest = Some(version.to_unresolved_spec());
+            output.versions.push(version);
+        }
+
+        return Ok(Json(output));
+    }
 
-    let response: VersionsResponse =
-        fetch_json(format!("https://crates.io/api/v1/crates/{id}/versions"))?;
+    let registry = match tool_config
+        .registry
+        .as_ref()
+        .or(backend_config.registry.as_ref())
+    {
+        Some(name) => Registry::load(name, get_host_environment()?, &input.context.working_dir)?,
+        None => Registry::crates_io(),
+    };
 
-    for version in response.versions {
-        if !version.yanked {
-            output.versions.push(VersionSpec::parse(version.num)?);
+    for entry in registry.load_index_entries(&id)? {
+        if !entry.yanked {
+            output.versions.push(VersionSpec::parse(entry.vers)?);
         }
     }
 
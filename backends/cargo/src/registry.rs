@@ -0,0 +1,149 @@
+use extism_pdk::json;
+use lang_rust_common::get_cargo_home;
+use proto_pdk::*;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use starbase_utils::fs;
+
+const CRATES_IO_INDEX: &str = "https://index.crates.io";
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct CargoRegistryConfig {
+    index: Option<String>,
+    token: Option<String>,
+}
+
+// Shape of both `config.toml` and `credentials.toml`
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct CargoConfig {
+    registries: FxHashMap<String, CargoRegistryConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct IndexEntry {
+    pub vers: String,
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+pub struct Registry {
+    pub index_url: String,
+    pub token: Option<String>,
+}
+
+impl Registry {
+    pub fn crates_io() -> Self {
+        Self {
+            index_url: CRATES_IO_INDEX.into(),
+            token: None,
+        }
+    }
+
+    /// Load the index and token of a registry by name, from environment
+    /// variables, the `.cargo` configuration in the working directory and
+    /// its parents, or the configuration in the Cargo home directory.
+    // https://doc.rust-lang.org/cargo/reference/config.html#hierarchical-structure
+    // https://doc.rust-lang.org/cargo/reference/registries.html
+    pub fn load(name: &str, env: &HostEnvironment, working_dir: &VirtualPath) -> AnyResult<Self> {
+        if name == "crates-io" {
+            return Ok(Self::crates_io());
+        }
+
+        let env_prefix = format!("CARGO_REGISTRIES_{}", name.to_uppercase().replace('-', "_"));
+        let cargo_home = get_cargo_home(env)?;
+        let mut index = get_host_env_var(format!("{env_prefix}_INDEX"))?;
+        let mut token = get_host_env_var(format!("{env_prefix}_TOKEN"))?;
+
+        // Closer configs take precedence, and the Cargo home is always last
+        let mut paths = vec![];
+        let mut dir = Some(working_dir.to_owned());
+
+        while let Some(current_dir) = dir {
+            paths.push(current_dir.join(".cargo/config.toml"));
+            paths.push(current_dir.join(".cargo/config"));
+
+            dir = current_dir.parent();
+        }
+
+        for file in ["config.toml", "config", "credentials.toml", "credentials"] {
+            paths.push(cargo_home.join(file));
+        }
+
+        for path in paths {
+            if index.is_some() && token.is_some() {
+                break;
+            }
+
+            if !path.exists() {
+                continue;
+            }
+
+            let mut config: CargoConfig = toml::from_str(&fs::read_file(&path)?)?;
+
+            if let Some(registry) = config.registries.remove(name) {
+                index = index.or(registry.index);
+                token = token.or(registry.token);
+            }
+        }
+
+        let Some(index) = index else {
+            return Err(PluginError::Message(format!(
+                "Unable to find the index for registry <id>{name}</id>. Define it with <property>registries.{name}.index</property> in Cargo's config, or the <env>{env_prefix}_INDEX</env> environment variable."
+            ))
+            .into());
+        };
+
+        // Git indexes require cloning the entire repository
+        let Some(index_url) = index.strip_prefix("sparse+") else {
+            return Err(PluginError::Message(format!(
+                "Registry <id>{name}</id> does not use a sparse index, which is required for loading versions."
+            ))
+            .into());
+        };
+
+        Ok(Self {
+            index_url: index_url.trim_end_matches('/').into(),
+            token,
+        })
+    }
+
+    pub fn load_index_entries(&self, crate_name: &str) -> AnyResult<Vec<IndexEntry>> {
+        let mut headers = FxHashMap::default();
+
+        if let Some(token) = &self.token {
+            headers.insert("Authorization".into(), token.to_owned());
+        }
+
+        let content = fetch(SendRequestInput {
+            url: format!("{}/{}", self.index_url, get_index_path(crate_name)),
+            headers,
+            ..Default::default()
+        })?
+        .text()?;
+
+        let mut entries = vec![];
+
+        // Each line is a JSON object for a single version
+        for line in content.lines() {
+            if !line.trim().is_empty() {
+                entries.push(json::from_str(line)?);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+// https://doc.rust-lang.org/cargo/reference/registry-index.html#index-files
+fn get_index_path(crate_name: &str) -> String {
+    let name = crate_name.to_lowercase();
+
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
@@ -69,14 +69,61 @@ mod cargo_backend_download {
     //     });
     // }
 
-    // mod git {
-    //     use super::*;
+    mod git {
+        use super::*;
 
-    //     generate_native_install_tests!("cargo:cargo-outdated", "0.17.0", None, |cfg| {
-    //         cfg.tool_config(CargoToolConfig {
-    //             git_url: Some("https://github.com/kbknapp/cargo-outdated.git".into()),
-    //             ..Default::default()
-    //         });
-    //     });
-    // }
+        generate_native_install_tests!("cargo:cargo-outdated", "0.17.0", None, |cfg| {
+            cfg.tool_config(CargoToolConfig {
+                git_url: Some("https://github.com/kbknapp/cargo-outdated.git".into()),
+                ..Default::default()
+            });
+        });
+    }
+
+    mod uninstall {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn uninstalls_tool() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:eza", |cfg| {
+                    cfg.backend_config(CargoBackendConfig {
+                        no_binstall: true,
+                        ..Default::default()
+                    });
+                })
+                .await;
+            let mut spec = ToolSpec::parse("0.23.1").unwrap();
+
+            flow::resolve::Resolver::new(&plugin.tool)
+                .resolve_version(&mut spec, false)
+                .await
+                .unwrap();
+
+            flow::install::Installer::new(&plugin.tool, &spec)
+                .install(flow::install::InstallOptions::default())
+                .await
+                .unwrap();
+
+            let install_dir = flow::locate::Locator::new(&plugin.tool, &spec).product_dir;
+            let exe_path = install_dir.join(if cfg!(windows) {
+                "bin/eza.exe"
+            } else {
+                "bin/eza"
+            });
+
+            assert!(exe_path.exists());
+
+            let output = plugin
+                .native_uninstall(NativeUninstallInput {
+                    uninstall_dir: plugin.tool.to_virtual_path(&install_dir),
+                    ..Default::default()
+                })
+                .await;
+
+            assert!(output.uninstalled);
+            assert!(!exe_path.exists());
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use cargo_backend::{CargoBackendConfig, CargoToolConfig};
 use proto_pdk_test_utils::*;
 
 mod cargo_backend_versions {
@@ -6,4 +7,259 @@ mod cargo_backend_versions {
     generate_resolve_versions_tests!("cargo:cargo-nextest", {
         "0.9.100" => "0.9.100",
     });
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn excludes_yanked_versions() {
+        let sandbox = create_empty_proto_sandbox();
+        let plugin = sandbox.create_plugin("cargo:cargo-nextest").await;
+
+        let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+        assert!(
+            output
+                .versions
+                .contains(&VersionSpec::parse("0.9.57").unwrap())
+        );
+        assert!(
+            !output
+                .versions
+                .contains(&VersionSpec::parse("0.9.56").unwrap())
+        );
+    }
+
+    mod with_registry {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_versions_from_sparse_index() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".home/.cargo/config.toml",
+                r#"
+[registries.internal]
+index = "sparse+https://index.crates.io/"
+"#,
+            );
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-nextest", |cfg| {
+                    cfg.backend_config(CargoBackendConfig {
+                        registry: Some("internal".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert!(
+                output
+                    .versions
+                    .contains(&VersionSpec::parse("0.9.100").unwrap())
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_registry_from_working_dir_config() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                "project/.cargo/config.toml",
+                r#"
+[registries.internal]
+index = "sparse+https://index.crates.io/"
+"#,
+            );
+            sandbox.create_file("project/crates/app/Cargo.toml", "");
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-nextest", |cfg| {
+                    cfg.backend_config(CargoBackendConfig {
+                        registry: Some("internal".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin
+                .load_versions(LoadVersionsInput {
+                    context: PluginUnresolvedContext {
+                        working_dir: plugin
+                            .tool
+                            .to_virtual_path(sandbox.path().join("project/crates/app")),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert!(
+                output
+                    .versions
+                    .contains(&VersionSpec::parse("0.9.100").unwrap())
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn prefers_working_dir_config_over_cargo_home() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".home/.cargo/config.toml",
+                r#"
+[registries.internal]
+index = "https://github.com/rust-lang/crates.io-index"
+"#,
+            );
+            sandbox.create_file(
+                "project/.cargo/config.toml",
+                r#"
+[registries.internal]
+index = "sparse+https://index.crates.io/"
+"#,
+            );
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-nextest", |cfg| {
+                    cfg.backend_config(CargoBackendConfig {
+                        registry: Some("internal".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin
+                .load_versions(LoadVersionsInput {
+                    context: PluginUnresolvedContext {
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path().join("project")),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert!(!output.versions.is_empty());
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        #[should_panic(expected = "Unable to find the index for registry")]
+        async fn errors_if_registry_not_configured() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-nextest", |cfg| {
+                    cfg.tool_config(CargoToolConfig {
+                        registry: Some("unknown".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            plugin.load_versions(LoadVersionsInput::default()).await;
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        #[should_panic(expected = "does not use a sparse index")]
+        async fn errors_if_registry_not_sparse() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".home/.cargo/config.toml",
+                r#"
+[registries.internal]
+index = "https://github.com/rust-lang/crates.io-index"
+"#,
+            );
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-nextest", |cfg| {
+                    cfg.backend_config(CargoBackendConfig {
+                        registry: Some("internal".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            plugin.load_versions(LoadVersionsInput::default()).await;
+        }
+    }
+
+    mod with_git {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_versions_from_git_tags() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:cargo-outdated", |cfg| {
+                    cfg.tool_config(CargoToolConfig {
+                        git_url: Some("https://github.com/kbknapp/cargo-outdated.git".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert!(
+                output
+                    .versions
+                    .contains(&VersionSpec::parse("0.17.0").unwrap())
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_placeholder_version_for_branch_without_tags() {
+            let sandbox = create_empty_proto_sandbox();
+            let repo_dir = sandbox.path().join("repo");
+
+            std::fs::create_dir_all(&repo_dir).unwrap();
+            std::process::Command::new("git")
+                .args(["init", "--quiet"])
+                .current_dir(&repo_dir)
+                .status()
+                .unwrap();
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:tool", |cfg| {
+                    cfg.tool_config(CargoToolConfig {
+                        git_url: Some(repo_dir.to_string_lossy().into()),
+                        branch: Some("main".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert_eq!(output.versions, [VersionSpec::parse("0.0.0").unwrap()]);
+            assert_eq!(
+                output.latest,
+                Some(UnresolvedVersionSpec::parse("0.0.0").unwrap())
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_load_placeholder_version_without_branch_or_rev() {
+            let sandbox = create_empty_proto_sandbox();
+            let repo_dir = sandbox.path().join("repo");
+
+            std::fs::create_dir_all(&repo_dir).unwrap();
+            std::process::Command::new("git")
+                .args(["init", "--quiet"])
+                .current_dir(&repo_dir)
+                .status()
+                .unwrap();
+
+            let plugin = sandbox
+                .create_plugin_with_config("cargo:tool", |cfg| {
+                    cfg.tool_config(CargoToolConfig {
+                        git_url: Some(repo_dir.to_string_lossy().into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert!(output.versions.is_empty());
+            assert_eq!(output.latest, None);
+        }
+    }
 }
@@ -1,10 +1,47 @@
 use schematic::Schematic;
 use serde::{Deserialize, Serialize};
 
+/// The package manager to install packages with.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, Schematic)]
+#[serde(rename_all = "lowercase")]
+pub enum NpmPackageManager {
+    Bun,
+    #[default]
+    Npm,
+    Pnpm,
+}
+
 /// Configuration for the `npm` backend plugin.
 #[derive(Debug, Default, Deserialize, Serialize, Schematic)]
 #[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
 pub struct NpmBackendConfig {
     /// Use `bun` for installing and executing packages instead of `npm` and `node`.
+    /// Deprecated, use `package-manager` instead.
     pub bun: bool,
+
+    /// The package manager to install packages with.
+    pub package_manager: Option<NpmPackageManager>,
+
+    /// The registry to load package metadata from. Defaults to the registry
+    /// configured in `.npmrc` for the package's scope, or npmjs.com.
+    pub registry_url: Option<String>,
+}
+
+impl NpmBackendConfig {
+    pub fn get_package_manager(&self) -> NpmPackageManager {
+        match self.package_manager {
+            Some(manager) => manager,
+            None if self.bun => NpmPackageManager::Bun,
+            None => NpmPackageManager::Npm,
+        }
+    }
+}
+
+/// Configuration for the tool within the `npm` backend plugin.
+#[derive(Debug, Default, Deserialize, Serialize, Schematic)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct NpmToolConfig {
+    /// List of additional packages to install alongside the tool,
+    /// like plugins or peer dependencies.
+    pub extra_packages: Vec<String>,
 }
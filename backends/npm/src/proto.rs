@@ -1,6 +1,8 @@
-use crate::config::NpmBackendConfig;
+use crate::config::{NpmBackendConfig, NpmPackageManager, NpmToolConfig};
 use backend_common::enable_tracing;
 use extism_pdk::*;
+use lang_javascript_common::{create_npm_auth_headers, load_npmrc, parse_registry_response};
+use npmrc_config_rs::registry::parse_registry_url;
 use proto_pdk::*;
 use rustc_hash::FxHashMap;
 use schematic::SchemaBuilder;
@@ -28,10 +30,10 @@ pub fn register_tool(Json(input): Json<RegisterToolInput>) -> FnResult<Json<Regi
             no_record: true,
             ..Default::default()
         },
-        requires: if config.bun {
-            vec!["bun".into()]
-        } else {
-            vec!["node".into(), "npm".into()]
+        requires: match config.get_package_manager() {
+            NpmPackageManager::Bun => vec!["bun".into()],
+            NpmPackageManager::Npm => vec!["node".into(), "npm".into()],
+            NpmPackageManager::Pnpm => vec!["node".into(), "pnpm".into()],
         },
         minimum_proto_version: Some(Version::new(0, 60, 0)),
         plugin_version: Version::parse(env!("CARGO_PKG_VERSION")).ok(),
@@ -40,6 +42,13 @@ pub fn register_tool(Json(input): Json<RegisterToolInput>) -> FnResult<Json<Regi
     }))
 }
 
+#[plugin_fn]
+pub fn define_tool_config() -> FnResult<Json<DefineToolConfigOutput>> {
+    Ok(Json(DefineToolConfigOutput {
+        schema: SchemaBuilder::build_root::<NpmToolConfig>(),
+    }))
+}
+
 #[plugin_fn]
 pub fn register_backend(
     Json(_input): Json<RegisterBackendInput>,
@@ -64,49 +73,53 @@ pub fn native_install(
     let config = get_backend_config::<NpmBackendConfig>()?;
     let id = get_plugin_id()?;
 
-    let mut command = ExecCommandInput {
-        command: "npm".into(),
-        args: vec![
-            "install".into(),
-            format!("{id}@{}", input.context.version),
-            "--global".into(),
-        ],
-        ..Default::default()
+    let args = match config.get_package_manager() {
+        NpmPackageManager::Bun => vec!["install", "--global", "--trust", "--no-save"],
+        NpmPackageManager::Npm => vec!["install", "--global"],
+        // Hoist so that packages can resolve the extra packages as siblings
+        NpmPackageManager::Pnpm => vec!["add", "--config.node-linker=hoisted"],
     };
 
-    let real_install_dir = input
-        .install_dir
-        .to_real_path()?
-        .expect("Invalid install directory!");
+    let result = exec(create_command(
+        &config,
+        &input.install_dir,
+        args,
+        format!("{id}@{}", input.context.version),
+    )?)?;
 
-    if config.bun {
-        command.command = "bun".into();
-        command.args.push("--trust".into());
-        command.args.push("--no-save".into());
-
-        // These dirs match the node/npm structure
-        command.env.insert(
-            "BUN_INSTALL_BIN".into(),
-            real_install_dir.join("bin").to_string(),
-        );
-        command.env.insert(
-            "BUN_INSTALL_GLOBAL_DIR".into(),
-            real_install_dir.join("lib").to_string(),
-        );
-    } else {
-        let install_dir = real_install_dir.to_string();
+    Ok(Json(NativeInstallOutput {
+        installed: result.exit_code == 0,
+        error: if result.stderr.is_empty() {
+            None
+        } else {
+            Some(result.stderr)
+        },
+        ..Default::default()
+    }))
+}
 
-        command.args.push("--prefix".into());
-        command.args.push(install_dir.clone());
-        command.env.insert("PREFIX".into(), install_dir);
-    }
+#[plugin_fn]
+pub fn native_uninstall(
+    Json(input): Json<NativeUninstallInput>,
+) -> FnResult<Json<NativeUninstallOutput>> {
+    let config = get_backend_config::<NpmBackendConfig>()?;
+    let id = get_plugin_id()?;
 
-    command.cwd = Some(input.install_dir.clone());
+    let args = match config.get_package_manager() {
+        NpmPackageManager::Bun => vec!["remove", "--global"],
+        NpmPackageManager::Npm => vec!["uninstall", "--global"],
+        NpmPackageManager::Pnpm => vec!["remove"],
+    };
 
-    let result = exec(prepare_command(command))?;
+    let result = exec(create_command(
+        &config,
+        &input.uninstall_dir,
+        args,
+        id.to_string(),
+    )?)?;
 
-    Ok(Json(NativeInstallOutput {
-        installed: result.exit_code == 0,
+    Ok(Json(NativeUninstallOutput {
+        uninstalled: result.exit_code == 0,
         error: if result.stderr.is_empty() {
             None
         } else {
@@ -116,12 +129,70 @@ pub fn native_install(
     }))
 }
 
+fn create_command(
+    config: &NpmBackendConfig,
+    install_dir: &VirtualPath,
+    args: Vec<&str>,
+    package: String,
+) -> AnyResult<ExecCommandInput> {
+    let tool_config = get_tool_config::<NpmToolConfig>()?;
+    let manager = config.get_package_manager();
+
+    let mut command = ExecCommandInput {
+        command: match manager {
+            NpmPackageManager::Bun => "bun",
+            NpmPackageManager::Npm => "npm",
+            NpmPackageManager::Pnpm => "pnpm",
+        }
+        .into(),
+        args: args.into_iter().map(|arg| arg.to_owned()).collect(),
+        ..Default::default()
+    };
+
+    command.args.push(package);
+    command.args.extend(tool_config.extra_packages);
+
+    let real_install_dir = install_dir
+        .to_real_path()?
+        .expect("Invalid install directory!");
+
+    match manager {
+        NpmPackageManager::Bun => {
+            // These dirs match the node/npm structure
+            command.env.insert(
+                "BUN_INSTALL_BIN".into(),
+                real_install_dir.join("bin").to_string(),
+            );
+            command.env.insert(
+                "BUN_INSTALL_GLOBAL_DIR".into(),
+                real_install_dir.join("lib").to_string(),
+            );
+        }
+        NpmPackageManager::Npm => {
+            let install_dir = real_install_dir.to_string();
+
+            command.args.push("--prefix".into());
+            command.args.push(install_dir.clone());
+            command.env.insert("PREFIX".into(), install_dir);
+        }
+        // Installed as a local project within the directory
+        NpmPackageManager::Pnpm => {
+            command.args.push("--dir".into());
+            command.args.push(real_install_dir.to_string());
+        }
+    };
+
+    command.cwd = Some(install_dir.clone());
+
+    Ok(prepare_command(command))
+}
+
 #[plugin_fn]
 pub fn locate_executables(
     Json(input): Json<LocateExecutablesInput>,
 ) -> FnResult<Json<LocateExecutablesOutput>> {
     let mut output = LocateExecutablesOutput::default();
-    let backend_config = get_backend_config::<NpmBackendConfig>()?;
+    let package_manager = get_backend_config::<NpmBackendConfig>()?.get_package_manager();
 
     let package_name = get_plugin_id()?;
     let package_name_without_scope = match package_name.rfind('/') {
@@ -143,6 +214,15 @@ pub fn locate_executables(
         .join(&rel_package_path)
         .join("package.json");
 
+    // Differences between unix/windows, node/bun, and global/local installs
+    let rel_bin_dir = if package_manager == NpmPackageManager::Pnpm {
+        "node_modules/.bin"
+    } else if input.install_dir.join("bin").exists() {
+        "bin"
+    } else {
+        "."
+    };
+
     let create_exe_config = |bin_path: &str, primary: bool| {
         let mut config = ExecutableConfig::new(format!(
             "{rel_package_path}/{}",
@@ -172,7 +252,7 @@ pub fn locate_executables(
             }
         }
 
-        if config.parent_exe_name.is_some() && backend_config.bun {
+        if config.parent_exe_name.is_some() && package_manager == NpmPackageManager::Bun {
             config.parent_exe_name = Some("bun".into());
         }
 
@@ -205,11 +285,10 @@ pub fn locate_executables(
 
     // Otherwise, scan the file system
     if output.exes.is_empty() {
-        // Differences between unix/windows and node/bun
-        let bin_dir = if input.install_dir.join("bin").exists() {
-            input.install_dir.join("bin")
-        } else {
+        let bin_dir = if rel_bin_dir == "." {
             input.install_dir.clone()
+        } else {
+            input.install_dir.join(rel_bin_dir)
         };
 
         for entry in fs::read_dir(bin_dir)? {
@@ -243,39 +322,112 @@ pub fn locate_executables(
     }
 
     // Support activate flows
-    output
-        .exes_dirs
-        .push(if input.install_dir.join("bin").exists() {
-            "bin".into()
-        } else {
-            ".".into()
-        });
+    output.exes_dirs.push(rel_bin_dir.into());
 
     Ok(Json(output))
 }
 
 #[plugin_fn]
 pub fn load_versions(Json(input): Json<LoadVersionsInput>) -> FnResult<Json<LoadVersionsOutput>> {
-    let mut output = LoadVersionsOutput::default();
     let config = get_backend_config::<NpmBackendConfig>()?;
     let id = get_plugin_id()?;
 
+    match load_versions_from_registry(&config, &id, &input.context.working_dir) {
+        Ok(output) => Ok(Json(output)),
+        // Only fall back to the CLI when the registry responds with a failure,
+        // and never when a registry has been explicitly configured
+        Err(error) if config.registry_url.is_none() => {
+            debug!("Failed to load versions from the registry, falling back to the CLI: {error}");
+
+            Ok(Json(load_versions_from_cli(&config, &id, input.context)?))
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn load_versions_from_registry(
+    config: &NpmBackendConfig,
+    id: &str,
+    working_dir: &VirtualPath,
+) -> AnyResult<LoadVersionsOutput> {
+    let mut output = LoadVersionsOutput::default();
+    let npmrc = load_npmrc(working_dir)?;
+    let registry = match &config.registry_url {
+        Some(url) => parse_registry_url(url)?,
+        None => npmrc.registry_for(id),
+    };
+
+    let mut headers = create_npm_auth_headers(npmrc.credentials_for(&registry));
+
+    // The abbreviated metadata only contains what's required for installing
+    // https://github.com/npm/registry/blob/main/docs/responses/package-metadata.md
+    headers.insert(
+        "Accept".into(),
+        "application/vnd.npm.install-v1+json".into(),
+    );
+
+    // Transport errors abort the plugin call, but non-2xx statuses
+    // and invalid bodies are returned as errors
+    let response = parse_registry_response(
+        fetch(SendRequestInput {
+            url: format!(
+                "{}/{}",
+                registry.as_str().trim_end_matches('/'),
+                id.replace('/', "%2f")
+            ),
+            headers,
+            ..Default::default()
+        })?
+        .text()?,
+        false,
+    )?;
+
+    for item in response.versions.values() {
+        output
+            .versions
+            .push(VersionSpec::parse(item.version.trim())?);
+    }
+
+    for (alias, version) in response.dist_tags {
+        let version = UnresolvedVersionSpec::parse(&version)?;
+
+        if alias == "latest" {
+            output.latest = Some(version.clone());
+        }
+
+        output.aliases.entry(alias).or_insert(version);
+    }
+
+    Ok(output)
+}
+
+fn load_versions_from_cli(
+    config: &NpmBackendConfig,
+    id: &str,
+    context: PluginUnresolvedContext,
+) -> AnyResult<LoadVersionsOutput> {
+    let mut output = LoadVersionsOutput::default();
+
     // Create a base command
-    let mut command = prepare_command(if config.bun {
-        ExecCommandInput::pipe("bun", ["info", &id, "--json"])
+    let is_bun = config.get_package_manager() == NpmPackageManager::Bun;
+    let mut command = prepare_command(if is_bun {
+        ExecCommandInput::pipe("bun", ["info", id, "--json"])
     } else {
-        ExecCommandInput::pipe("npm", ["view", &id, "--json"])
+        ExecCommandInput::pipe("npm", ["view", id, "--json"])
     });
 
     // Bun requires a `package.json` in the directory or it fails...
-    if config.bun {
-        let package_path = input.context.temp_dir.join("package.json");
+    if is_bun {
+        let package_path = context.temp_dir.join("package.json");
 
         if !package_path.exists() {
             fs::write_file(package_path, "{}")?;
         }
 
-        command.cwd = Some(input.context.temp_dir);
+        command.cwd = Some(context.temp_dir);
+    } else {
+        // Resolve the same `.npmrc` as the registry request
+        command.cwd = Some(context.working_dir);
     }
 
     // Fetch versions
@@ -308,12 +460,13 @@ pub fn load_versions(Json(input): Json<LoadVersionsInput>) -> FnResult<Json<Load
         output.aliases.entry(alias).or_insert(version);
     }
 
-    Ok(Json(output))
+    Ok(output)
 }
 
 fn prepare_command(mut command: ExecCommandInput) -> ExecCommandInput {
     command.env.insert("PROTO_NODE_VERSION?".into(), "*".into());
     command.env.insert("PROTO_NPM_VERSION?".into(), "*".into());
+    command.env.insert("PROTO_PNPM_VERSION?".into(), "*".into());
     command.env.insert("PROTO_BUN_VERSION?".into(), "*".into());
     command.stream = false;
     command
@@ -1,5 +1,43 @@
-use npm_backend::NpmBackendConfig;
+use npm_backend::{NpmBackendConfig, NpmPackageManager, NpmToolConfig};
 use proto_pdk_test_utils::*;
+use std::path::{Path, PathBuf};
+
+// Returns the directory the tool was installed into
+async fn install_tool(plugin: &WasmTestWrapper, version: &str) -> PathBuf {
+    let mut spec = ToolSpec::parse(version).unwrap();
+
+    flow::resolve::Resolver::new(&plugin.tool)
+        .resolve_version(&mut spec, false)
+        .await
+        .unwrap();
+
+    flow::install::Installer::new(&plugin.tool, &spec)
+        .install(flow::install::InstallOptions::default())
+        .await
+        .unwrap();
+
+    flow::locate::Locator::new(&plugin.tool, &spec).product_dir
+}
+
+fn get_package_dir(install_dir: &Path, package: &str) -> PathBuf {
+    // Differences between unix/windows and node/bun
+    if install_dir.join("lib").exists() {
+        install_dir.join("lib/node_modules").join(package)
+    } else {
+        install_dir.join("node_modules").join(package)
+    }
+}
+
+async fn uninstall_tool(plugin: &WasmTestWrapper, install_dir: PathBuf) {
+    let output = plugin
+        .native_uninstall(NativeUninstallInput {
+            uninstall_dir: plugin.tool.to_virtual_path(install_dir),
+            ..Default::default()
+        })
+        .await;
+
+    assert!(output.uninstalled);
+}
 
 mod npm_backend_download {
     use super::*;
@@ -8,6 +46,19 @@ mod npm_backend_download {
         use super::*;
 
         generate_native_install_tests!("npm:typescript", "5.9.2");
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn uninstalls_tool() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("npm:typescript").await;
+            let install_dir = install_tool(&plugin, "5.9.2").await;
+
+            assert!(get_package_dir(&install_dir, "typescript").exists());
+
+            uninstall_tool(&plugin, install_dir.clone()).await;
+
+            assert!(!get_package_dir(&install_dir, "typescript").exists());
+        }
     }
 
     // With scope and package name doesn't match bin names
@@ -21,8 +72,31 @@ mod npm_backend_download {
         use super::*;
 
         generate_native_install_tests!("npm:typescript", "5.9.2", None, |cfg| {
-            cfg.backend_config(NpmBackendConfig { bun: true });
+            cfg.backend_config(NpmBackendConfig {
+                bun: true,
+                ..Default::default()
+            });
         });
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn uninstalls_tool() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:typescript", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        bun: true,
+                        ..Default::default()
+                    });
+                })
+                .await;
+            let install_dir = install_tool(&plugin, "5.9.2").await;
+
+            assert!(get_package_dir(&install_dir, "typescript").exists());
+
+            uninstall_tool(&plugin, install_dir.clone()).await;
+
+            assert!(!get_package_dir(&install_dir, "typescript").exists());
+        }
     }
 
     // With scope and package name doesn't match bin names
@@ -30,7 +104,68 @@ mod npm_backend_download {
         use super::*;
 
         generate_native_install_tests!("npm:@moonrepo/cli", "2.0.0", None, |cfg| {
-            cfg.backend_config(NpmBackendConfig { bun: true });
+            cfg.backend_config(NpmBackendConfig {
+                bun: true,
+                ..Default::default()
+            });
+        });
+    }
+
+    mod with_pnpm {
+        use super::*;
+
+        generate_native_install_tests!("npm:typescript", "5.9.2", None, |cfg| {
+            cfg.backend_config(NpmBackendConfig {
+                package_manager: Some(NpmPackageManager::Pnpm),
+                ..Default::default()
+            });
+        });
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn uninstalls_tool() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:typescript", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        package_manager: Some(NpmPackageManager::Pnpm),
+                        ..Default::default()
+                    });
+                })
+                .await;
+            let install_dir = install_tool(&plugin, "5.9.2").await;
+
+            assert!(get_package_dir(&install_dir, "typescript").exists());
+
+            uninstall_tool(&plugin, install_dir.clone()).await;
+
+            assert!(!get_package_dir(&install_dir, "typescript").exists());
+        }
+    }
+
+    // Plugins must be resolvable from the main package
+    mod with_extra_packages {
+        use super::*;
+
+        generate_native_install_tests!("npm:eslint", "9.0.0", None, |cfg| {
+            cfg.tool_config(NpmToolConfig {
+                extra_packages: vec!["eslint-plugin-react@7.37.0".into()],
+            });
         });
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn installs_extra_packages_as_siblings() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:eslint", |cfg| {
+                    cfg.tool_config(NpmToolConfig {
+                        extra_packages: vec!["eslint-plugin-react@7.37.0".into()],
+                    });
+                })
+                .await;
+            let install_dir = install_tool(&plugin, "9.0.0").await;
+
+            assert!(get_package_dir(&install_dir, "eslint").exists());
+            assert!(get_package_dir(&install_dir, "eslint-plugin-react").exists());
+        }
     }
 }
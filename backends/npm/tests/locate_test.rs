@@ -1,4 +1,4 @@
-use npm_backend::NpmBackendConfig;
+use npm_backend::{NpmBackendConfig, NpmPackageManager};
 use proto_pdk_test_utils::*;
 use std::path::PathBuf;
 
@@ -264,7 +264,10 @@ mod npm_backend_locate {
 
             let plugin = sandbox
                 .create_plugin_with_config("npm:typescript", |cfg| {
-                    cfg.backend_config(NpmBackendConfig { bun: true });
+                    cfg.backend_config(NpmBackendConfig {
+                        bun: true,
+                        ..Default::default()
+                    });
                 })
                 .await;
             let output = plugin
@@ -289,7 +292,10 @@ mod npm_backend_locate {
 
             let plugin = sandbox
                 .create_plugin_with_config("npm:@moonrepo/cli", |cfg| {
-                    cfg.backend_config(NpmBackendConfig { bun: true });
+                    cfg.backend_config(NpmBackendConfig {
+                        bun: true,
+                        ..Default::default()
+                    });
                 })
                 .await;
             let output = plugin
@@ -304,4 +310,64 @@ mod npm_backend_locate {
             assert!(cli.primary);
         }
     }
+
+    mod with_pnpm {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn reads_from_local_node_modules() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                "node_modules/eslint/package.json",
+                r#"{"bin":{"eslint":"./bin/eslint.js"}}"#,
+            );
+            sandbox.create_file("node_modules/.bin/eslint", "");
+
+            let plugin = sandbox
+                .create_plugin_with_config("npm:eslint", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        package_manager: Some(NpmPackageManager::Pnpm),
+                        ..Default::default()
+                    });
+                })
+                .await;
+            let output = plugin
+                .locate_executables(locate_input(&sandbox, &plugin))
+                .await;
+
+            let eslint = output.exes.get("eslint").unwrap();
+            assert_eq!(
+                eslint.exe_path,
+                Some(PathBuf::from("node_modules/eslint/bin/eslint.js"))
+            );
+            assert!(eslint.primary);
+            assert_eq!(eslint.parent_exe_name, Some("node".into()));
+            assert_eq!(output.exes_dirs, [PathBuf::from("node_modules/.bin")]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn scans_bin_dir_when_no_package_json() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file("node_modules/.bin/eslint", "");
+
+            let plugin = sandbox
+                .create_plugin_with_config("npm:eslint", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        package_manager: Some(NpmPackageManager::Pnpm),
+                        ..Default::default()
+                    });
+                })
+                .await;
+            let output = plugin
+                .locate_executables(locate_input(&sandbox, &plugin))
+                .await;
+
+            let eslint = output.exes.get("eslint").unwrap();
+            assert_eq!(
+                eslint.exe_path,
+                Some(PathBuf::from("node_modules/.bin/eslint"))
+            );
+            assert!(eslint.primary);
+        }
+    }
 }
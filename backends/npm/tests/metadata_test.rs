@@ -1,4 +1,4 @@
-use npm_backend::NpmBackendConfig;
+use npm_backend::{NpmBackendConfig, NpmPackageManager};
 use proto_pdk_test_utils::*;
 
 mod npm_backend_metadata {
@@ -56,7 +56,10 @@ mod npm_backend_metadata {
         let sandbox = create_empty_proto_sandbox();
         let plugin = sandbox
             .create_plugin_with_config("typescript", |cfg| {
-                cfg.backend_config(NpmBackendConfig { bun: true });
+                cfg.backend_config(NpmBackendConfig {
+                    bun: true,
+                    ..Default::default()
+                });
             })
             .await;
 
@@ -68,4 +71,47 @@ mod npm_backend_metadata {
 
         assert_eq!(metadata.requires, ["bun"]);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn requires_node_pnpm() {
+        let sandbox = create_empty_proto_sandbox();
+        let plugin = sandbox
+            .create_plugin_with_config("typescript", |cfg| {
+                cfg.backend_config(NpmBackendConfig {
+                    package_manager: Some(NpmPackageManager::Pnpm),
+                    ..Default::default()
+                });
+            })
+            .await;
+
+        let metadata = plugin
+            .register_tool(RegisterToolInput {
+                id: Id::raw("typescript"),
+            })
+            .await;
+
+        assert_eq!(metadata.requires, ["node", "pnpm"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn package_manager_takes_precedence_over_bun() {
+        let sandbox = create_empty_proto_sandbox();
+        let plugin = sandbox
+            .create_plugin_with_config("typescript", |cfg| {
+                cfg.backend_config(NpmBackendConfig {
+                    bun: true,
+                    package_manager: Some(NpmPackageManager::Npm),
+                    ..Default::default()
+                });
+            })
+            .await;
+
+        let metadata = plugin
+            .register_tool(RegisterToolInput {
+                id: Id::raw("typescript"),
+            })
+            .await;
+
+        assert_eq!(metadata.requires, ["node", "npm"]);
+    }
 }
@@ -1,5 +1,10 @@
+use lang_javascript_common::serve_npm_registry;
 use npm_backend::NpmBackendConfig;
 use proto_pdk_test_utils::*;
+use std::net::TcpListener;
+
+const PACKAGE_METADATA: &str =
+    r#"{"name":"pkg","dist-tags":{"latest":"1.2.3"},"versions":{"1.2.3":{"version":"1.2.3"}}}"#;
 
 mod npm_backend_versions {
     use super::*;
@@ -24,6 +29,235 @@ mod npm_backend_versions {
         }
     }
 
+    mod with_registry {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_versions_from_custom_registry() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:@moonrepo/cli", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        registry_url: Some("https://registry.npmjs.org/".into()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert!(
+                output
+                    .versions
+                    .contains(&VersionSpec::parse("1.0.0").unwrap())
+            );
+            assert!(output.aliases.contains_key("latest"));
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_versions_over_http_from_configured_registry() {
+            let (registry_url, requests) =
+                serve_npm_registry(|_| ("200 OK", PACKAGE_METADATA.into()));
+
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:@moonrepo/cli", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        registry_url: Some(registry_url.clone()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            let output = plugin.load_versions(LoadVersionsInput::default()).await;
+
+            assert_eq!(output.versions, [VersionSpec::parse("1.2.3").unwrap()]);
+            assert_eq!(
+                output.latest,
+                Some(UnresolvedVersionSpec::parse("1.2.3").unwrap())
+            );
+
+            let requests = requests.lock().unwrap();
+
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].starts_with("GET /@moonrepo%2fcli "));
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("accept: application/vnd.npm.install-v1+json")
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        #[should_panic(expected = "Failed to make HTTP request")]
+        async fn errors_if_configured_registry_unreachable() {
+            // Nothing listens on the port once the listener is dropped
+            let registry_url = format!(
+                "http://{}",
+                TcpListener::bind("127.0.0.1:0")
+                    .unwrap()
+                    .local_addr()
+                    .unwrap()
+            );
+
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:typescript", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        registry_url: Some(registry_url.clone()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            plugin.load_versions(LoadVersionsInput::default()).await;
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        #[should_panic(expected = "Failed to request")]
+        async fn errors_if_configured_registry_responds_with_failure() {
+            let (registry_url, _) = serve_npm_registry(|_| ("404 Not Found", "{}".into()));
+
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox
+                .create_plugin_with_config("npm:typescript", |cfg| {
+                    cfg.backend_config(NpmBackendConfig {
+                        registry_url: Some(registry_url.clone()),
+                        ..Default::default()
+                    });
+                })
+                .await;
+
+            plugin.load_versions(LoadVersionsInput::default()).await;
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn falls_back_to_cli_if_registry_responds_with_failure() {
+            // Only the abbreviated metadata requested over HTTP fails
+            let (registry_url, requests) = serve_npm_registry(|request| {
+                if request
+                    .to_lowercase()
+                    .contains("accept: application/vnd.npm.install-v1+json")
+                {
+                    ("404 Not Found", "{}".into())
+                } else {
+                    ("200 OK", PACKAGE_METADATA.into())
+                }
+            });
+
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(".npmrc", format!("registry={registry_url}/"));
+
+            let plugin = sandbox.create_plugin("npm:pkg").await;
+
+            let output = plugin
+                .load_versions(LoadVersionsInput {
+                    context: PluginUnresolvedContext {
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(output.versions, vec![VersionSpec::parse("1.2.3").unwrap()]);
+            assert_eq!(
+                output.latest,
+                Some(UnresolvedVersionSpec::parse("1.2.3").unwrap())
+            );
+
+            let requests = requests.lock().unwrap();
+
+            assert!(requests.len() > 1);
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("accept: application/vnd.npm.install-v1+json")
+            );
+            assert!(
+                requests[1..]
+                    .iter()
+                    .all(|request| request.starts_with("GET /pkg "))
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_registry_and_auth_from_npmrc() {
+            let (registry_url, requests) =
+                serve_npm_registry(|_| ("200 OK", PACKAGE_METADATA.into()));
+
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".npmrc",
+                format!(
+                    "registry={registry_url}/\n{}/:_authToken=abc123\n",
+                    registry_url.trim_start_matches("http:")
+                ),
+            );
+
+            let plugin = sandbox.create_plugin("npm:typescript").await;
+
+            let output = plugin
+                .load_versions(LoadVersionsInput {
+                    context: PluginUnresolvedContext {
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(output.versions, [VersionSpec::parse("1.2.3").unwrap()]);
+
+            let requests = requests.lock().unwrap();
+
+            assert!(requests[0].starts_with("GET /typescript "));
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("authorization: bearer abc123")
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_scoped_registry_and_auth_from_npmrc() {
+            let (registry_url, requests) =
+                serve_npm_registry(|_| ("200 OK", PACKAGE_METADATA.into()));
+
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".npmrc",
+                format!(
+                    "@moonrepo:registry={registry_url}/\n{}/:_authToken=abc123\n",
+                    registry_url.trim_start_matches("http:")
+                ),
+            );
+
+            let plugin = sandbox.create_plugin("npm:@moonrepo/cli").await;
+
+            let output = plugin
+                .load_versions(LoadVersionsInput {
+                    context: PluginUnresolvedContext {
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(output.versions, [VersionSpec::parse("1.2.3").unwrap()]);
+
+            let requests = requests.lock().unwrap();
+
+            assert!(requests[0].starts_with("GET /@moonrepo%2fcli "));
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("authorization: bearer abc123")
+            );
+        }
+    }
+
     mod with_bun {
         use super::*;
 
@@ -31,7 +265,10 @@ mod npm_backend_versions {
             "5.7" => "5.7.3",
             "5.9.2" => "5.9.2",
         }, None, |cfg| {
-            cfg.backend_config(NpmBackendConfig { bun: true });
+            cfg.backend_config(NpmBackendConfig {
+                bun: true,
+                ..Default::default()
+            });
         });
 
         #[tokio::test(flavor = "multi_thread")]
@@ -39,7 +276,10 @@ mod npm_backend_versions {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("npm:typescript", |cfg| {
-                    cfg.backend_config(NpmBackendConfig { bun: true });
+                    cfg.backend_config(NpmBackendConfig {
+                        bun: true,
+                        ..Default::default()
+                    });
                 })
                 .await;
 
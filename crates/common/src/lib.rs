@@ -1,3 +1,6 @@
+mod encoding;
+
+pub use encoding::*;
 use proto_pdk::{WarpgateTracingOptions, get_test_environment, initialize_tracing_with_options};
 use std::sync::atomic::{AtomicBool, Ordering};
 
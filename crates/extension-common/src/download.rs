@@ -1,11 +1,160 @@
+use common::percent_decode;
 use extism_pdk::debug;
-use moon_pdk::{AnyResult, VirtualPath, fetch_bytes};
+use moon_pdk::{AnyResult, VirtualPath, anyhow, fetch_bytes, fetch_text};
+use sha2::{Digest, Sha256};
 use starbase_utils::fs;
 
+/// Parse a checksum in the format of `sha256:<hex>`, and return the
+/// lowercased hex digest.
+pub fn parse_checksum(value: &str) -> AnyResult<String> {
+    let Some((algo, hash)) = value.trim().split_once(':') else {
+        return Err(anyhow!(
+            "Invalid checksum <id>{value}</id>, expected the format <id>sha256:<hex></id>."
+        ));
+    };
+
+    if !algo.eq_ignore_ascii_case("sha256") {
+        return Err(anyhow!(
+            "Unsupported checksum algorithm <id>{algo}</id>, only <id>sha256</id> is supported."
+        ));
+    }
+
+    validate_sha256(hash)
+}
+
+/// Fetch a checksum from a sidecar file, typically `<url>.sha256`. Supports both
+/// a bare digest, and the `<hex>  <file>` format output by `sha256sum`.
+pub fn fetch_checksum<U: AsRef<str>>(checksum_url: U) -> AnyResult<String> {
+    let url = checksum_url.as_ref();
+
+    debug!("Fetching checksum from <url>{}</url>", url);
+
+    let content = fetch_text(url)?;
+    let hash = content.split_whitespace().next().unwrap_or_default();
+
+    validate_sha256(hash.strip_prefix("sha256:").unwrap_or(hash))
+}
+
+fn validate_sha256(hash: &str) -> AnyResult<String> {
+    if hash.len() != 64 || !hash.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err(anyhow!(
+            "Invalid SHA-256 checksum <id>{hash}</id>, expected 64 hexadecimal characters."
+        ));
+    }
+
+    Ok(hash.to_lowercase())
+}
+
+/// Hash the provided bytes and compare against the expected SHA-256 digest.
+pub fn verify_checksum(url: &str, bytes: &[u8], expected: &str) -> AnyResult<()> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+
+    if actual != expected {
+        return Err(anyhow!(
+            "Checksum mismatch for <url>{url}</url>, expected <hash>{expected}</hash> but received <hash>{actual}</hash>. The file was not written."
+        ));
+    }
+
+    Ok(())
+}
+
+fn sanitize_file_name(name: &str) -> Option<String> {
+    // Never allow directories in the name, only the last component
+    let name = name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches('"');
+
+    if name.is_empty() || name == "." || name == ".." {
+        None
+    } else {
+        Some(name.to_owned())
+    }
+}
+
+/// Extract the file name from a `Content-Disposition` header value,
+/// preferring the extended `filename*` parameter when available.
+pub fn extract_file_name_from_disposition(header: &str) -> Option<String> {
+    let mut basic = None;
+
+    for param in header.split(';').skip(1) {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+
+        match key.trim().to_lowercase().as_str() {
+            "filename*" => {
+                // RFC 5987: charset'language'encoded-value
+                let value = value.trim();
+                let encoded = value.splitn(3, '\'').nth(2).unwrap_or(value);
+
+                if let Some(name) = sanitize_file_name(&percent_decode(encoded)) {
+                    return Some(name);
+                }
+            }
+            "filename" => {
+                basic = sanitize_file_name(value);
+            }
+            _ => {}
+        };
+    }
+
+    basic
+}
+
+/// Extract the file name from the last segment of the URL path,
+/// ignoring the query string and fragment.
+pub fn extract_file_name_from_url(url: &str) -> Option<String> {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+
+    // Remove the scheme and authority, so the host is never used as a name
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map(|(_, path)| path)?,
+        None => url,
+    };
+
+    path.split('/')
+        .rfind(|segment| !segment.is_empty())
+        .and_then(|segment| sanitize_file_name(&percent_decode(segment)))
+}
+
+fn detect_file_name(url: &str) -> AnyResult<String> {
+    // Host fetches do not expose response headers, but signed URLs (S3, GCS, etc)
+    // can define the `Content-Disposition` to respond with in the query string
+    let disposition = url
+        .split('#')
+        .next()
+        .and_then(|url| url.split_once('?'))
+        .and_then(|(_, query)| {
+            query.split('&').find_map(|pair| {
+                pair.split_once('=').and_then(|(key, value)| {
+                    key.eq_ignore_ascii_case("response-content-disposition")
+                        .then(|| percent_decode(&value.replace('+', " ")))
+                })
+            })
+        });
+
+    if let Some(name) = disposition
+        .as_deref()
+        .and_then(extract_file_name_from_disposition)
+    {
+        return Ok(name);
+    }
+
+    extract_file_name_from_url(url).ok_or_else(|| {
+        anyhow!(
+            "Unable to determine a file name from <url>{url}</url>, please provide one explicitly."
+        )
+    })
+}
+
 pub fn download_from_url<U: AsRef<str>, P: AsRef<VirtualPath>>(
     src_url: U,
     dst_dir: P,
     file_name: Option<&str>,
+    checksum: Option<&str>,
 ) -> AnyResult<VirtualPath> {
     let url = src_url.as_ref();
     let dir = dst_dir.as_ref();
@@ -13,11 +162,21 @@ pub fn download_from_url<U: AsRef<str>, P: AsRef<VirtualPath>>(
     debug!("Downloading file from <url>{}</url>", url);
 
     // Extract the file name from the URL
-    let file_name = file_name.unwrap_or_else(|| &url[url.rfind('/').unwrap() + 1..]);
+    let file_name = match file_name {
+        Some(name) => name.to_owned(),
+        None => detect_file_name(url)?,
+    };
 
     // Fetch the bytes of the URL
     let bytes = fetch_bytes(url)?;
 
+    // Verify the bytes before writing anything
+    if let Some(expected) = checksum {
+        verify_checksum(url, &bytes, expected)?;
+
+        debug!("Checksum <hash>{expected}</hash> verified");
+    }
+
     // Write the to the provided file
     let file = dir.join(file_name);
 
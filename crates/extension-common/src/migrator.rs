@@ -5,7 +5,49 @@ use moon_config::{
 };
 use moon_pdk::{AnyResult, VirtualPath};
 use rustc_hash::FxHashMap;
-use starbase_utils::yaml;
+use serde::Serialize;
+use similar::TextDiff;
+use starbase_utils::{fs, json, yaml};
+use std::path::{Path, PathBuf};
+
+/// A setting that could not be migrated, or was only partially migrated,
+/// and must be reviewed by hand.
+#[derive(Debug, Serialize)]
+pub struct MigrationWarning {
+    /// Source file, relative from the workspace root.
+    pub file: String,
+    /// JSON pointer to the setting within the source file.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl MigrationWarning {
+    pub fn new(file: impl AsRef<str>, pointer: impl AsRef<str>, message: impl AsRef<str>) -> Self {
+        Self {
+            file: file.as_ref().to_owned(),
+            pointer: pointer.as_ref().to_owned(),
+            message: message.as_ref().to_owned(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MigrationReport<'a> {
+    warnings: Vec<&'a MigrationWarning>,
+}
+
+pub struct ConfigPreview {
+    pub path: VirtualPath,
+    pub content: String,
+    pub diff: String,
+    pub exists: bool,
+}
+
+impl ConfigPreview {
+    pub fn has_changes(&self) -> bool {
+        !self.diff.is_empty()
+    }
+}
 
 pub struct Migrator {
     pub moon_config_dir: VirtualPath,
@@ -13,6 +55,7 @@ pub struct Migrator {
     pub root: VirtualPath,
     pub tasks_configs: FxHashMap<VirtualPath, PartialInheritedTasksConfig>,
     pub toolchain: Id,
+    pub warnings: Vec<MigrationWarning>,
     pub workspace_config: Option<PartialWorkspaceConfig>,
 }
 
@@ -27,6 +70,7 @@ impl Migrator {
             project_configs: FxHashMap::default(),
             tasks_configs: FxHashMap::default(),
             toolchain: Id::raw("node"),
+            warnings: vec![],
             workspace_config: None,
             root: workspace_root.to_owned(),
         })
@@ -49,6 +93,16 @@ impl Migrator {
         package_manager.to_owned()
     }
 
+    pub fn add_warning(
+        &mut self,
+        file: impl AsRef<str>,
+        pointer: impl AsRef<str>,
+        message: impl AsRef<str>,
+    ) {
+        self.warnings
+            .push(MigrationWarning::new(file, pointer, message));
+    }
+
     pub fn load_project_config(
         &mut self,
         project_source: &str,
@@ -130,28 +184,194 @@ impl Migrator {
         Ok(self.workspace_config.as_mut().unwrap())
     }
 
-    pub fn save_configs(&self) -> AnyResult<()> {
+    fn to_rel_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// Render all config files that will be written, sorted by path.
+    pub fn render_configs(&self) -> AnyResult<Vec<(VirtualPath, String)>> {
+        let mut configs = vec![];
+
         if let Some(workspace_config) = &self.workspace_config {
-            yaml::write_file_with_config(
-                self.moon_config_dir.join("workspace.yml"),
-                workspace_config,
-            )?;
+            let workspace_config_path = self.moon_config_dir.join("workspace.yml");
+
+            configs.push((
+                workspace_config_path.clone(),
+                render_config(&workspace_config_path, workspace_config)?,
+            ));
         }
 
         for (tasks_config_path, tasks_config) in &self.tasks_configs {
-            yaml::write_file_with_config(tasks_config_path, tasks_config)?;
+            configs.push((
+                tasks_config_path.clone(),
+                render_config(tasks_config_path, tasks_config)?,
+            ));
         }
 
         for (project_config_path, project_config) in &self.project_configs {
-            yaml::write_file_with_config(project_config_path, project_config)?;
+            configs.push((
+                project_config_path.clone(),
+                render_config(project_config_path, project_config)?,
+            ));
+        }
+
+        configs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(configs)
+    }
+
+    /// Render all config files that will be written, and diff them
+    /// against the files that currently exist, without touching disk.
+    pub fn preview_configs(&self) -> AnyResult<Vec<ConfigPreview>> {
+        let mut previews = vec![];
+
+        for (config_path, content) in self.render_configs()? {
+            let exists = config_path.exists();
+            let current = if exists {
+                fs::read_file(&config_path)?
+            } else {
+                String::new()
+            };
+            let rel_path = self.to_rel_path(&config_path);
+
+            previews.push(ConfigPreview {
+                diff: create_unified_diff(&rel_path, &current, &content, exists),
+                path: config_path,
+                content,
+                exists,
+            });
+        }
+
+        Ok(previews)
+    }
+
+    /// Describe the changes a migration would make, for dry runs:
+    /// which config files would be created or updated (with a diff),
+    /// and which source files would be removed.
+    pub fn describe_dry_run(&self, cleanup_paths: &[PathBuf]) -> AnyResult<Vec<String>> {
+        let mut messages = vec![];
+
+        for preview in self.preview_configs()? {
+            let rel_path = self.to_rel_path(&preview.path);
+
+            if !preview.has_changes() {
+                messages.push(format!("Unchanged <file>{rel_path}</file>"));
+                continue;
+            }
+
+            messages.push(format!(
+                "{} <file>{rel_path}</file>",
+                if preview.exists {
+                    "Would update"
+                } else {
+                    "Would create"
+                }
+            ));
+            messages.push(preview.diff);
+        }
+
+        for path in cleanup_paths {
+            messages.push(format!(
+                "Would remove <file>{}</file>",
+                self.to_rel_path(path)
+            ));
+        }
+
+        Ok(messages)
+    }
+
+    fn sorted_warnings(&self) -> Vec<&MigrationWarning> {
+        let mut warnings = self.warnings.iter().collect::<Vec<_>>();
+        warnings.sort_by(|a, b| (&a.file, &a.pointer).cmp(&(&b.file, &b.pointer)));
+        warnings
+    }
+
+    /// Describe all settings that could not be migrated, for a summary.
+    pub fn describe_warnings(&self) -> Vec<String> {
+        if self.warnings.is_empty() {
+            return vec![];
+        }
+
+        let mut messages = vec![format!(
+            "<caution>{} setting(s) could not be migrated and must be reviewed by hand:</caution>",
+            self.warnings.len()
+        )];
+
+        for warning in self.sorted_warnings() {
+            messages.push(format!(
+                "  <file>{}</file> <property>{}</property>: {}",
+                warning.file, warning.pointer, warning.message
+            ));
+        }
+
+        messages
+    }
+
+    /// Write all warnings as a JSON report to the provided path.
+    pub fn save_report(&self, report_path: &Path) -> AnyResult<()> {
+        json::write_file(
+            report_path,
+            &MigrationReport {
+                warnings: self.sorted_warnings(),
+            },
+            true,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn save_configs(&self) -> AnyResult<()> {
+        for (config_path, content) in self.render_configs()? {
+            fs::write_file(config_path, content)?;
         }
 
         Ok(())
     }
 }
 
+fn render_config<T: Serialize>(path: &VirtualPath, config: &T) -> AnyResult<String> {
+    let editor_config = fs::get_editor_config_props(path)?;
+
+    let mut content = yaml::format_with_identation(config, &editor_config.indent)?;
+    editor_config.apply_eof(&mut content);
+
+    Ok(content)
+}
+
+fn create_unified_diff(rel_path: &str, current: &str, next: &str, exists: bool) -> String {
+    if current == next {
+        return String::new();
+    }
+
+    TextDiff::from_lines(current, next)
+        .unified_diff()
+        .header(
+            &if exists {
+                format!("a/{rel_path}")
+            } else {
+                "/dev/null".into()
+            },
+            &format!("b/{rel_path}"),
+        )
+        .to_string()
+}
+
 pub fn create_id<T: AsRef<str>>(id: T) -> AnyResult<Id> {
     Ok(Id::clean(
         id.as_ref().replace(':', ".").trim_start_matches('@'),
     )?)
 }
+
+/// Create a JSON pointer (RFC 6901) from the provided keys.
+pub fn create_json_pointer<I, T>(keys: I) -> String
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    keys.into_iter()
+        .map(|key| format!("/{}", key.as_ref().replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
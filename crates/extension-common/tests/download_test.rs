@@ -0,0 +1,191 @@
+use extension_common::download::*;
+use extension_common::percent_decode;
+
+mod download {
+    use super::*;
+
+    // echo -n "hello world" | sha256sum
+    const HELLO_WORLD_SHA256: &str =
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    mod checksum {
+        use super::*;
+
+        #[test]
+        fn parses_sha256() {
+            assert_eq!(
+                parse_checksum(&format!("sha256:{}", HELLO_WORLD_SHA256.to_uppercase())).unwrap(),
+                HELLO_WORLD_SHA256
+            );
+            assert_eq!(
+                parse_checksum(&format!("SHA256:{HELLO_WORLD_SHA256}\n")).unwrap(),
+                HELLO_WORLD_SHA256
+            );
+        }
+
+        #[test]
+        fn errors_for_invalid_format() {
+            assert!(parse_checksum(HELLO_WORLD_SHA256).is_err());
+            assert!(parse_checksum("sha256:abc").is_err());
+            assert!(parse_checksum(&format!("sha512:{HELLO_WORLD_SHA256}")).is_err());
+        }
+
+        #[test]
+        fn verifies_matching_bytes() {
+            assert!(
+                verify_checksum(
+                    "https://domain.com/file",
+                    b"hello world",
+                    HELLO_WORLD_SHA256
+                )
+                .is_ok()
+            );
+        }
+
+        #[test]
+        fn errors_for_mismatched_bytes() {
+            let error =
+                verify_checksum("https://domain.com/file", b"hello moon", HELLO_WORLD_SHA256)
+                    .unwrap_err();
+
+            assert!(error.to_string().contains("Checksum mismatch"));
+        }
+    }
+
+    mod file_name_from_url {
+        use super::*;
+
+        #[test]
+        fn uses_last_segment() {
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/path/to/file.tar.gz").unwrap(),
+                "file.tar.gz"
+            );
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/path/to/file.tar.gz/").unwrap(),
+                "file.tar.gz"
+            );
+        }
+
+        #[test]
+        fn ignores_query_and_fragment() {
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/file.zip?token=a/b.txt#frag")
+                    .unwrap(),
+                "file.zip"
+            );
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/file.zip#a/b.txt").unwrap(),
+                "file.zip"
+            );
+        }
+
+        #[test]
+        fn decodes_segment() {
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/my%20file%2Bv1.txt").unwrap(),
+                "my file+v1.txt"
+            );
+        }
+
+        #[test]
+        fn never_uses_host() {
+            assert_eq!(extract_file_name_from_url("https://domain.com"), None);
+            assert_eq!(extract_file_name_from_url("https://domain.com/"), None);
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com?file=a.txt"),
+                None
+            );
+        }
+
+        #[test]
+        fn supports_urls_without_scheme() {
+            assert_eq!(extract_file_name_from_url("file.txt").unwrap(), "file.txt");
+            assert_eq!(
+                extract_file_name_from_url("dir/file.txt").unwrap(),
+                "file.txt"
+            );
+        }
+
+        #[test]
+        fn rejects_traversal() {
+            assert_eq!(extract_file_name_from_url("https://domain.com/.."), None);
+            assert_eq!(
+                extract_file_name_from_url("https://domain.com/%2E%2E%2Fetc%2Fpasswd").unwrap(),
+                "passwd"
+            );
+        }
+    }
+
+    mod file_name_from_disposition {
+        use super::*;
+
+        #[test]
+        fn uses_filename() {
+            assert_eq!(
+                extract_file_name_from_disposition("attachment; filename=file.zip").unwrap(),
+                "file.zip"
+            );
+            assert_eq!(
+                extract_file_name_from_disposition(r#"attachment; filename="my file.zip""#)
+                    .unwrap(),
+                "my file.zip"
+            );
+        }
+
+        #[test]
+        fn prefers_extended_filename() {
+            assert_eq!(
+                extract_file_name_from_disposition(
+                    "attachment; filename=\"fallback.zip\"; filename*=UTF-8''na%C3%AFve%20file.zip"
+                )
+                .unwrap(),
+                "naïve file.zip"
+            );
+            assert_eq!(
+                extract_file_name_from_disposition(
+                    "attachment; filename*=UTF-8'en'file.zip; filename=fallback.zip"
+                )
+                .unwrap(),
+                "file.zip"
+            );
+        }
+
+        #[test]
+        fn strips_directories() {
+            assert_eq!(
+                extract_file_name_from_disposition("attachment; filename=\"../../etc/passwd\"")
+                    .unwrap(),
+                "passwd"
+            );
+            assert_eq!(
+                extract_file_name_from_disposition(
+                    "attachment; filename*=UTF-8''..%2F..%2Fevil.sh"
+                )
+                .unwrap(),
+                "evil.sh"
+            );
+        }
+
+        #[test]
+        fn returns_none_without_filename() {
+            assert_eq!(extract_file_name_from_disposition("inline"), None);
+            assert_eq!(
+                extract_file_name_from_disposition("attachment; filename=\"\""),
+                None
+            );
+            assert_eq!(
+                extract_file_name_from_disposition("attachment; filename=.."),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn percent_decodes() {
+        assert_eq!(percent_decode("a%20b%3Ac"), "a b:c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode("%C3%AF"), "ï");
+    }
+}
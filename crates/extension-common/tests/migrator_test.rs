@@ -0,0 +1,83 @@
+use extension_common::migrator::Migrator;
+use moon_config::{PartialProjectConfig, PartialProjectMetadataConfig};
+use moon_pdk::VirtualPath;
+use starbase_sandbox::{assert_snapshot, create_empty_sandbox};
+
+mod migrator {
+    use super::*;
+
+    fn create_project_config(name: &str) -> PartialProjectConfig {
+        PartialProjectConfig {
+            project: Some(PartialProjectMetadataConfig {
+                title: Some(name.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn describes_dry_run() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("app/moon.yml", "project:\n  title: old\n");
+        sandbox.create_file("app/turbo.json", "{}");
+        sandbox.create_file("lib/turbo.json", "{}");
+
+        let root = VirtualPath::new(sandbox.path());
+        let mut migrator = Migrator::new(&root).unwrap();
+
+        migrator
+            .project_configs
+            .insert(root.join("app/moon.yml"), create_project_config("app"));
+        migrator
+            .project_configs
+            .insert(root.join("lib/moon.yml"), create_project_config("lib"));
+
+        let messages = migrator
+            .describe_dry_run(&[
+                sandbox.path().join("app/turbo.json"),
+                sandbox.path().join("lib/turbo.json"),
+            ])
+            .unwrap();
+
+        assert_snapshot!(messages.join("\n"));
+    }
+
+    #[test]
+    fn describes_unchanged_configs() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("app/moon.yml", "project:\n  title: app\n");
+
+        let root = VirtualPath::new(sandbox.path());
+        let mut migrator = Migrator::new(&root).unwrap();
+
+        migrator
+            .project_configs
+            .insert(root.join("app/moon.yml"), create_project_config("app"));
+
+        assert_eq!(
+            migrator.describe_dry_run(&[]).unwrap(),
+            ["Unchanged <file>app/moon.yml</file>"]
+        );
+    }
+
+    #[test]
+    fn diffs_existing_configs() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("app/moon.yml", "project:\n  title: old\n");
+
+        let root = VirtualPath::new(sandbox.path());
+        let mut migrator = Migrator::new(&root).unwrap();
+
+        migrator
+            .project_configs
+            .insert(root.join("app/moon.yml"), create_project_config("app"));
+
+        let previews = migrator.preview_configs().unwrap();
+
+        assert_eq!(previews.len(), 1);
+        assert!(previews[0].exists);
+        assert!(previews[0].has_changes());
+        assert_snapshot!(previews[0].diff);
+    }
+}
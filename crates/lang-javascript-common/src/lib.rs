@@ -1,5 +1,13 @@
 mod node_dist;
+mod npm_registry;
+mod npmrc;
 mod package_json;
+#[cfg(feature = "test-utils")]
+mod test_utils;
 
 pub use node_dist::*;
+pub use npm_registry::*;
+pub use npmrc::*;
 pub use package_json::*;
+#[cfg(feature = "test-utils")]
+pub use test_utils::*;
@@ -0,0 +1,126 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use proto_pdk_api::{AnyResult, Checksum};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct RegistryDist {
+    pub integrity: Option<String>, // Subresource Integrity
+    pub shasum: Option<String>,    // SHA-1 hex
+}
+
+impl RegistryDist {
+    /// Convert the integrity into a checksum that proto can verify.
+    /// Legacy SHA-1 hashes are not supported by proto, so are ignored.
+    pub fn get_checksum(&self) -> Option<Checksum> {
+        let integrity = self.integrity.as_deref()?;
+
+        // Multiple hashes may be provided, so prefer the strongest
+        for (prefix, create) in [
+            ("sha512-", Checksum::sha512 as fn(String) -> Checksum),
+            ("sha256-", Checksum::sha256),
+        ] {
+            if let Some(hash) = integrity
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix(prefix))
+                .and_then(|hash| BASE64.decode(hash).ok())
+            {
+                return Some(create(
+                    hash.iter().map(|byte| format!("{byte:02x}")).collect(),
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RegistryVersion {
+    #[serde(default)]
+    pub dist: RegistryDist,
+    pub version: String, // No v prefix
+}
+
+#[derive(Deserialize)]
+pub struct RegistryResponse {
+    pub name: String,
+    #[serde(rename = "dist-tags")]
+    pub dist_tags: FxHashMap<String, String>,
+    pub versions: FxHashMap<String, RegistryVersion>,
+}
+
+pub fn parse_registry_response(res_text: String, is_yarn: bool) -> AnyResult<RegistryResponse> {
+    parse_registry_json(res_text, is_yarn)
+}
+
+pub fn parse_registry_version(res_text: String, is_yarn: bool) -> AnyResult<RegistryVersion> {
+    parse_registry_json(res_text, is_yarn)
+}
+
+fn parse_registry_json<T: DeserializeOwned>(res_text: String, is_yarn: bool) -> AnyResult<T> {
+    if !is_yarn {
+        return Ok(serde_json::from_str(&res_text)?);
+    }
+
+    // https://github.com/moonrepo/proto/issues/257
+    let pattern = regex::bytes::Regex::new("[\u{0000}-\u{001F}]+").unwrap();
+    let body = res_text.as_bytes();
+
+    Ok(serde_json::from_slice(&pattern.replace_all(body, b""))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_dist(integrity: &str) -> RegistryDist {
+        RegistryDist {
+            integrity: Some(integrity.into()),
+            shasum: None,
+        }
+    }
+
+    #[test]
+    fn converts_sha512_integrity_to_hex() {
+        assert_eq!(
+            create_dist("sha512-MJ7MSJwS1utMxA9QyQLytNDtd+5RGnx6m808qG1M2G+YndNbxf9JlnDaNCVbRbDP2DDoH2Bdz33FVC6TrpzXbw==").get_checksum(),
+            Some(Checksum::sha512(
+                "309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn converts_sha256_integrity_to_hex() {
+        assert_eq!(
+            create_dist("sha256-uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=").get_checksum(),
+            Some(Checksum::sha256(
+                "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn prefers_sha512_when_multiple_hashes() {
+        assert_eq!(
+            create_dist("sha256-uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek= sha512-MJ7MSJwS1utMxA9QyQLytNDtd+5RGnx6m808qG1M2G+YndNbxf9JlnDaNCVbRbDP2DDoH2Bdz33FVC6TrpzXbw==").get_checksum(),
+            Some(Checksum::sha512(
+                "309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn ignores_legacy_and_invalid_integrity() {
+        assert_eq!(
+            create_dist("sha1-Kq5sNclPz7QV2+lfQIuc6R7oRu0=").get_checksum(),
+            None
+        );
+        assert_eq!(create_dist("sha512-not base64!").get_checksum(), None);
+        assert_eq!(RegistryDist::default().get_checksum(), None);
+    }
+}
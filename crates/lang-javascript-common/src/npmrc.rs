@@ -0,0 +1,37 @@
+use npmrc_config_rs::{Credentials, LoadOptions, NpmrcConfig};
+use proto_pdk_api::{AnyResult, VirtualPath};
+use rustc_hash::FxHashMap;
+
+/// Load `.npmrc` from the project (relative to the working directory),
+/// and from the user's home directory.
+pub fn load_npmrc(working_dir: &VirtualPath) -> AnyResult<NpmrcConfig> {
+    Ok(NpmrcConfig::load_with_options(LoadOptions {
+        cwd: Some(working_dir.into()),
+        global_prefix: None,
+        user_config: Some("/userhome/.npmrc".into()),
+        skip_project: false,
+        skip_user: false,
+        skip_global: true,
+    })?)
+}
+
+// https://github.com/npm/registry/blob/main/docs/user/authentication.md
+pub fn create_npm_auth_headers(credentials: Option<Credentials>) -> FxHashMap<String, String> {
+    let mut headers = FxHashMap::default();
+
+    if let Some(creds) = credentials {
+        match &creds {
+            Credentials::Token { token, .. } => {
+                headers.insert("Authorization".into(), format!("Bearer {token}"));
+            }
+            Credentials::BasicAuth { .. } | Credentials::LegacyAuth { .. } => {
+                if let Some(encoded) = creds.basic_auth_header() {
+                    headers.insert("Authorization".into(), format!("Basic {encoded}"));
+                }
+            }
+            Credentials::ClientCertOnly(_) => {}
+        };
+    }
+
+    headers
+}
@@ -0,0 +1,44 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Serve responses from a local npm registry, for testing. Each request is
+/// passed to the responder, which returns the status line and JSON body, and
+/// is recorded. Returns the registry URL and the recorded requests.
+pub fn serve_npm_registry(
+    respond: impl Fn(&str) -> (&'static str, String) + Send + 'static,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let registry_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let recorded = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+
+            while let Ok(count) = stream.read(&mut buffer) {
+                request.extend_from_slice(&buffer[..count]);
+
+                if count == 0 || request.ends_with(b"\r\n\r\n") {
+                    break;
+                }
+            }
+
+            let request = String::from_utf8_lossy(&request).into_owned();
+            let (status, body) = respond(&request);
+
+            recorded.lock().unwrap().push(request);
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    (registry_url, requests)
+}
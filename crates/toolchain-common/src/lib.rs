@@ -1 +1,4 @@
+mod project_graph;
+
 pub use common::*;
+pub use project_graph::*;
@@ -0,0 +1,46 @@
+use moon_config::DependencyScope;
+use moon_pdk_api::{ExtendProjectOutput, Id, ProjectDependency};
+use std::path::{Component, Path, PathBuf};
+
+/// Add a dependency on another project, unless it's a self-dependency,
+/// or the same dependency and scope has already been added.
+pub fn add_project_dependency(
+    output: &mut ExtendProjectOutput,
+    id: &Id,
+    dep_id: &Id,
+    scope: DependencyScope,
+    via: String,
+) {
+    if dep_id == id
+        || output
+            .dependencies
+            .iter()
+            .any(|dep| &dep.id == dep_id && dep.scope == scope)
+    {
+        return;
+    }
+
+    output.dependencies.push(ProjectDependency {
+        id: dep_id.to_owned(),
+        scope,
+        via: Some(via),
+    });
+}
+
+/// Resolve `.`/`..` components in a relative path without touching the
+/// filesystem, so `packages/a` + `../b` -> `packages/b`.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other.as_os_str()),
+        }
+    }
+
+    out
+}
@@ -1,4 +1,4 @@
-use extension_common::download::download_from_url;
+use extension_common::download::{download_from_url, fetch_checksum, parse_checksum};
 use extension_common::enable_tracing;
 use extism_pdk::*;
 use moon_pdk::*;
@@ -32,6 +32,12 @@ pub struct DownloadExtensionArgs {
 
     #[arg(long)]
     pub name: Option<String>,
+
+    #[arg(long)]
+    pub checksum: Option<String>,
+
+    #[arg(long, num_args = 0..=1, default_missing_value = "", conflicts_with = "checksum")]
+    pub checksum_url: Option<String>,
 }
 
 #[plugin_fn]
@@ -42,6 +48,21 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
         return Err(plugin_err!("A valid URL is required for downloading."));
     }
 
+    // Determine the expected checksum before downloading
+    let checksum = if let Some(checksum) = &args.checksum {
+        Some(parse_checksum(checksum)?)
+    } else if let Some(checksum_url) = &args.checksum_url {
+        let checksum_url = if checksum_url.is_empty() {
+            format!("{}.sha256", args.url)
+        } else {
+            checksum_url.to_owned()
+        };
+
+        Some(fetch_checksum(checksum_url)?)
+    } else {
+        None
+    };
+
     // Determine destination directory
     debug!("Determining destination directory");
 
@@ -60,7 +81,16 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
     // Attempt to download the file
     host_log!(stdout, "Downloading <url>{}</url>", args.url);
 
-    let dest_file = download_from_url(&args.url, &dest_dir, args.name.as_deref())?;
+    let dest_file = download_from_url(
+        &args.url,
+        &dest_dir,
+        args.name.as_deref(),
+        checksum.as_deref(),
+    )?;
+
+    if checksum.is_some() {
+        host_log!(stdout, "Verified SHA-256 checksum");
+    }
 
     host_log!(stdout, "Downloaded to <path>{dest_file}</path>",);
 
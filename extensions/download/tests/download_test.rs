@@ -1,5 +1,28 @@
 use moon_pdk_test_utils::{ExecuteExtensionInput, create_empty_moon_sandbox};
 use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+// Serves the same body for every request on a local port
+fn serve_file(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    url
+}
 
 mod download_extension {
     use super::*;
@@ -114,4 +137,186 @@ mod download_extension {
 
         assert!(sandbox.path().join("sub/dir/moon.md").exists());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn downloads_file_without_query_in_name() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md?raw=true#intro"
+                        .into(),
+                    "--dest".into(),
+                    ".".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("README.md").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Unable to determine a file name")]
+    async fn errors_if_no_name_in_url() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--url".into(), "https://moonrepo.dev?file=README.md".into()],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Invalid checksum")]
+    async fn errors_if_checksum_format_invalid() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                    "--checksum".into(),
+                    "abc123".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Unsupported checksum algorithm")]
+    async fn errors_if_checksum_algo_unsupported() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                    "--checksum".into(),
+                    "md5:d41d8cd98f00b204e9800998ecf8427e".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "cannot be used with")]
+    async fn errors_if_both_checksum_modes() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                    "--checksum".into(),
+                    format!("sha256:{}", "0".repeat(64)),
+                    "--checksum-url".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Checksum mismatch")]
+    async fn errors_if_checksum_mismatch() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                    "--checksum".into(),
+                    format!("sha256:{}", "0".repeat(64)),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Invalid SHA-256 checksum")]
+    async fn errors_if_checksum_sidecar_invalid() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                    "--checksum-url".into(),
+                    "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn downloads_file_with_matching_checksum() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    format!("{}/hello", serve_file("hello world")),
+                    "--name".into(),
+                    "hello.txt".into(),
+                    "--checksum".into(),
+                    "sha256:b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+                        .into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("hello.txt")).unwrap(),
+            "hello world"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn downloads_file_with_checksum_sidecar() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--url".into(),
+                    "https://github.com/moonrepo/proto/releases/download/v0.44.0/proto_cli-x86_64-unknown-linux-gnu.tar.xz".into(),
+                    // Defaults to <url>.sha256
+                    "--checksum-url".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(
+            sandbox
+                .path()
+                .join("proto_cli-x86_64-unknown-linux-gnu.tar.xz")
+                .exists()
+        );
+    }
 }
@@ -68,10 +68,23 @@ impl NxMigrator {
     }
 
     pub fn migrate_root_config(&mut self, nx_json: NxJson) -> AnyResult<()> {
-        if let Some(default_branch) = nx_json
-            .default_base
-            .or_else(|| nx_json.affected.and_then(|aff| aff.default_base))
+        let file = "nx.json";
+        let mut warnings = vec![];
+        let affected_base = nx_json.affected.and_then(|aff| aff.default_base);
+
+        if let (Some(base), Some(affected_base)) = (&nx_json.default_base, &affected_base)
+            && base != affected_base
         {
+            warnings.push(MigrationWarning::new(
+                file,
+                "/affected/defaultBase",
+                format!(
+                    "Conflicts with defaultBase, so {base} was used as the VCS default branch instead of {affected_base}."
+                ),
+            ));
+        }
+
+        if let Some(default_branch) = nx_json.default_base.or(affected_base) {
             self.inner
                 .load_workspace_config()?
                 .vcs
@@ -89,7 +102,13 @@ impl NxMigrator {
                 .get_or_insert(FxHashMap::default());
 
             for (name, raw_inputs) in named_inputs {
-                let group = migrate_inputs(&raw_inputs, true)?;
+                let group = migrate_inputs(
+                    &raw_inputs,
+                    true,
+                    &mut warnings,
+                    file,
+                    &create_json_pointer(["namedInputs", &name]),
+                )?;
 
                 if !group.is_empty() {
                     file_groups.insert(create_id(name)?, group);
@@ -105,10 +124,15 @@ impl NxMigrator {
                 .get_or_insert(BTreeMap::default());
 
             for (name, target_config) in target_defaults {
-                tasks.insert(
-                    create_id(name)?,
-                    migrate_task(&target_config, &self.package_manager)?,
-                );
+                let task = migrate_task(
+                    &target_config,
+                    &self.package_manager,
+                    &mut warnings,
+                    file,
+                    &create_json_pointer(["targetDefaults", &name]),
+                )?;
+
+                tasks.insert(create_id(name)?, task);
             }
         }
 
@@ -123,6 +147,8 @@ impl NxMigrator {
             }
         }
 
+        self.inner.warnings.extend(warnings);
+
         Ok(())
     }
 
@@ -146,6 +172,22 @@ impl NxMigrator {
         project_source: &str,
         project_json: NxProjectJson,
     ) -> AnyResult<()> {
+        self.migrate_project(
+            project_source,
+            project_json,
+            &create_source_file(project_source, "project.json"),
+            "",
+        )
+    }
+
+    fn migrate_project(
+        &mut self,
+        project_source: &str,
+        project_json: NxProjectJson,
+        file: &str,
+        pointer: &str,
+    ) -> AnyResult<()> {
+        let mut warnings = vec![];
         let config = self.inner.load_project_config(project_source)?;
 
         if let Some(name) = project_json.name {
@@ -168,7 +210,13 @@ impl NxMigrator {
             let file_groups = config.file_groups.get_or_insert(FxHashMap::default());
 
             for (name, raw_inputs) in named_inputs {
-                let group = migrate_inputs(&raw_inputs, true)?;
+                let group = migrate_inputs(
+                    &raw_inputs,
+                    true,
+                    &mut warnings,
+                    file,
+                    &format!("{pointer}{}", create_json_pointer(["namedInputs", &name])),
+                )?;
 
                 if !group.is_empty() {
                     file_groups.insert(create_id(name)?, group);
@@ -196,12 +244,16 @@ impl NxMigrator {
             let tasks = config.tasks.get_or_insert(BTreeMap::default());
 
             for (name, target) in targets {
-                let task_id = create_id(name)?;
+                let task_id = create_id(&name)?;
+                let task = migrate_task(
+                    &target,
+                    &self.package_manager,
+                    &mut warnings,
+                    file,
+                    &format!("{pointer}{}", create_json_pointer(["targets", &name])),
+                )?;
 
-                tasks.insert(
-                    task_id.clone(),
-                    migrate_task(&target, &self.package_manager)?,
-                );
+                tasks.insert(task_id.clone(), task);
 
                 // https://nx.dev/concepts/executors-and-configurations#use-task-configurations
                 if let Some(configurations) = target.configurations {
@@ -230,6 +282,8 @@ impl NxMigrator {
                 .extend(metadata);
         }
 
+        self.inner.warnings.extend(warnings);
+
         Ok(())
     }
 
@@ -239,13 +293,26 @@ impl NxMigrator {
         nx_package_json: PackageJsonWithNx,
     ) -> AnyResult<()> {
         if let Some(nx) = nx_package_json.nx {
-            self.migrate_project_config(project_source, nx)?;
+            self.migrate_project(
+                project_source,
+                nx,
+                &create_source_file(project_source, "package.json"),
+                "/nx",
+            )?;
         }
 
         Ok(())
     }
 }
 
+fn create_source_file(project_source: &str, file_name: &str) -> String {
+    if project_source.is_empty() {
+        file_name.to_owned()
+    } else {
+        format!("{project_source}/{file_name}")
+    }
+}
+
 fn is_path_or_glob(value: &str) -> bool {
     (value.contains('/')
         || value.contains('*')
@@ -286,16 +353,30 @@ fn replace_tokens(value: &str, for_sources: bool) -> String {
     result
 }
 
-fn migrate_inputs(raw_inputs: &[NxInput], for_file_groups: bool) -> AnyResult<Vec<Input>> {
+fn migrate_inputs(
+    raw_inputs: &[NxInput],
+    for_file_groups: bool,
+    warnings: &mut Vec<MigrationWarning>,
+    file: &str,
+    pointer: &str,
+) -> AnyResult<Vec<Input>> {
     let mut inputs = vec![];
 
-    for input in raw_inputs {
+    for (index, input) in raw_inputs.iter().enumerate() {
         match input {
-            NxInput::Dep { .. } => {
-                // Not supported
+            NxInput::Dep { input, .. } => {
+                warnings.push(MigrationWarning::new(
+                    file,
+                    format!("{pointer}/{index}"),
+                    format!("Inputs from dependencies ({input}) are not supported."),
+                ));
             }
             NxInput::DepOutput { .. } => {
-                // Not supported
+                warnings.push(MigrationWarning::new(
+                    file,
+                    format!("{pointer}/{index}"),
+                    "Output files of dependent tasks are not supported as inputs.",
+                ));
             }
             NxInput::External { .. } => {
                 // Not supported, moon parses lockfiles automatically
@@ -323,8 +404,16 @@ fn migrate_inputs(raw_inputs: &[NxInput], for_file_groups: bool) -> AnyResult<Ve
                         inputs.push(Input::parse(path)?);
                     }
                 }
+                // Named input of dependencies
+                else if source.starts_with('^') {
+                    warnings.push(MigrationWarning::new(
+                        file,
+                        format!("{pointer}/{index}"),
+                        format!("Named inputs from dependencies ({source}) are not supported."),
+                    ));
+                }
                 // Named input
-                else if !source.starts_with('^') && !for_file_groups {
+                else if !for_file_groups {
                     inputs.push(Input::TokenFunc(format!("@group({source})")));
                 }
             }
@@ -483,6 +572,9 @@ fn migrate_run_script_task(
 fn migrate_task(
     nx_target: &NxTargetOptions,
     package_manager: &str,
+    warnings: &mut Vec<MigrationWarning>,
+    file: &str,
+    pointer: &str,
 ) -> AnyResult<PartialTaskConfig> {
     let mut inject_args = false;
 
@@ -502,14 +594,24 @@ fn migrate_task(
                 package = &package[index + 1..];
             }
 
+            let command = if package == target {
+                target.to_owned()
+            } else {
+                format!("{package} {target}")
+            };
+
+            warnings.push(MigrationWarning::new(
+                file,
+                format!("{pointer}/executor"),
+                format!(
+                    "Executor {executor} is not supported, so the command was guessed as \"{command}\", verify that it's correct."
+                ),
+            ));
+
             inject_args = true;
 
             PartialTaskConfig {
-                command: Some(PartialTaskArgs::String(if package == target {
-                    target.to_owned()
-                } else {
-                    format!("{package} {target}")
-                })),
+                command: Some(PartialTaskArgs::String(command)),
                 ..Default::default()
             }
         }
@@ -601,7 +703,13 @@ fn migrate_task(
     let mut inputs = vec![];
 
     if let Some(raw_inputs) = &nx_target.inputs {
-        inputs.extend(migrate_inputs(raw_inputs, false)?);
+        inputs.extend(migrate_inputs(
+            raw_inputs,
+            false,
+            warnings,
+            file,
+            &format!("{pointer}/inputs"),
+        )?);
     }
 
     if !inputs.is_empty() {
@@ -629,6 +737,16 @@ fn migrate_task(
             .cache = nx_target.cache.map(TaskOptionCache::Enabled);
     }
 
+    if let Some(default_config) = &nx_target.default_configuration {
+        warnings.push(MigrationWarning::new(
+            file,
+            format!("{pointer}/defaultConfiguration"),
+            format!(
+                "Default configurations are not supported, run the {default_config} configuration task variant explicitly."
+            ),
+        ));
+    }
+
     if nx_target.continuous == Some(true) {
         config
             .options
@@ -41,6 +41,41 @@ mod migrate_nx_extension {
         assert_snapshot!(fs::read_to_string(sandbox.path().join("moon.yml")).unwrap());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn doesnt_write_or_remove_files_in_dry_run() {
+        let sandbox = create_moon_sandbox("root");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--cleanup".into(), "--dry-run".into()],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("nx.json").exists());
+        assert!(sandbox.path().join("workspace.json").exists());
+        assert!(!sandbox.path().join(".moon/tasks/node.yml").exists());
+        assert!(!sandbox.path().join(".moon/workspace.yml").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn writes_a_report_of_unsupported_settings() {
+        let sandbox = create_moon_sandbox("unsupported");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--report".into()],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("migration-report.json").exists());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("migration-report.json")).unwrap());
+    }
+
     mod nx_json {
         use super::*;
 
@@ -29,6 +29,10 @@ pub struct MigrateTurborepoExtensionArgs {
     pub bun: bool,
     #[arg(long)]
     pub cleanup: bool,
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long)]
+    pub report: bool,
 }
 
 #[plugin_fn]
@@ -36,6 +40,7 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
     let args = parse_args::<MigrateTurborepoExtensionArgs>(&input.args)?;
     let workspace_root = &input.context.workspace_root;
     let mut migrator = TurboMigrator::new(&input.context, args.bun)?;
+    let mut cleanup_paths = vec![];
 
     // Migrate the workspace root config first
     let root_config_path = workspace_root.join("turbo.json");
@@ -46,7 +51,7 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
         migrator.migrate_root_config(json::read_file(&root_config_path)?)?;
 
         if args.cleanup {
-            fs::remove(root_config_path)?;
+            cleanup_paths.push(root_config_path.into_inner());
         }
     }
 
@@ -66,13 +71,50 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
         migrator.migrate_project_config(&project_source, json::read_file(&project_config_path)?)?;
 
         if args.cleanup {
-            fs::remove(project_config_path)?;
+            cleanup_paths.push(project_config_path);
+        }
+    }
+
+    // Preview the new config files
+    if args.dry_run {
+        for message in migrator.inner.describe_dry_run(&cleanup_paths)? {
+            host_log!(stdout, "{message}");
         }
+
+        for message in migrator.inner.describe_warnings() {
+            host_log!(stdout, "{message}");
+        }
+
+        host_log!(
+            stdout,
+            "Dry run complete, no files were written or removed!"
+        );
+
+        return Ok(());
     }
 
     // Write the new config files
     migrator.inner.save_configs()?;
 
+    for path in cleanup_paths {
+        fs::remove(path)?;
+    }
+
+    for message in migrator.inner.describe_warnings() {
+        host_log!(stdout, "{message}");
+    }
+
+    if args.report {
+        migrator
+            .inner
+            .save_report(&workspace_root.join("migration-report.json"))?;
+
+        host_log!(
+            stdout,
+            "Wrote migration report to <file>migration-report.json</file>"
+        );
+    }
+
     host_log!(stdout, "Successfully migrated from Turborepo to moon!");
 
     Ok(())
@@ -3,7 +3,7 @@
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum TurboEnvMode {
     Loose,
@@ -11,7 +11,7 @@ pub enum TurboEnvMode {
     Strict,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum TurboOutputMode {
     #[default]
@@ -31,7 +31,7 @@ pub enum TurboUi {
 }
 
 // Future: interruptible, with
-#[derive(Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TurboTask {
     pub cache: Option<bool>,
@@ -49,6 +49,46 @@ pub struct TurboTask {
     pub output_mode: Option<TurboOutputMode>,
 }
 
+impl TurboTask {
+    /// Apply the provided task on top of the current task, following
+    /// Turborepo's `extends` semantics: fields that are defined replace
+    /// the base value, unless a list contains `$TURBO_EXTENDS$`,
+    /// in which case the lists are concatenated.
+    pub fn extend(self, other: TurboTask) -> TurboTask {
+        TurboTask {
+            cache: other.cache.or(self.cache),
+            depends_on: extend_list(self.depends_on, other.depends_on),
+            env: extend_list(self.env, other.env),
+            inputs: extend_list(self.inputs, other.inputs),
+            outputs: extend_list(self.outputs, other.outputs),
+            pass_through_env: extend_list(self.pass_through_env, other.pass_through_env),
+            persistent: other.persistent.or(self.persistent),
+            interactive: other.interactive.or(self.interactive),
+            output_logs: other.output_logs.or(self.output_logs),
+            dot_env: extend_list(self.dot_env, other.dot_env),
+            output_mode: other.output_mode.or(self.output_mode),
+        }
+    }
+}
+
+fn extend_list(base: Option<Vec<String>>, other: Option<Vec<String>>) -> Option<Vec<String>> {
+    match other {
+        Some(list) if list.iter().any(|item| item == "$TURBO_EXTENDS$") => {
+            let mut result = base.unwrap_or_default();
+
+            for item in list {
+                if item != "$TURBO_EXTENDS$" && !result.contains(&item) {
+                    result.push(item);
+                }
+            }
+
+            Some(result)
+        }
+        Some(list) => Some(list),
+        None => base,
+    }
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TurboJson {
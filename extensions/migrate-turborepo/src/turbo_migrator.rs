@@ -2,14 +2,14 @@ use crate::turbo_json::*;
 use extension_common::migrator::*;
 use moon_common::Id;
 use moon_config::{
-    FilePath, Input, Output, PartialTaskArgs, PartialTaskConfig, PartialTaskDependency,
-    PartialTaskOptionsConfig, PartialWorkspaceProjects, TaskOptionCache, TaskOptionEnvFile,
-    TaskOutputStyle, TaskPreset,
+    FilePath, Input, MergeStrategy, Output, PartialTaskArgs, PartialTaskConfig,
+    PartialTaskDependency, PartialTaskOptionsConfig, PartialWorkspaceProjects, TaskOptionCache,
+    TaskOptionEnvFile, TaskOutputStyle, TaskPreset,
 };
 use moon_pdk::*;
 use moon_pdk_api::MoonContext;
 use moon_target::Target;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use starbase_utils::{fs, glob, json};
 use std::collections::BTreeMap;
 use std::str::FromStr;
@@ -20,11 +20,19 @@ pub struct PackageEntry {
     source: String,
 }
 
+pub struct ExtendedTask {
+    task: TurboTask,
+    // Whether a root-level global task contributed to this definition,
+    // which moon will also inherit through the tasks config
+    inherits_root: bool,
+}
+
 pub struct TurboMigrator {
     pub inner: Migrator,
     pub package_manager: String,
     pub package_globs: FxHashSet<String>,
     pub packages: Vec<PackageEntry>,
+    pub root_tasks: FxHashMap<String, TurboTask>,
 }
 
 impl TurboMigrator {
@@ -67,6 +75,7 @@ impl TurboMigrator {
             inner: migrator,
             package_globs,
             packages,
+            root_tasks: FxHashMap::default(),
         })
     }
 
@@ -91,9 +100,140 @@ impl TurboMigrator {
         ))
     }
 
+    fn find_project_in_sources(&self, project_source: &str) -> Option<&PackageEntry> {
+        self.packages
+            .iter()
+            .find(|package| package.source == project_source)
+    }
+
+    fn migrate_env_settings(&mut self, turbo_json: &mut TurboJson, file: &str) {
+        // moon does not filter environment variables, so every variable is
+        // available to a task (like loose mode), and variables are only
+        // hashed when they're an explicit input. This means pass-through
+        // variables require no configuration, but strict mode can't be enforced.
+        turbo_json.global_pass_through_env.take();
+
+        if turbo_json.env_mode.take() == Some(TurboEnvMode::Strict) {
+            self.inner.add_warning(
+                file,
+                "/envMode",
+                "Strict mode is not supported, moon does not filter environment variables for tasks.",
+            );
+        }
+
+        if let Some(cache_dir) = turbo_json.cache_dir.take() {
+            self.inner.add_warning(
+                file,
+                "/cacheDir",
+                format!(
+                    "Custom cache directory {cache_dir} is not supported, moon always caches within its own cache directory."
+                ),
+            );
+        }
+    }
+
+    // Tasks merged through `extends` are reported by the file that defines them,
+    // so only a file's own task definitions are checked here
+    fn migrate_task_settings(&mut self, turbo_json: &TurboJson, file: &str) {
+        let (pipeline_key, pipeline) = match (&turbo_json.tasks, &turbo_json.pipeline) {
+            (Some(tasks), _) => ("tasks", tasks),
+            (None, Some(pipeline)) => ("pipeline", pipeline),
+            (None, None) => return,
+        };
+
+        for (script, task) in pipeline {
+            let Some(inputs) = &task.inputs else {
+                continue;
+            };
+
+            for (index, input) in inputs.iter().enumerate() {
+                if input == "$TURBO_DEFAULT$" {
+                    self.inner.add_warning(
+                        file,
+                        create_json_pointer([pipeline_key, script, "inputs", &index.to_string()]),
+                        "Default inputs ($TURBO_DEFAULT$) are not supported, add the project files to inputs explicitly.",
+                    );
+                }
+            }
+        }
+    }
+
+    fn resolve_extended_tasks(
+        &self,
+        project_source: &str,
+        extends: &[String],
+        visited: &mut FxHashSet<String>,
+    ) -> AnyResult<BTreeMap<String, ExtendedTask>> {
+        let mut tasks = BTreeMap::<String, ExtendedTask>::default();
+
+        for name in extends {
+            // Extending the root config
+            if name == "//" {
+                let package = self.find_project_in_sources(project_source);
+
+                for (script, task) in &self.root_tasks {
+                    if !script.contains('#') {
+                        inherit_task(&mut tasks, script.to_owned(), task.clone(), true);
+                    }
+                }
+
+                // Package-scoped tasks take precedence over global tasks
+                for (script, task) in &self.root_tasks {
+                    if let Some((package_name, script_name)) = script.split_once('#')
+                        && package.is_some_and(|package| {
+                            package.id == package_name || package.alias == package_name
+                        })
+                    {
+                        inherit_task(&mut tasks, script_name.to_owned(), task.clone(), false);
+                    }
+                }
+
+                continue;
+            }
+
+            // Extending another package config
+            let package = self.find_project_in_packages(name)?;
+
+            if !visited.insert(package.source.clone()) {
+                return Err(anyhow!(
+                    "Unable to migrate <file>turbo.json</file> as package <id>{name}</id> is extended in a cycle."
+                ));
+            }
+
+            let config_path = self.inner.root.join(&package.source).join("turbo.json");
+
+            if !config_path.exists() {
+                return Err(anyhow!(
+                    "Unable to migrate <file>turbo.json</file> as package <id>{name}</id> does not have a <file>turbo.json</file> to extend."
+                ));
+            }
+
+            let mut turbo_json: TurboJson = json::read_file(&config_path)?;
+
+            if let Some(package_extends) = turbo_json.extends.take() {
+                for (script, entry) in
+                    self.resolve_extended_tasks(project_source, &package_extends, visited)?
+                {
+                    inherit_task(&mut tasks, script, entry.task, entry.inherits_root);
+                }
+            }
+
+            for (script, task) in turbo_json.tasks.or(turbo_json.pipeline).unwrap_or_default() {
+                inherit_task(&mut tasks, script, task, false);
+            }
+
+            visited.remove(&package.source);
+        }
+
+        Ok(tasks)
+    }
+
     pub fn migrate_root_config(&mut self, mut turbo_json: TurboJson) -> AnyResult<()> {
         let mut implicit_inputs = vec![];
 
+        self.migrate_env_settings(&mut turbo_json, "turbo.json");
+        self.migrate_task_settings(&turbo_json, "turbo.json");
+
         if let Some(global_deps) = turbo_json.global_dependencies.take() {
             for dep in global_deps {
                 implicit_inputs.push(Input::parse(&dep)?);
@@ -130,6 +270,11 @@ impl TurboMigrator {
             }
         }
 
+        // Keep the root tasks around for packages that extend them
+        if let Some(pipeline) = turbo_json.tasks.as_ref().or(turbo_json.pipeline.as_ref()) {
+            self.root_tasks = pipeline.clone();
+        }
+
         self.migrate_pipeline(turbo_json, None)?;
 
         Ok(())
@@ -138,9 +283,67 @@ impl TurboMigrator {
     pub fn migrate_project_config(
         &mut self,
         project_source: &str,
-        turbo_json: TurboJson,
+        mut turbo_json: TurboJson,
     ) -> AnyResult<()> {
-        self.migrate_pipeline(turbo_json, Some(project_source))
+        let file = format!("{project_source}/turbo.json");
+
+        self.migrate_env_settings(&mut turbo_json, &file);
+        self.migrate_task_settings(&turbo_json, &file);
+
+        let Some(extends) = turbo_json.extends.take() else {
+            return self.migrate_pipeline(turbo_json, Some(project_source));
+        };
+
+        // Merge the extended task definitions with the package overrides,
+        // so that the resulting moon task is complete
+        let uses_pipeline = turbo_json.tasks.is_none() && turbo_json.pipeline.is_some();
+        let mut pipeline = turbo_json
+            .tasks
+            .take()
+            .or(turbo_json.pipeline.take())
+            .unwrap_or_default();
+        let mut replace_inherited = vec![];
+
+        let mut visited = FxHashSet::default();
+        visited.insert(project_source.to_owned());
+
+        for (script, base) in self.resolve_extended_tasks(project_source, &extends, &mut visited)? {
+            if let Some(task) = pipeline.remove(&script) {
+                if base.inherits_root {
+                    replace_inherited.push(create_id(&script)?);
+                }
+
+                pipeline.insert(script, base.task.extend(task));
+            }
+            // Root tasks are already inherited or created, while tasks
+            // from other packages must be created for this package
+            else if !base.inherits_root {
+                pipeline.insert(script, base.task);
+            }
+        }
+
+        if uses_pipeline {
+            turbo_json.pipeline = Some(pipeline);
+        } else {
+            turbo_json.tasks = Some(pipeline);
+        }
+
+        self.migrate_pipeline(turbo_json, Some(project_source))?;
+
+        // The merged task is complete, so it must replace the inherited task
+        if !replace_inherited.is_empty()
+            && let Some(tasks) = &mut self.inner.load_project_config(project_source)?.tasks
+        {
+            for task_id in replace_inherited {
+                if let Some(task) = tasks.get_mut(&task_id) {
+                    task.options
+                        .get_or_insert(PartialTaskOptionsConfig::default())
+                        .merge = Some(MergeStrategy::Replace);
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub fn migrate_pipeline(
@@ -360,3 +563,23 @@ impl TurboMigrator {
         Ok(config)
     }
 }
+
+fn inherit_task(
+    tasks: &mut BTreeMap<String, ExtendedTask>,
+    script: String,
+    task: TurboTask,
+    inherits_root: bool,
+) {
+    let entry = match tasks.remove(&script) {
+        Some(base) => ExtendedTask {
+            task: base.task.extend(task),
+            inherits_root: base.inherits_root || inherits_root,
+        },
+        None => ExtendedTask {
+            task,
+            inherits_root,
+        },
+    };
+
+    tasks.insert(script, entry);
+}
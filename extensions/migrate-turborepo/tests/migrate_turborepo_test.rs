@@ -93,6 +93,55 @@ mod migrate_turborepo_extension {
         assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/tasks/node.yml")).unwrap());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn merges_extended_tasks() {
+        let sandbox = create_moon_sandbox("extends");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/tasks/node.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/app/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/lib/moon.yml")).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn doesnt_write_or_remove_files_in_dry_run() {
+        let sandbox = create_moon_sandbox("monorepo");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--cleanup".into(), "--dry-run".into()],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("turbo.json").exists());
+        assert!(sandbox.path().join("client/turbo.json").exists());
+        assert!(sandbox.path().join("server/turbo.json").exists());
+        assert!(!sandbox.path().join(".moon/tasks/node.yml").exists());
+        assert!(!sandbox.path().join("client/moon.yml").exists());
+        assert!(!sandbox.path().join("server/moon.yml").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn writes_a_report_of_unsupported_settings() {
+        let sandbox = create_moon_sandbox("extends");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--report".into()],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("migration-report.json").exists());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("migration-report.json")).unwrap());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn supports_no_pipeline() {
         let sandbox = create_moon_sandbox("missing-pipeline");
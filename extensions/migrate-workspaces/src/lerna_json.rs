@@ -0,0 +1,16 @@
+// https://lerna.js.org/docs/api-reference/configuration
+
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use starbase_utils::json::JsonValue;
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LernaJson {
+    pub command: Option<JsonValue>,
+    pub npm_client: Option<String>,
+    pub packages: Option<Vec<String>>,
+    // v6 (removed)
+    pub use_workspaces: Option<bool>,
+}
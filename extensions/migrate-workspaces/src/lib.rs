@@ -0,0 +1,9 @@
+mod lerna_json;
+#[cfg(feature = "wasm")]
+mod migrate_workspaces_ext;
+mod package_json;
+mod rush_json;
+mod workspaces_migrator;
+
+#[cfg(feature = "wasm")]
+pub use migrate_workspaces_ext::*;
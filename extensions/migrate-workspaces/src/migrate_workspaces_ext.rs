@@ -0,0 +1,153 @@
+use crate::rush_json::RushCommandLineJson;
+use crate::workspaces_migrator::WorkspacesMigrator;
+use extension_common::enable_tracing;
+use extism_pdk::*;
+use moon_pdk::*;
+use moon_pdk_api::{ExecuteExtensionInput, RegisterExtensionInput, RegisterExtensionOutput};
+use starbase_utils::{fs, json, yaml};
+
+#[host_fn]
+extern "ExtismHost" {
+    fn host_log(input: Json<HostLogInput>);
+}
+
+#[plugin_fn]
+pub fn register_extension(
+    Json(_): Json<RegisterExtensionInput>,
+) -> FnResult<Json<RegisterExtensionOutput>> {
+    enable_tracing();
+
+    Ok(Json(RegisterExtensionOutput {
+        name: "Migrate workspaces".into(),
+        description: Some("Migrate a Lerna, Rush or pnpm workspace repository to moon by converting <file>lerna.json</file>, <file>rush.json</file> or <file>pnpm-workspace.yaml</file> and all <file>package.json</file> files into moon configuration files.".into()),
+        plugin_version: env!("CARGO_PKG_VERSION").into(),
+    }))
+}
+
+#[derive(Args)]
+pub struct MigrateWorkspacesExtensionArgs {
+    #[arg(long)]
+    pub cleanup: bool,
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long)]
+    pub report: bool,
+}
+
+#[plugin_fn]
+pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<()> {
+    let args = parse_args::<MigrateWorkspacesExtensionArgs>(&input.args)?;
+    let workspace_root = &input.context.workspace_root;
+    let mut migrator = WorkspacesMigrator::new(&input.context)?;
+    let mut cleanup_paths = vec![];
+
+    let rush_config_path = workspace_root.join("rush.json");
+    let lerna_config_path = workspace_root.join("lerna.json");
+    let pnpm_config_path = workspace_root.join("pnpm-workspace.yaml");
+
+    let is_rush = rush_config_path.exists();
+
+    // Load the workspace projects first
+    if is_rush {
+        host_log!(stdout, "Migrating root config <file>rush.json</file>");
+
+        migrator.migrate_rush_config(json::read_file(&rush_config_path)?)?;
+
+        if args.cleanup {
+            cleanup_paths.push(rush_config_path.into_inner());
+        }
+    } else if lerna_config_path.exists() {
+        host_log!(stdout, "Migrating root config <file>lerna.json</file>");
+
+        migrator.migrate_lerna_config(json::read_file(&lerna_config_path)?)?;
+
+        if args.cleanup {
+            cleanup_paths.push(lerna_config_path.into_inner());
+        }
+    } else if pnpm_config_path.exists() {
+        host_log!(
+            stdout,
+            "Migrating root config <file>pnpm-workspace.yaml</file>"
+        );
+
+        // Don't delete pnpm-workspace.yaml, since pnpm still requires it
+        migrator.migrate_pnpm_workspace(yaml::read_file(&pnpm_config_path)?)?;
+    } else {
+        return Err(plugin_err!(
+            "Unable to migrate, no <file>rush.json</file>, <file>lerna.json</file>, or <file>pnpm-workspace.yaml</file> found in the workspace root."
+        ));
+    }
+
+    // Then migrate all project package.json files
+    host_log!(
+        stdout,
+        "Migrating {} project <file>package.json</file> files",
+        migrator.projects.len()
+    );
+
+    migrator.migrate_projects()?;
+
+    // And lastly the Rush commands, which build on the project tasks
+    let command_line_path = workspace_root.join("common/config/rush/command-line.json");
+
+    if is_rush {
+        if command_line_path.exists() {
+            host_log!(
+                stdout,
+                "Migrating commands config <file>common/config/rush/command-line.json</file>"
+            );
+
+            migrator.migrate_rush_command_line(json::read_file(&command_line_path)?)?;
+
+            if args.cleanup {
+                cleanup_paths.push(command_line_path.into_inner());
+            }
+        } else {
+            migrator.migrate_rush_command_line(RushCommandLineJson::default())?;
+        }
+    }
+
+    // Preview the new config files
+    if args.dry_run {
+        for message in migrator.inner.describe_dry_run(&cleanup_paths)? {
+            host_log!(stdout, "{message}");
+        }
+
+        for message in migrator.inner.describe_warnings() {
+            host_log!(stdout, "{message}");
+        }
+
+        host_log!(
+            stdout,
+            "Dry run complete, no files were written or removed!"
+        );
+
+        return Ok(());
+    }
+
+    // Write the new config files
+    migrator.inner.save_configs()?;
+
+    for path in cleanup_paths {
+        fs::remove(path)?;
+    }
+
+    for message in migrator.inner.describe_warnings() {
+        host_log!(stdout, "{message}");
+    }
+
+    if args.report {
+        migrator
+            .inner
+            .save_report(&workspace_root.join("migration-report.json"))?;
+
+        host_log!(
+            stdout,
+            "Wrote migration report to <file>migration-report.json</file>"
+        );
+    }
+
+    host_log!(stdout, "Successfully migrated workspaces to moon!");
+
+    Ok(())
+}
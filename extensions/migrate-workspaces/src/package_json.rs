@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum PackageWorkspaces {
+    Globs(Vec<String>),
+    Config { packages: Option<Vec<String>> },
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageJson {
+    pub name: Option<String>,
+    pub scripts: Option<BTreeMap<String, String>>,
+    pub workspaces: Option<PackageWorkspaces>,
+    // Dependencies
+    pub dependencies: Option<FxHashMap<String, String>>,
+    pub dev_dependencies: Option<FxHashMap<String, String>>,
+    pub optional_dependencies: Option<FxHashMap<String, String>>,
+    pub peer_dependencies: Option<FxHashMap<String, String>>,
+}
+
+impl PackageJson {
+    pub fn get_workspace_globs(&self) -> Option<Vec<String>> {
+        match self.workspaces.as_ref()? {
+            PackageWorkspaces::Globs(globs) => Some(globs.to_owned()),
+            PackageWorkspaces::Config { packages } => packages.to_owned(),
+        }
+    }
+}
+
+// https://pnpm.io/pnpm-workspace_yaml
+#[derive(Default, Deserialize)]
+pub struct PnpmWorkspaceYaml {
+    pub packages: Option<Vec<String>>,
+}
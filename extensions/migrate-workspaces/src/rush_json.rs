@@ -0,0 +1,72 @@
+// https://rushjs.io/pages/configs/rush_json/
+// https://rushjs.io/pages/configs/command_line_json/
+
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use starbase_utils::json::JsonValue;
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushProject {
+    pub package_name: String,
+    pub project_folder: String,
+    pub tags: Option<Vec<String>>,
+    pub decoupled_local_dependencies: Option<Vec<String>>,
+    // v5 (renamed)
+    pub cyclic_dependency_projects: Option<Vec<String>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushJson {
+    pub npm_version: Option<String>,
+    pub pnpm_version: Option<String>,
+    pub yarn_version: Option<String>,
+    pub projects: Vec<RushProject>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RushCommandKind {
+    #[default]
+    Bulk,
+    Global,
+    Phased,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushCommand {
+    pub command_kind: RushCommandKind,
+    pub name: String,
+    pub summary: Option<String>,
+    pub ignore_dependency_order: Option<bool>,
+    pub phases: Option<Vec<String>>,
+    pub shell_command: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushPhaseDependencies {
+    #[serde(rename = "self")]
+    pub self_: Option<Vec<String>>,
+    pub upstream: Option<Vec<String>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushPhase {
+    pub name: String,
+    pub dependencies: Option<RushPhaseDependencies>,
+    pub ignore_missing_script: Option<bool>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RushCommandLineJson {
+    pub commands: Option<Vec<RushCommand>>,
+    pub phases: Option<Vec<RushPhase>>,
+    // Not supported
+    pub parameters: Option<Vec<JsonValue>>,
+}
@@ -0,0 +1,596 @@
+use crate::lerna_json::*;
+use crate::package_json::*;
+use crate::rush_json::*;
+use extension_common::migrator::*;
+use moon_common::Id;
+use moon_config::{
+    DependencyScope, OneOrMany, PartialProjectDependencyConfig, PartialProjectDependsOn,
+    PartialTaskArgs, PartialTaskConfig, PartialTaskDependency, PartialWorkspaceProjects,
+};
+use moon_pdk::{AnyResult, anyhow, map_miette_error};
+use moon_pdk_api::MoonContext;
+use moon_target::Target;
+use rustc_hash::FxHashMap;
+use starbase_utils::{fs, glob, json, yaml};
+use std::collections::BTreeMap;
+
+// Scripts that are ran automatically by the package manager
+const LIFECYCLE_SCRIPTS: [&str; 16] = [
+    "dependencies",
+    "install",
+    "pack",
+    "postinstall",
+    "postpack",
+    "postpublish",
+    "postuninstall",
+    "postversion",
+    "preinstall",
+    "prepack",
+    "prepare",
+    "prepublish",
+    "prepublishOnly",
+    "publish",
+    "uninstall",
+    "version",
+];
+
+pub struct ProjectEntry {
+    pub id: Id,
+    pub source: String,
+    pub package_json: PackageJson,
+    pub decoupled_deps: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl ProjectEntry {
+    pub fn has_script(&self, script: &str) -> bool {
+        self.package_json
+            .scripts
+            .as_ref()
+            .is_some_and(|scripts| scripts.contains_key(script))
+    }
+
+    pub fn filter_scripts(&self, scripts: &[String]) -> Vec<String> {
+        scripts
+            .iter()
+            .filter(|script| self.has_script(script))
+            .cloned()
+            .collect()
+    }
+}
+
+pub struct WorkspacesMigrator {
+    pub inner: Migrator,
+    pub package_manager: String,
+    pub projects: Vec<ProjectEntry>,
+}
+
+impl WorkspacesMigrator {
+    pub fn new(context: &MoonContext) -> AnyResult<Self> {
+        let migrator = Migrator::new(&context.workspace_root)?;
+
+        Ok(Self {
+            package_manager: migrator.detect_package_manager(),
+            inner: migrator,
+            projects: vec![],
+        })
+    }
+
+    fn load_project(&self, project_source: &str) -> AnyResult<Option<ProjectEntry>> {
+        let package_json_path = self.inner.root.join(project_source).join("package.json");
+
+        if !package_json_path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(ProjectEntry {
+            id: create_id(fs::file_name(package_json_path.parent().unwrap()))?,
+            source: project_source.to_owned(),
+            package_json: json::read_file(&package_json_path)?,
+            decoupled_deps: vec![],
+            tags: vec![],
+        }))
+    }
+
+    fn load_projects_from_globs(&mut self, globs: &[String]) -> AnyResult<()> {
+        let mut patterns = vec!["!**/node_modules/**/*".to_owned()];
+
+        for pattern in globs {
+            patterns.push(format!("{}/package.json", pattern.trim_end_matches('/')));
+        }
+
+        let mut projects = vec![];
+
+        for package_json_path in glob::walk_files(&self.inner.root, &patterns)? {
+            let project_source = package_json_path
+                .parent()
+                .unwrap()
+                .strip_prefix(&self.inner.root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            if let Some(project) = self.load_project(&project_source)? {
+                projects.push(project);
+            }
+        }
+
+        projects.sort_by(|a, b| a.source.cmp(&b.source));
+
+        // moon derives project IDs from folder names when using globs,
+        // so projects in folders with the same name would collide
+        let mut sources = FxHashMap::<&Id, &str>::default();
+
+        for project in &projects {
+            if let Some(other_source) = sources.insert(&project.id, &project.source) {
+                return Err(anyhow!(
+                    "Unable to migrate projects <path>{other_source}</path> and <path>{}</path> as both would have the ID <id>{}</id>. Rename one of the folders so that project IDs are unique.",
+                    project.source,
+                    project.id,
+                ));
+            }
+        }
+
+        self.projects = projects;
+
+        let workspace_config = self.inner.load_workspace_config()?;
+
+        if workspace_config.projects.is_none() {
+            workspace_config.projects = Some(PartialWorkspaceProjects::Globs(globs.to_vec()));
+        }
+
+        Ok(())
+    }
+
+    fn load_package_manager_globs(&self) -> AnyResult<Option<Vec<String>>> {
+        let pnpm_workspace_path = self.inner.root.join("pnpm-workspace.yaml");
+
+        if pnpm_workspace_path.exists() {
+            let pnpm_workspace: PnpmWorkspaceYaml = yaml::read_file(&pnpm_workspace_path)?;
+
+            return Ok(pnpm_workspace.packages);
+        }
+
+        let package_json_path = self.inner.root.join("package.json");
+
+        if package_json_path.exists() {
+            let package_json: PackageJson = json::read_file(&package_json_path)?;
+
+            return Ok(package_json.get_workspace_globs());
+        }
+
+        Ok(None)
+    }
+
+    pub fn migrate_lerna_config(&mut self, lerna_json: LernaJson) -> AnyResult<()> {
+        if let Some(npm_client) = &lerna_json.npm_client {
+            self.package_manager = npm_client.to_owned();
+        }
+
+        if lerna_json.command.is_some() {
+            self.inner.add_warning(
+                "lerna.json",
+                "/command",
+                "Command options are not supported, pass them as task arguments instead.",
+            );
+        }
+
+        // When packages are not defined, Lerna falls back to the package manager
+        let globs = match lerna_json.packages {
+            Some(packages) if lerna_json.use_workspaces != Some(true) => packages,
+            _ => self
+                .load_package_manager_globs()?
+                .unwrap_or_else(|| vec!["packages/*".into()]),
+        };
+
+        self.load_projects_from_globs(&globs)
+    }
+
+    pub fn migrate_pnpm_workspace(&mut self, pnpm_workspace: PnpmWorkspaceYaml) -> AnyResult<()> {
+        self.package_manager = "pnpm".into();
+
+        self.load_projects_from_globs(&pnpm_workspace.packages.unwrap_or_default())
+    }
+
+    pub fn migrate_rush_config(&mut self, rush_json: RushJson) -> AnyResult<()> {
+        self.package_manager = if rush_json.pnpm_version.is_some() {
+            "pnpm"
+        } else if rush_json.yarn_version.is_some() {
+            "yarn"
+        } else {
+            "npm"
+        }
+        .into();
+
+        let mut sources = FxHashMap::default();
+
+        for rush_project in rush_json.projects {
+            let project_source = rush_project.project_folder.trim_end_matches('/');
+
+            let Some(mut project) = self.load_project(project_source)? else {
+                return Err(anyhow!(
+                    "Unable to migrate project <id>{}</id> as <path>{project_source}/package.json</path> does not exist.",
+                    rush_project.package_name,
+                ));
+            };
+
+            project.decoupled_deps = rush_project
+                .decoupled_local_dependencies
+                .or(rush_project.cyclic_dependency_projects)
+                .unwrap_or_default();
+            project.tags = rush_project.tags.unwrap_or_default();
+
+            // Projects in folders with the same name (`apps/ui` and `libs/ui`)
+            // would collide, so fallback to the package name for the ID
+            if sources.contains_key(&project.id) {
+                project.id = create_id(&rush_project.package_name)?;
+
+                if sources.contains_key(&project.id) {
+                    return Err(anyhow!(
+                        "Unable to migrate project <id>{}</id> as the ID <id>{}</id> is already used by another project.",
+                        rush_project.package_name,
+                        project.id,
+                    ));
+                }
+            }
+
+            sources.insert(project.id.clone(), project.source.clone());
+
+            self.projects.push(project);
+        }
+
+        self.inner.load_workspace_config()?.projects =
+            Some(PartialWorkspaceProjects::Sources(sources));
+
+        Ok(())
+    }
+
+    pub fn migrate_rush_command_line(
+        &mut self,
+        command_line_json: RushCommandLineJson,
+    ) -> AnyResult<()> {
+        let file = "common/config/rush/command-line.json";
+        let mut commands = command_line_json.commands.unwrap_or_default();
+
+        if command_line_json.parameters.is_some() {
+            self.inner.add_warning(
+                file,
+                "/parameters",
+                "Custom parameters are not supported, pass them as task arguments instead.",
+            );
+        }
+
+        // The built-in build command can be overridden, otherwise it runs in order
+        if !commands.iter().any(|command| command.name == "build") {
+            commands.push(RushCommand {
+                command_kind: RushCommandKind::Bulk,
+                name: "build".into(),
+                ..Default::default()
+            });
+        }
+
+        // Phases are ran for each project, with dependencies on itself or upstream
+        for phase in command_line_json.phases.unwrap_or_default() {
+            let mut self_scripts = vec![];
+            let mut upstream_ids = vec![];
+
+            if let Some(dependencies) = phase.dependencies {
+                self_scripts.extend(dependencies.self_.unwrap_or_default());
+
+                for dep in dependencies.upstream.iter().flatten() {
+                    upstream_ids.push(create_phase_id(dep)?);
+                }
+            }
+
+            self.migrate_command_to_projects(
+                &phase.name,
+                create_phase_id(&phase.name)?,
+                &self_scripts,
+                &upstream_ids,
+            )?;
+        }
+
+        for (index, command) in commands.into_iter().enumerate() {
+            let pointer = create_json_pointer(["commands", &index.to_string()]);
+
+            match command.command_kind {
+                RushCommandKind::Bulk => {
+                    let task_id = create_id(&command.name)?;
+                    let upstream_ids = if command.ignore_dependency_order == Some(true) {
+                        vec![]
+                    } else {
+                        vec![task_id.clone()]
+                    };
+
+                    match &command.shell_command {
+                        Some(shell_command) => {
+                            for project in &self.projects {
+                                let task = self
+                                    .inner
+                                    .load_project_config(&project.source)?
+                                    .tasks
+                                    .get_or_insert(BTreeMap::default())
+                                    .entry(task_id.clone())
+                                    .or_default();
+
+                                task.command =
+                                    Some(PartialTaskArgs::String(shell_command.to_owned()));
+                                task.description = command.summary.clone();
+                                task.deps = create_deps(&[], &upstream_ids)?;
+                            }
+                        }
+                        None => {
+                            self.migrate_command_to_projects(
+                                &command.name,
+                                task_id,
+                                &[],
+                                &upstream_ids,
+                            )?;
+                        }
+                    };
+                }
+                RushCommandKind::Global => {
+                    let Some(shell_command) = command.shell_command else {
+                        self.inner.add_warning(
+                            file,
+                            format!("{pointer}/shellCommand"),
+                            "Global commands without a shell command are not supported.",
+                        );
+                        continue;
+                    };
+
+                    self.inner
+                        .load_project_config("")?
+                        .tasks
+                        .get_or_insert(BTreeMap::default())
+                        .insert(
+                            create_id(&command.name)?,
+                            PartialTaskConfig {
+                                command: Some(PartialTaskArgs::String(shell_command)),
+                                description: command.summary,
+                                toolchains: Some(OneOrMany::One(Id::raw("system"))),
+                                ..Default::default()
+                            },
+                        );
+
+                    if let Some(PartialWorkspaceProjects::Sources(sources)) =
+                        &mut self.inner.load_workspace_config()?.projects
+                    {
+                        sources.insert(Id::raw("root"), ".".into());
+                    }
+                }
+                RushCommandKind::Phased => {
+                    let task_id = create_id(&command.name)?;
+
+                    // When the command has the same name as a phase,
+                    // the phase task is the equivalent of the command
+                    let mut phases = vec![];
+
+                    for phase in command.phases.unwrap_or_default() {
+                        if create_phase_id(&phase)? != task_id {
+                            phases.push(phase);
+                        }
+                    }
+
+                    for project in &self.projects {
+                        let Some(deps) = create_deps(&project.filter_scripts(&phases), &[])? else {
+                            continue;
+                        };
+
+                        let tasks = self
+                            .inner
+                            .load_project_config(&project.source)?
+                            .tasks
+                            .get_or_insert(BTreeMap::default());
+
+                        if tasks.contains_key(&task_id) {
+                            continue;
+                        }
+
+                        tasks.insert(
+                            task_id.clone(),
+                            PartialTaskConfig {
+                                command: Some(PartialTaskArgs::String("noop".into())),
+                                description: command.summary.clone(),
+                                deps: Some(deps),
+                                ..Default::default()
+                            },
+                        );
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    fn migrate_command_to_projects(
+        &mut self,
+        script: &str,
+        task_id: Id,
+        self_scripts: &[String],
+        upstream_ids: &[Id],
+    ) -> AnyResult<()> {
+        for project in &self.projects {
+            // Only projects that define the script run it
+            if !project.has_script(script) {
+                continue;
+            }
+
+            let tasks = self
+                .inner
+                .load_project_config(&project.source)?
+                .tasks
+                .get_or_insert(BTreeMap::default());
+
+            // Remove the task that was created from the script name
+            tasks.remove(&create_id(script)?);
+
+            tasks.insert(
+                task_id.clone(),
+                PartialTaskConfig {
+                    command: Some(PartialTaskArgs::String(format!(
+                        "{} run {script}",
+                        self.package_manager
+                    ))),
+                    deps: create_deps(&project.filter_scripts(self_scripts), upstream_ids)?,
+                    ..Default::default()
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn migrate_projects(&mut self) -> AnyResult<()> {
+        let mut package_ids = FxHashMap::default();
+
+        for project in &self.projects {
+            if let Some(name) = &project.package_json.name {
+                package_ids.insert(name.as_str(), project.id.clone());
+            }
+        }
+
+        for project in &self.projects {
+            let config = self.inner.load_project_config(&project.source)?;
+
+            // Dependencies
+            let mut depends_on = vec![];
+
+            for (deps, scope) in [
+                (
+                    &project.package_json.dependencies,
+                    DependencyScope::Production,
+                ),
+                (
+                    &project.package_json.optional_dependencies,
+                    DependencyScope::Production,
+                ),
+                (
+                    &project.package_json.peer_dependencies,
+                    DependencyScope::Peer,
+                ),
+                (
+                    &project.package_json.dev_dependencies,
+                    DependencyScope::Development,
+                ),
+            ] {
+                let Some(deps) = deps else {
+                    continue;
+                };
+
+                let mut names = deps.keys().collect::<Vec<_>>();
+                names.sort();
+
+                for name in names {
+                    let Some(dep_id) = package_ids.get(name.as_str()) else {
+                        continue;
+                    };
+
+                    if *dep_id == project.id
+                        || project.decoupled_deps.contains(name)
+                        || depends_on.iter().any(|dep| match dep {
+                            PartialProjectDependsOn::String(id) => id == dep_id,
+                            PartialProjectDependsOn::Object(dep) => dep.id.as_ref() == Some(dep_id),
+                        })
+                    {
+                        continue;
+                    }
+
+                    depends_on.push(if scope == DependencyScope::Production {
+                        PartialProjectDependsOn::String(dep_id.clone())
+                    } else {
+                        PartialProjectDependsOn::Object(PartialProjectDependencyConfig {
+                            id: Some(dep_id.clone()),
+                            scope: Some(scope),
+                            ..Default::default()
+                        })
+                    });
+                }
+            }
+
+            if !depends_on.is_empty() {
+                config.depends_on = Some(depends_on);
+            }
+
+            // Tags
+            if !project.tags.is_empty() {
+                let tags = config.tags.get_or_insert(vec![]);
+
+                for tag in &project.tags {
+                    tags.push(Id::new(tag)?);
+                }
+            }
+
+            // Tasks
+            if let Some(scripts) = &project.package_json.scripts {
+                let tasks = config.tasks.get_or_insert(BTreeMap::default());
+
+                for script in scripts.keys() {
+                    if !is_task_script(script, scripts) {
+                        continue;
+                    }
+
+                    tasks.insert(
+                        create_id(script)?,
+                        PartialTaskConfig {
+                            command: Some(PartialTaskArgs::String(format!(
+                                "{} run {script}",
+                                self.package_manager
+                            ))),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                if tasks.is_empty() {
+                    config.tasks = None;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_task_script(script: &str, scripts: &BTreeMap<String, String>) -> bool {
+    if LIFECYCLE_SCRIPTS.contains(&script) || script.starts_with("_phase:") {
+        return false;
+    }
+
+    // Hooks are ran automatically before/after the script
+    for prefix in ["pre", "post"] {
+        if let Some(base) = script.strip_prefix(prefix)
+            && scripts.contains_key(base)
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn create_phase_id(phase: &str) -> AnyResult<Id> {
+    create_id(phase.strip_prefix("_phase:").unwrap_or(phase))
+}
+
+fn create_deps(
+    self_scripts: &[String],
+    upstream_ids: &[Id],
+) -> AnyResult<Option<Vec<PartialTaskDependency>>> {
+    let mut deps = vec![];
+
+    for script in self_scripts {
+        deps.push(PartialTaskDependency::Target(
+            Target::new_self(create_phase_id(script)?).map_err(map_miette_error)?,
+        ));
+    }
+
+    for id in upstream_ids {
+        deps.push(PartialTaskDependency::Target(
+            Target::parse(&format!("^:{id}")).map_err(map_miette_error)?,
+        ));
+    }
+
+    Ok(if deps.is_empty() { None } else { Some(deps) })
+}
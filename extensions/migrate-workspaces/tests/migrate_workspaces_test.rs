@@ -0,0 +1,118 @@
+use moon_pdk_test_utils::{ExecuteExtensionInput, create_empty_moon_sandbox, create_moon_sandbox};
+use starbase_sandbox::assert_snapshot;
+use std::fs;
+
+fn create_input() -> ExecuteExtensionInput {
+    ExecuteExtensionInput {
+        args: vec!["--cleanup".into()],
+        ..Default::default()
+    }
+}
+
+mod migrate_workspaces_extension {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(
+        expected = "Unable to migrate, no rush.json, lerna.json, or pnpm-workspace.yaml"
+    )]
+    async fn errors_if_no_workspace_config() {
+        let sandbox = create_empty_moon_sandbox();
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn converts_lerna() {
+        let sandbox = create_moon_sandbox("lerna");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+
+        assert!(!sandbox.path().join("lerna.json").exists());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/foo/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/bar/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/baz/moon.yml")).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn converts_pnpm_workspace() {
+        let sandbox = create_moon_sandbox("pnpm");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+
+        assert!(sandbox.path().join("pnpm-workspace.yaml").exists());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/a/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("packages/b/moon.yml")).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn converts_rush() {
+        let sandbox = create_moon_sandbox("rush");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--cleanup".into(), "--report".into()],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(!sandbox.path().join("rush.json").exists());
+        assert!(
+            !sandbox
+                .path()
+                .join("common/config/rush/command-line.json")
+                .exists()
+        );
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("apps/web/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("libs/ui/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("libs/utils/moon.yml")).unwrap());
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("migration-report.json")).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn uses_package_name_for_rush_projects_with_the_same_folder_name() {
+        let sandbox = create_empty_moon_sandbox();
+        sandbox.create_file(
+            "rush.json",
+            r#"{
+  "rushVersion": "5.112.0",
+  "projects": [
+    { "packageName": "@apps/ui", "projectFolder": "apps/ui" },
+    { "packageName": "@libs/ui", "projectFolder": "libs/ui" }
+  ]
+}"#,
+        );
+        sandbox.create_file("apps/ui/package.json", r#"{ "name": "@apps/ui" }"#);
+        sandbox.create_file("libs/ui/package.json", r#"{ "name": "@libs/ui" }"#);
+
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "as both would have the ID ui")]
+    async fn errors_if_glob_projects_have_the_same_folder_name() {
+        let sandbox = create_empty_moon_sandbox();
+        sandbox.create_file("pnpm-workspace.yaml", "packages:\n  - apps/*\n  - libs/*\n");
+        sandbox.create_file("apps/ui/package.json", r#"{ "name": "@apps/ui" }"#);
+        sandbox.create_file("libs/ui/package.json", r#"{ "name": "@libs/ui" }"#);
+
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin.execute_extension(create_input()).await;
+    }
+}
@@ -0,0 +1,463 @@
+use extism_pdk::debug;
+use moon_pdk::{
+    AnyResult, anyhow, convert_to_real_path, exec, get_host_environment, get_host_to_guest_paths,
+};
+use moon_pdk_api::ExecCommandInput;
+use starbase_utils::fs;
+use std::fmt;
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArchiveFormat {
+    SevenZip,
+    Tar,
+    TarBz2,
+    TarGz,
+    TarXz,
+    TarZstd,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the format of the archive by inspecting its magic bytes,
+    /// instead of relying on the file extension.
+    pub fn detect(file: &Path) -> AnyResult<Self> {
+        let mut header = vec![];
+
+        fs::open_file(file)?.take(262).read_to_end(&mut header)?;
+
+        Self::from_magic_bytes(&header).ok_or_else(|| {
+            anyhow!(
+                "Unable to detect the archive format of <path>{}</path>. Only tar (gz, bz2, xz, zst), zip, and 7z archives are supported.",
+                file.display()
+            )
+        })
+    }
+
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<Self> {
+        let format = if bytes.starts_with(&[0x1F, 0x8B]) {
+            Self::TarGz
+        } else if bytes.starts_with(b"BZh") {
+            Self::TarBz2
+        } else if bytes.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+            Self::TarXz
+        } else if bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Self::TarZstd
+        } else if bytes.starts_with(&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C]) {
+            Self::SevenZip
+        } else if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            Self::Zip
+        } else if bytes.get(257..262) == Some(b"ustar") {
+            Self::Tar
+        } else {
+            return None;
+        };
+
+        Some(format)
+    }
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SevenZip => "7z",
+            Self::Tar => "tar",
+            Self::TarBz2 => "tar.bz2",
+            Self::TarGz => "tar.gz",
+            Self::TarXz => "tar.xz",
+            Self::TarZstd => "tar.zst",
+            Self::Zip => "zip",
+        })
+    }
+}
+
+/// 7z archives created on unix store the file mode in the upper bits
+/// of the Windows attributes, flagged by `FILE_ATTRIBUTE_UNIX_EXTENSION`.
+fn get_7z_mode(entry: &sevenz_rust2::ArchiveEntry) -> Option<u32> {
+    let attributes = entry.windows_attributes();
+
+    (entry.has_windows_attributes && attributes & 0x8000 != 0).then_some(attributes >> 16)
+}
+
+/// Unpacks archive entries into a destination directory, while stripping
+/// an optional prefix, and refusing any entry that escapes the destination.
+pub struct Unpacker {
+    dest: PathBuf,
+    prefix: Vec<String>,
+
+    // Links can't be created from WASM, so they're materialized
+    // by copying their target once all entries have been unpacked
+    links: Vec<(PathBuf, PathBuf)>,
+
+    // Permissions can't be updated from WASM, so the unix modes of
+    // entries are applied through the host once all entries exist
+    modes: Vec<(PathBuf, u32)>,
+
+    // Outermost paths created while unpacking, which are removed
+    // if unpacking fails, so that a partial archive isn't left behind
+    created: Vec<PathBuf>,
+}
+
+impl Unpacker {
+    pub fn new(dest: &Path, prefix: Option<&str>) -> Self {
+        Self {
+            dest: dest.to_path_buf(),
+            prefix: prefix
+                .unwrap_or_default()
+                .split(['/', '\\'])
+                .filter(|part| !part.is_empty() && *part != ".")
+                .map(|part| part.to_owned())
+                .collect(),
+            links: vec![],
+            modes: vec![],
+            created: vec![],
+        }
+    }
+
+    pub fn unpack(&mut self, src: &Path, format: ArchiveFormat) -> AnyResult<()> {
+        debug!("Unpacking <path>{}</path> as {format}", src.display());
+
+        let result = self
+            .unpack_entries(src, format)
+            .and_then(|_| self.materialize_links())
+            .and_then(|_| self.apply_modes());
+
+        if result.is_err() {
+            self.remove_created();
+        }
+
+        result
+    }
+
+    fn unpack_entries(&mut self, src: &Path, format: ArchiveFormat) -> AnyResult<()> {
+        let file = BufReader::new(fs::open_file(src)?);
+
+        match format {
+            ArchiveFormat::SevenZip => self.unpack_7z(file),
+            ArchiveFormat::Tar => self.unpack_tar(file),
+            ArchiveFormat::TarBz2 => self.unpack_tar(bzip2::read::MultiBzDecoder::new(file)),
+            ArchiveFormat::TarGz => self.unpack_tar(flate2::read::MultiGzDecoder::new(file)),
+            ArchiveFormat::TarXz => self.unpack_tar(lzma_rust2::XzReader::new(file, true)),
+            ArchiveFormat::TarZstd => self.unpack_tar(
+                ruzstd::decoding::StreamingDecoder::new(file)
+                    .map_err(|error| anyhow!("Invalid zstd archive: {error}"))?,
+            ),
+            ArchiveFormat::Zip => self.unpack_zip(file),
+        }
+    }
+
+    fn unpack_7z(&mut self, reader: impl Read + io::Seek) -> AnyResult<()> {
+        let mut archive = sevenz_rust2::ArchiveReader::new(reader, sevenz_rust2::Password::empty())
+            .map_err(|error| anyhow!("Invalid 7z archive: {error}"))?;
+        let mut failure = None;
+
+        // Threads can't be spawned from WASM
+        archive.set_thread_count(1);
+
+        let result = archive.for_each_entries(|entry, reader| {
+            let mut unpack_entry = || -> AnyResult<()> {
+                let entry_path = PathBuf::from(entry.name());
+
+                match self.resolve_path(&entry_path)? {
+                    Some(out) if entry.is_directory() => {
+                        self.track_created(&out);
+                        fs::create_dir_all(&out)?;
+                    }
+                    Some(out) if !entry.is_anti_item() => {
+                        self.write_file(&out, reader, get_7z_mode(entry))?;
+                    }
+                    // Entries in a solid block share a stream, so skipped
+                    // entries must still be read before the next entry
+                    _ => {
+                        io::copy(reader, &mut io::sink())?;
+                    }
+                };
+
+                Ok(())
+            };
+
+            unpack_entry().map_err(|error| {
+                let message = error.to_string();
+                failure = Some(error);
+
+                sevenz_rust2::Error::from(io::Error::other(message))
+            })?;
+
+            Ok(true)
+        });
+
+        match (failure, result) {
+            (Some(error), _) => Err(error),
+            (None, Err(error)) => Err(anyhow!("Invalid 7z archive: {error}")),
+            (None, Ok(_)) => Ok(()),
+        }
+    }
+
+    fn unpack_tar(&mut self, reader: impl Read) -> AnyResult<()> {
+        let mut archive = tar::Archive::new(reader);
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+            let Some(out) = self.resolve_path(&entry_path)? else {
+                continue;
+            };
+            let kind = entry.header().entry_type();
+
+            if kind.is_dir() {
+                self.track_created(&out);
+                fs::create_dir_all(&out)?;
+            } else if kind.is_symlink() || kind.is_hard_link() {
+                let link_name = entry.link_name()?.ok_or_else(|| {
+                    anyhow!("Link <path>{}</path> has no target.", entry_path.display())
+                })?;
+
+                // Hard links are relative to the archive root, not the link
+                let target = if kind.is_hard_link() {
+                    match self.resolve_path(&link_name)? {
+                        Some(target) => target,
+                        None => continue,
+                    }
+                } else {
+                    self.resolve_link(&out, &link_name)?
+                };
+
+                self.links.push((out, target));
+            } else if kind.is_file() || kind.is_contiguous() {
+                let mode = entry.header().mode().ok();
+
+                self.write_file(&out, &mut entry, mode)?;
+            } else {
+                debug!(
+                    "Skipping unsupported entry <path>{}</path>",
+                    entry_path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unpack_zip(&mut self, reader: impl Read + io::Seek) -> AnyResult<()> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            let entry_path = PathBuf::from(entry.name());
+            let Some(out) = self.resolve_path(&entry_path)? else {
+                continue;
+            };
+
+            if entry.is_dir() {
+                self.track_created(&out);
+                fs::create_dir_all(&out)?;
+            } else if entry.is_symlink() {
+                let mut link_name = String::new();
+                entry.read_to_string(&mut link_name)?;
+
+                let target = self.resolve_link(&out, Path::new(&link_name))?;
+
+                self.links.push((out, target));
+            } else {
+                let mode = entry.unix_mode();
+
+                self.write_file(&out, &mut entry, mode)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        out: &Path,
+        reader: &mut (impl Read + ?Sized),
+        mode: Option<u32>,
+    ) -> AnyResult<()> {
+        self.track_created(out);
+
+        let mut file = fs::create_file(out)?;
+
+        io::copy(reader, &mut file)?;
+
+        if let Some(mode) = mode {
+            self.modes.push((out.to_path_buf(), mode & 0o7777));
+        }
+
+        Ok(())
+    }
+
+    fn apply_modes(&mut self) -> AnyResult<()> {
+        let modes = std::mem::take(&mut self.modes);
+
+        if modes.is_empty() || get_host_environment()?.os.is_windows() {
+            return Ok(());
+        }
+
+        // Group files by mode, so that a single command is ran for each
+        let mut groups: Vec<(u32, Vec<String>)> = vec![];
+        let paths = get_host_to_guest_paths()?;
+
+        for (file, mode) in modes {
+            let Some(real_file) = convert_to_real_path(&file, paths) else {
+                debug!(
+                    "Skipping permissions of <path>{}</path> as it has no real path",
+                    file.display()
+                );
+
+                continue;
+            };
+
+            let real_file = real_file.to_string_lossy().into_owned();
+
+            match groups
+                .iter_mut()
+                .find(|(group_mode, _)| *group_mode == mode)
+            {
+                Some((_, files)) => files.push(real_file),
+                None => groups.push((mode, vec![real_file])),
+            };
+        }
+
+        for (mode, files) in groups {
+            let mut args = vec![format!("{mode:o}")];
+            args.extend(files);
+
+            let result = exec(ExecCommandInput::pipe("chmod", args))?;
+
+            if result.exit_code != 0 {
+                return Err(anyhow!(
+                    "Failed to update the permissions of unpacked files: {}",
+                    result.stderr.trim()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn materialize_links(&mut self) -> AnyResult<()> {
+        for (link, target) in std::mem::take(&mut self.links) {
+            // Copying a parent into itself would recurse forever
+            if link.starts_with(&target) {
+                debug!(
+                    "Skipping link <path>{}</path> as it points to a parent directory",
+                    link.display()
+                );
+            } else if target.is_dir() {
+                self.track_created(&link);
+                fs::copy_dir_all(&target, &link)?;
+            } else if target.is_file() {
+                self.track_created(&link);
+                fs::copy_file(&target, &link)?;
+            } else {
+                debug!(
+                    "Skipping link <path>{}</path> as its target does not exist",
+                    link.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the outermost path that will be created when writing to
+    /// the provided path, which includes any missing parent directories.
+    fn track_created(&mut self, out: &Path) {
+        let created = out
+            .ancestors()
+            .take_while(|path| *path != self.dest && !path.exists())
+            .last();
+
+        if let Some(path) = created {
+            self.created.push(path.to_path_buf());
+        }
+    }
+
+    fn remove_created(&mut self) {
+        for path in std::mem::take(&mut self.created).into_iter().rev() {
+            debug!(
+                "Removing partially unpacked <path>{}</path>",
+                path.display()
+            );
+
+            if let Err(error) = fs::remove(&path) {
+                debug!("Failed to remove <path>{}</path>: {error}", path.display());
+            }
+        }
+    }
+
+    /// Resolve an entry path within the archive to a path within the destination.
+    /// Returns `None` if the entry is outside of the configured prefix.
+    pub fn resolve_path(&self, entry_path: &Path) -> AnyResult<Option<PathBuf>> {
+        let mut parts = vec![];
+
+        for component in entry_path.components() {
+            match component {
+                Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+                Component::CurDir => {}
+                _ => {
+                    return Err(anyhow!(
+                        "Refusing to unpack <path>{}</path>, as it would escape the destination directory.",
+                        entry_path.display()
+                    ));
+                }
+            };
+        }
+
+        if !self.prefix.is_empty() {
+            if parts.len() <= self.prefix.len() || !parts.starts_with(&self.prefix) {
+                return Ok(None);
+            }
+
+            parts.drain(0..self.prefix.len());
+        }
+
+        if parts.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            parts
+                .iter()
+                .fold(self.dest.clone(), |dir, part| dir.join(part)),
+        ))
+    }
+
+    /// Resolve a symlink target relative to the link, and ensure that
+    /// the target does not escape the destination directory.
+    pub fn resolve_link(&self, link: &Path, target: &Path) -> AnyResult<PathBuf> {
+        let escape_error = || {
+            anyhow!(
+                "Refusing to unpack link <path>{}</path> to <path>{}</path>, as it would escape the destination directory.",
+                link.display(),
+                target.display()
+            )
+        };
+
+        let mut parts = link
+            .parent()
+            .and_then(|parent| parent.strip_prefix(&self.dest).ok())
+            .ok_or_else(escape_error)?
+            .components()
+            .collect::<Vec<_>>();
+
+        for component in target.components() {
+            match component {
+                Component::Normal(_) => parts.push(component),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if parts.pop().is_none() {
+                        return Err(escape_error());
+                    }
+                }
+                _ => return Err(escape_error()),
+            };
+        }
+
+        Ok(parts
+            .iter()
+            .fold(self.dest.clone(), |dir, part| dir.join(part)))
+    }
+}
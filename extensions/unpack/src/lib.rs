@@ -1,3 +1,4 @@
+mod archive;
 #[cfg(feature = "wasm")]
 mod unpack_ext;
 
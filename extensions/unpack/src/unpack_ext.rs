@@ -1,3 +1,4 @@
+use crate::archive::{ArchiveFormat, Unpacker};
 use extension_common::download::download_from_url;
 use extension_common::enable_tracing;
 use extism_pdk::*;
@@ -44,7 +45,7 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
     let src_file = if args.src.starts_with("http") {
         debug!("Received a URL as the input source");
 
-        download_from_url(&args.src, VirtualPath::new("/moon/temp"), None)?
+        download_from_url(&args.src, VirtualPath::new("/moon/temp"), None, None)?
     } else {
         debug!(
             "Converting source <file>{}</file> to an absolute virtual path",
@@ -73,39 +74,18 @@ pub fn execute_extension(Json(input): Json<ExecuteExtensionInput>) -> FnResult<(
 
     host_log!(stdout, "Unpacking archive to <path>{dest_dir}</path>",);
 
+    let format = ArchiveFormat::detect(&src_file)?;
+    let dest_existed = dest_dir.exists();
+
     fs::create_dir_all(&dest_dir)?;
 
-    if args.src.ends_with(".zip") {
-        exec_streamed(
-            "unzip",
-            [
-                src_file
-                    .to_real_path()?
-                    .expect("Invalid source")
-                    .to_string(),
-                "-d".into(),
-                dest_dir
-                    .to_real_path()?
-                    .expect("Invalid destination")
-                    .to_string(),
-            ],
-        )?;
-    } else {
-        exec_streamed(
-            "tar",
-            [
-                "-xf".into(),
-                src_file
-                    .to_real_path()?
-                    .expect("Invalid source")
-                    .to_string(),
-                "-C".into(),
-                dest_dir
-                    .to_real_path()?
-                    .expect("Invalid destination")
-                    .to_string(),
-            ],
-        )?;
+    if let Err(error) = Unpacker::new(&dest_dir, args.prefix.as_deref()).unpack(&src_file, format) {
+        // The unpacker removes what it created, but not the destination itself
+        if !dest_existed {
+            fs::remove_dir_all(&dest_dir)?;
+        }
+
+        return Err(error.into());
     }
 
     host_log!(stdout, "Unpacked archive!");
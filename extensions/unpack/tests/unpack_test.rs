@@ -14,24 +14,19 @@ mod unpack_extension {
             .await;
     }
 
-    // #[tokio::test(flavor = "multi_thread")]
-    // #[should_panic(
-    //     expected = "Invalid source, only .tar, .tar.gz, and .zip archives are supported."
-    // )]
-    // async fn errors_if_unsupported_ext() {
-    //     let sandbox = create_empty_moon_sandbox();
-    //     let plugin = sandbox.create_extension("test").await;
-
-    //     plugin
-    //         .execute_extension(ExecuteExtensionInput {
-    //             args: vec![
-    //                 "--src".into(),
-    //                 "https://raw.githubusercontent.com/moonrepo/moon/master/README.md".into(),
-    //             ],
-    //             ..Default::default()
-    //         })
-    //         .await;
-    // }
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "Unable to detect the archive format")]
+    async fn errors_if_unsupported_format() {
+        let sandbox = create_moon_sandbox("unsupported");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec!["--src".into(), "./archive.txt".into()],
+                ..Default::default()
+            })
+            .await;
+    }
 
     #[tokio::test(flavor = "multi_thread")]
     #[should_panic(expected = "must be a valid file")]
@@ -128,6 +123,333 @@ mod unpack_extension {
         assert!(sandbox.path().join("out/dir/file.txt").exists());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unpacks_tar_bz2() {
+        let sandbox = create_moon_sandbox("tar");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.bz2".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/dir/file.txt").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unpacks_tar_xz() {
+        let sandbox = create_moon_sandbox("tar");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.xz".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/dir/file.txt").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unpacks_tar_zst() {
+        let sandbox = create_moon_sandbox("tar");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.zst".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/dir/file.txt").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unpacks_7z() {
+        let sandbox = create_moon_sandbox("7z");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.7z".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert_eq!(
+            std::fs::read_to_string(sandbox.path().join("out/dir/file.txt")).unwrap(),
+            "This is in the archive!\n"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn detects_format_from_magic_bytes() {
+        let sandbox = create_moon_sandbox("tar");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive-no-ext".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/dir/file.txt").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn strips_prefix_from_tar() {
+        let sandbox = create_moon_sandbox("tar");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.gz".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                    "--prefix".into(),
+                    "dir".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/file.txt").exists());
+        assert!(!sandbox.path().join("out/dir").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn strips_prefix_from_zip() {
+        let sandbox = create_moon_sandbox("zip");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.zip".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                    "--prefix".into(),
+                    "dir/".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert!(sandbox.path().join("out/file.txt").exists());
+        assert!(!sandbox.path().join("out/dir").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn copies_symlink_targets() {
+        let sandbox = create_moon_sandbox("links");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.gz".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        assert_eq!(
+            std::fs::read_to_string(sandbox.path().join("out/dir/alias.txt")).unwrap(),
+            "linked content\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn preserves_modes_from_tar() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let sandbox = create_moon_sandbox("modes");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.tar.gz".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        let mode = |path: &str| {
+            std::fs::metadata(sandbox.path().join(path))
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o777
+        };
+
+        assert_eq!(mode("out/dir/bin/tool"), 0o755);
+        assert_eq!(mode("out/dir/file.txt"), 0o644);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn preserves_modes_from_zip() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let sandbox = create_moon_sandbox("modes");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./archive.zip".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+
+        let mode = |path: &str| {
+            std::fs::metadata(sandbox.path().join(path))
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o777
+        };
+
+        assert_eq!(mode("out/dir/bin/tool"), 0o755);
+        assert_eq!(mode("out/dir/file.txt"), 0o644);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "as it would escape the destination directory")]
+    async fn errors_if_entry_escapes_dest() {
+        let sandbox = create_moon_sandbox("unsafe");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./traversal.tar".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_partially_unpacked_dest_if_entry_escapes() {
+        let sandbox = create_moon_sandbox("unsafe");
+        let plugin = sandbox.create_extension("test").await;
+
+        let result = plugin
+            .plugin
+            .call_func_without_output(
+                "execute_extension",
+                ExecuteExtensionInput {
+                    args: vec![
+                        "--src".into(),
+                        "./traversal.tar".into(),
+                        "--dest".into(),
+                        "./out".into(),
+                    ],
+                    context: plugin.create_context(),
+                },
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(!sandbox.path().join("out").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_only_unpacked_entries_if_dest_existed() {
+        let sandbox = create_moon_sandbox("unsafe");
+        sandbox.create_file("out/existing.txt", "existing");
+
+        let plugin = sandbox.create_extension("test").await;
+
+        let result = plugin
+            .plugin
+            .call_func_without_output(
+                "execute_extension",
+                ExecuteExtensionInput {
+                    args: vec![
+                        "--src".into(),
+                        "./traversal.tar".into(),
+                        "--dest".into(),
+                        "./out".into(),
+                    ],
+                    context: plugin.create_context(),
+                },
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(sandbox.path().join("out/existing.txt").exists());
+        assert!(!sandbox.path().join("out/dir").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "as it would escape the destination directory")]
+    async fn errors_if_symlink_escapes_dest() {
+        let sandbox = create_moon_sandbox("unsafe");
+        let plugin = sandbox.create_extension("test").await;
+
+        plugin
+            .execute_extension(ExecuteExtensionInput {
+                args: vec![
+                    "--src".into(),
+                    "./symlink.tar".into(),
+                    "--dest".into(),
+                    "./out".into(),
+                ],
+                ..Default::default()
+            })
+            .await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn downloads_and_unpacks_tar() {
         let sandbox = create_empty_moon_sandbox();
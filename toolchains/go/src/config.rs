@@ -24,6 +24,15 @@ config_struct!(
         /// Set to `["."]` to restrict inference to the root package only.
         pub infer_relationships_packages: Vec<String>,
 
+        /// Automatically infer moon tasks from the packages of each module.
+        /// Will infer a `build-<name>` task for each `package main` under `cmd/`,
+        /// a `generate` task when `//go:generate` directives exist, and `test`
+        /// and `vet` tasks.
+        pub infer_tasks_from_packages: bool,
+
+        /// Run the inferred `test` task with the race detector (`-race`).
+        pub infer_tasks_test_race: bool,
+
         /// Tidy modules when dependencies or `go.sum` changes by running `go mod tidy`.
         /// This setting is ignored when using Go workspaces.
         pub tidy_on_change: bool,
@@ -0,0 +1,108 @@
+// `go env`
+
+use moon_pdk_api::{HostArch, HostOS};
+use std::collections::BTreeMap;
+
+/// Environment variables that affect the compiled output.
+pub const BUILD_ENV_VARS: [&str; 10] = [
+    "CGO_ENABLED",
+    "GO386",
+    "GOAMD64",
+    "GOARCH",
+    "GOARM",
+    "GOARM64",
+    "GOEXPERIMENT",
+    "GOFLAGS",
+    "GOOS",
+    "GOTOOLCHAIN",
+];
+
+// https://go.dev/doc/toolchain#config
+pub fn parse_go_env_file(content: impl AsRef<str>) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::default();
+
+    for line in content.as_ref().lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            vars.insert(key.trim().to_owned(), value.trim().to_owned());
+        }
+    }
+
+    vars
+}
+
+pub fn get_default_goos(os: &HostOS) -> Option<&'static str> {
+    match os {
+        HostOS::FreeBSD => Some("freebsd"),
+        HostOS::Linux => Some("linux"),
+        HostOS::MacOS => Some("darwin"),
+        HostOS::NetBSD => Some("netbsd"),
+        HostOS::OpenBSD => Some("openbsd"),
+        HostOS::Windows => Some("windows"),
+        _ => None,
+    }
+}
+
+pub fn get_default_goarch(arch: &HostArch) -> Option<&'static str> {
+    match arch {
+        HostArch::Arm => Some("arm"),
+        HostArch::Arm64 => Some("arm64"),
+        HostArch::Powerpc64 => Some("ppc64"),
+        HostArch::S390x => Some("s390x"),
+        HostArch::X64 => Some("amd64"),
+        HostArch::X86 => Some("386"),
+        _ => None,
+    }
+}
+
+/// Resolve the effective build environment, in the same order of
+/// precedence as `go env`: process environment variables, then
+/// the `go env -w` config file, and finally the defaults.
+pub fn resolve_go_env(
+    os: &HostOS,
+    arch: &HostArch,
+    env_vars: &BTreeMap<String, String>,
+    env_file_vars: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::default();
+
+    for name in BUILD_ENV_VARS {
+        // Empty values are treated as unset
+        if let Some(value) = env_vars
+            .get(name)
+            .filter(|value| !value.is_empty())
+            .or_else(|| env_file_vars.get(name).filter(|value| !value.is_empty()))
+        {
+            vars.insert(name.to_owned(), value.to_owned());
+        }
+    }
+
+    let host_os = get_default_goos(os);
+    let host_arch = get_default_goarch(arch);
+
+    if let Some(value) = host_os {
+        vars.entry("GOOS".into()).or_insert_with(|| value.into());
+    }
+
+    if let Some(value) = host_arch {
+        vars.entry("GOARCH".into()).or_insert_with(|| value.into());
+    }
+
+    // cgo is disabled by default when cross-compiling
+    if !vars.contains_key("CGO_ENABLED") {
+        let is_native = vars.get("GOOS").map(|v| v.as_str()) == host_os
+            && vars.get("GOARCH").map(|v| v.as_str()) == host_arch;
+
+        vars.insert(
+            "CGO_ENABLED".into(),
+            if is_native { "1" } else { "0" }.into(),
+        );
+    }
+
+    vars
+}
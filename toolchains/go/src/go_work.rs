@@ -6,6 +6,7 @@ use moon_pdk_api::AnyResult;
 #[derive(Debug, Default)]
 pub struct GoWork {
     pub modules: Vec<String>,
+    pub toolchain: Option<String>,
     pub version: Option<String>,
 }
 
@@ -38,6 +39,12 @@ impl GoWork {
                 continue;
             }
 
+            // toolchain go1.2.3
+            if let Some(toolchain) = line.strip_prefix("toolchain ") {
+                work.toolchain = Some(toolchain.trim().to_owned());
+                continue;
+            }
+
             // use (), replace (), ignore ()
             if line.starts_with("use (") {
                 in_use_block = true;
@@ -0,0 +1,299 @@
+use crate::config::GoToolchainConfig;
+use moon_config::{Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig};
+use moon_pdk_api::{AnyResult, Id, VirtualPath};
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+
+/// A directory of Go source files within a module.
+#[derive(Debug, Default)]
+pub struct GoPackage {
+    /// Module relative directory, or an empty string for the root.
+    pub dir: String,
+    pub name: String,
+    pub has_generate: bool,
+    pub has_testdata: bool,
+    pub has_tests: bool,
+    /// Package relative `//go:embed` patterns.
+    pub embeds: Vec<String>,
+}
+
+impl GoPackage {
+    fn to_path(&self, path: &str) -> String {
+        if self.dir.is_empty() {
+            path.to_owned()
+        } else {
+            format!("{}/{path}", self.dir)
+        }
+    }
+
+    /// Every file of the package, not only Go sources, as cgo, assembly,
+    /// and embedded files are also compiled in. Test fixtures are only
+    /// included when requested.
+    fn to_inputs(&self, with_testdata: bool) -> Vec<String> {
+        let mut inputs = vec![self.to_path("*")];
+
+        for embed in &self.embeds {
+            inputs.push(self.to_path(embed));
+        }
+
+        if with_testdata && self.has_testdata {
+            inputs.push(self.to_path("testdata/**/*"));
+        }
+
+        inputs
+    }
+}
+
+fn parse_embed_patterns(root: &VirtualPath, dir: &str, line: &str, embeds: &mut Vec<String>) {
+    for pattern in line.split_whitespace() {
+        let pattern = pattern.trim_matches(['"', '`']);
+        let pattern = pattern.strip_prefix("all:").unwrap_or(pattern);
+
+        if pattern.is_empty() {
+            continue;
+        }
+
+        let pattern = if root.join(dir).join(pattern).is_dir() {
+            format!("{pattern}/**/*")
+        } else if pattern.contains('/') {
+            pattern.to_owned()
+        } else {
+            // Files within the package directory are already an input
+            continue;
+        };
+
+        if !embeds.contains(&pattern) {
+            embeds.push(pattern);
+        }
+    }
+}
+
+fn scan_dir(root: &VirtualPath, dir: &str, packages: &mut Vec<GoPackage>) -> AnyResult<()> {
+    let mut package = GoPackage {
+        dir: dir.to_owned(),
+        ..Default::default()
+    };
+    let mut has_go_files = false;
+    let mut sub_dirs = vec![];
+
+    for entry in fs::read_dir(if dir.is_empty() {
+        root.to_owned()
+    } else {
+        root.join(dir)
+    })? {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        if path.is_dir() {
+            if file_name == "testdata" {
+                package.has_testdata = true;
+                continue;
+            }
+
+            // Ignored by the go command, and nested modules
+            if file_name.starts_with(['.', '_'])
+                || file_name == "vendor"
+                || path.join("go.mod").exists()
+            {
+                continue;
+            }
+
+            sub_dirs.push(if dir.is_empty() {
+                file_name.to_owned()
+            } else {
+                format!("{dir}/{file_name}")
+            });
+
+            continue;
+        }
+
+        let Some(stem) = file_name.strip_suffix(".go") else {
+            continue;
+        };
+
+        has_go_files = true;
+
+        let is_test = stem.ends_with("_test");
+        let content = fs::read_file(&path)?;
+
+        if is_test {
+            package.has_tests = true;
+        }
+
+        for line in content.lines() {
+            if line.starts_with("//go:generate ") {
+                package.has_generate = true;
+            } else if let Some(patterns) = line.strip_prefix("//go:embed ") {
+                parse_embed_patterns(root, dir, patterns, &mut package.embeds);
+            } else if !is_test
+                && package.name.is_empty()
+                && let Some(name) = line.strip_prefix("package ")
+            {
+                package.name = name.trim().to_owned();
+            }
+        }
+    }
+
+    if has_go_files {
+        packages.push(package);
+    }
+
+    sub_dirs.sort();
+
+    for sub_dir in sub_dirs {
+        scan_dir(root, &sub_dir, packages)?;
+    }
+
+    Ok(())
+}
+
+/// Scan the packages within a module, in the same way the `./...` pattern does.
+pub fn scan_packages(root: &VirtualPath) -> AnyResult<Vec<GoPackage>> {
+    let mut packages = vec![];
+
+    scan_dir(root, "", &mut packages)?;
+
+    Ok(packages)
+}
+
+pub struct TasksInferrer<'a> {
+    config: &'a GoToolchainConfig,
+    packages: &'a [GoPackage],
+    tasks: BTreeMap<Id, PartialTaskConfig>,
+    /// Inputs for the `go.work` and `go.work.sum` files the module belongs to.
+    work_files: Vec<String>,
+}
+
+impl<'a> TasksInferrer<'a> {
+    pub fn new(
+        config: &'a GoToolchainConfig,
+        packages: &'a [GoPackage],
+        work_files: Vec<String>,
+    ) -> TasksInferrer<'a> {
+        Self {
+            config,
+            packages,
+            tasks: BTreeMap::default(),
+            work_files,
+        }
+    }
+
+    pub fn infer(mut self) -> AnyResult<BTreeMap<Id, PartialTaskConfig>> {
+        if self.packages.is_empty() {
+            return Ok(self.tasks);
+        }
+
+        // build
+        for package in self.packages {
+            let Some(cmd_name) = package.dir.strip_prefix("cmd/") else {
+                continue;
+            };
+
+            if package.name != "main" || cmd_name.contains('/') {
+                continue;
+            }
+
+            let output = format!("bin/{cmd_name}");
+
+            // Other commands are not imported by this command
+            let inputs = self
+                .packages
+                .iter()
+                .filter(|pkg| pkg.dir == package.dir || !pkg.dir.starts_with("cmd/"));
+
+            let mut task = self.create_task(
+                format!("Builds the `{cmd_name}` command."),
+                &["build", "-o", &output, &format!("./{}", package.dir)],
+                inputs,
+                false,
+            )?;
+            task.outputs = Some(vec![Output::parse(output)?]);
+
+            self.tasks
+                .insert(Id::clean(format!("build-{cmd_name}"))?, task);
+        }
+
+        // generate
+        if self.packages.iter().any(|pkg| pkg.has_generate) {
+            self.tasks.insert(
+                Id::raw("generate"),
+                self.create_task(
+                    "Runs `//go:generate` directives.".into(),
+                    &["generate", "./..."],
+                    self.packages.iter(),
+                    true,
+                )?,
+            );
+        }
+
+        // test
+        if self.packages.iter().any(|pkg| pkg.has_tests) {
+            let mut args = vec!["test"];
+
+            if self.config.infer_tasks_test_race {
+                args.push("-race");
+            }
+
+            args.push("./...");
+
+            self.tasks.insert(
+                Id::raw("test"),
+                self.create_task(
+                    "Tests all packages.".into(),
+                    &args,
+                    self.packages.iter(),
+                    true,
+                )?,
+            );
+        }
+
+        // vet
+        self.tasks.insert(
+            Id::raw("vet"),
+            self.create_task(
+                "Vets all packages.".into(),
+                &["vet", "./..."],
+                self.packages.iter(),
+                false,
+            )?,
+        );
+
+        Ok(self.tasks)
+    }
+
+    fn create_task<'p>(
+        &self,
+        description: String,
+        args: &[&str],
+        packages: impl Iterator<Item = &'p GoPackage>,
+        with_testdata: bool,
+    ) -> AnyResult<PartialTaskConfig> {
+        let mut command = vec!["go".to_owned()];
+        command.extend(args.iter().map(|arg| arg.to_string()));
+
+        let mut inputs = vec![];
+
+        for package in packages {
+            for input in package.to_inputs(with_testdata) {
+                inputs.push(Input::parse(input)?);
+            }
+        }
+
+        inputs.push(Input::parse("go.mod")?);
+        inputs.push(Input::parse("go.sum")?);
+
+        for work_file in &self.work_files {
+            inputs.push(Input::parse(work_file)?);
+        }
+
+        Ok(PartialTaskConfig {
+            description: Some(description),
+            command: Some(PartialTaskArgs::List(command)),
+            inputs: Some(inputs),
+            toolchains: Some(OneOrMany::One(Id::raw("go"))),
+            ..Default::default()
+        })
+    }
+}
@@ -1,8 +1,11 @@
 pub mod config;
+pub mod go_env;
 pub mod go_mod;
 pub mod go_sum;
 pub mod go_work;
 
+#[cfg(feature = "wasm")]
+mod infer_tasks;
 #[cfg(feature = "wasm")]
 mod package_graph;
 #[cfg(feature = "wasm")]
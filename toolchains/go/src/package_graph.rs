@@ -180,7 +180,7 @@ pub struct GoProject {
     pub id: Id,
     /// Module path as declared when the project owns its `go.mod`
     pub alias: Option<String>,
-    root: VirtualPath,
+    pub root: VirtualPath,
     import_path: Option<String>,
     /// Direct module requires from the project's own `go.mod`
     requires: Vec<GoRequire>,
@@ -329,6 +329,10 @@ impl GoPackageGraph {
         Ok(())
     }
 
+    pub fn config(&self) -> &GoToolchainConfig {
+        &self.config
+    }
+
     pub fn projects(&self) -> &[GoProject] {
         &self.projects
     }
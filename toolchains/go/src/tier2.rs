@@ -1,13 +1,15 @@
 use crate::config::GoToolchainConfig;
+use crate::go_env::{BUILD_ENV_VARS, parse_go_env_file, resolve_go_env};
 use crate::go_mod::parse_go_mod;
 use crate::go_sum::GoSum;
 use crate::go_work::GoWork;
+use crate::infer_tasks::{TasksInferrer, scan_packages};
 use crate::package_graph::{GoPackageGraph, is_version_segment};
 use extism_pdk::*;
 use moon_config::BinEntry;
 use moon_pdk::{
-    VirtualPathExt, command_exists, get_host_env_var, get_host_environment, locate_root,
-    parse_toolchain_config_schema,
+    RealPathExt, VirtualPathExt, command_exists, get_host_env_var, get_host_environment,
+    locate_root, parse_toolchain_config_schema,
 };
 use moon_pdk_api::*;
 use starbase_utils::fs;
@@ -21,11 +23,8 @@ pub fn extend_project_graph(
     let config = parse_toolchain_config_schema::<GoToolchainConfig>(input.toolchain_config)?;
     let env = get_host_environment()?;
 
-    let mut graph = GoPackageGraph::new(
-        input.context.workspace_root,
-        config,
-        command_exists(env, "go"),
-    );
+    let workspace_root = &input.context.workspace_root;
+    let mut graph = GoPackageGraph::new(workspace_root.clone(), config, command_exists(env, "go"));
 
     // First pass through, we figure out what projects we have and what their root import path is
     graph.load_projects(input.project_sources)?;
@@ -34,13 +33,25 @@ pub fn extend_project_graph(
 
     // On the second pass, we work through all the projects and resolve their dependencies
     for project in graph.projects() {
-        let project_output = ExtendProjectOutput {
+        let mut project_output = ExtendProjectOutput {
             alias: project.alias.clone(),
             dependencies: graph.project_dependencies(project)?,
             ..Default::default()
         };
 
-        if project_output.alias.is_some() || !project_output.dependencies.is_empty() {
+        // Only infer tasks for module roots
+        if graph.config().infer_tasks_from_packages && project.root.join("go.mod").exists() {
+            let packages = scan_packages(&project.root)?;
+            let work_files = get_go_work_inputs(graph.config(), &project.root, workspace_root);
+
+            project_output.tasks =
+                TasksInferrer::new(graph.config(), &packages, work_files).infer()?;
+        }
+
+        if project_output.alias.is_some()
+            || !project_output.dependencies.is_empty()
+            || !project_output.tasks.is_empty()
+        {
             output
                 .extended_projects
                 .insert(project.id.to_owned(), project_output);
@@ -52,6 +63,44 @@ pub fn extend_project_graph(
     Ok(Json(output))
 }
 
+/// Inputs for the `go.work` and `go.work.sum` files that a module belongs to,
+/// relative to the project if possible, otherwise to the workspace.
+fn get_go_work_inputs(
+    config: &GoToolchainConfig,
+    project_root: &VirtualPath,
+    workspace_root: &VirtualPath,
+) -> Vec<String> {
+    let mut inputs = vec![];
+
+    if !config.workspaces {
+        return inputs;
+    }
+
+    let Some(work_root) = locate_root(project_root, "go.work") else {
+        return inputs;
+    };
+
+    for file_name in ["go.work", "go.work.sum"] {
+        if !work_root.join(file_name).exists() {
+            continue;
+        }
+
+        if work_root == *project_root {
+            inputs.push(file_name.to_owned());
+        } else if let Ok(rel_root) = work_root.strip_prefix(workspace_root) {
+            inputs.push(format!(
+                "/{}",
+                rel_root
+                    .join(file_name)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            ));
+        }
+    }
+
+    inputs
+}
+
 fn gather_shared_paths(
     env: &HostEnvironment,
     globals_dir: Option<&VirtualPath>,
@@ -320,7 +369,7 @@ pub fn setup_environment(
 
 #[plugin_fn]
 pub fn hash_task_contents(
-    Json(_): Json<HashTaskContentsInput>,
+    Json(input): Json<HashTaskContentsInput>,
 ) -> FnResult<Json<HashTaskContentsOutput>> {
     let env = get_host_environment()?;
 
@@ -329,11 +378,86 @@ pub fn hash_task_contents(
     map.insert("arch".into(), json::Value::String(env.arch.to_string()));
     map.insert("libc".into(), json::Value::String(env.libc.to_string()));
 
+    // Cross-compilation and build settings
+    let mut env_vars = BTreeMap::default();
+
+    for name in BUILD_ENV_VARS {
+        if let Some(value) = get_host_env_var(name)? {
+            env_vars.insert(name.to_owned(), value);
+        }
+    }
+
+    let env_file_vars = match load_go_env_file(env)? {
+        Some(content) => parse_go_env_file(content),
+        None => BTreeMap::default(),
+    };
+
+    map.insert(
+        "env".into(),
+        json::to_value(resolve_go_env(
+            &env.os,
+            &env.arch,
+            &env_vars,
+            &env_file_vars,
+        ))?,
+    );
+
+    // The `toolchain` directive changes the compiler
+    let project_root = input
+        .context
+        .get_project_root_from_source(&input.project.source);
+
+    if let Some(root) = locate_root(&project_root, "go.mod")
+        && let Some(toolchain) = parse_go_mod(fs::read_file(root.join("go.mod"))?)?.toolchain
+    {
+        map.insert("modToolchain".into(), json::Value::String(toolchain));
+    }
+
+    if let Some(root) = locate_root(&project_root, "go.work")
+        && let Some(toolchain) = GoWork::parse(fs::read_file(root.join("go.work"))?)?.toolchain
+    {
+        map.insert("workToolchain".into(), json::Value::String(toolchain));
+    }
+
     Ok(Json(HashTaskContentsOutput {
         contents: vec![json::Value::Object(map)],
     }))
 }
 
+// https://pkg.go.dev/cmd/go#hdr-Environment_variables
+fn load_go_env_file(env: &HostEnvironment) -> AnyResult<Option<String>> {
+    let file = match get_host_env_var("GOENV")? {
+        Some(value) if value == "off" => return Ok(None),
+        Some(value) => RealPath::new(value).to_virtual_path()?,
+        None => get_user_config_dir(env)?.map(|dir| dir.join("go/env")),
+    };
+
+    if let Some(file) = file
+        && file.exists()
+    {
+        return Ok(Some(fs::read_file(file)?));
+    }
+
+    Ok(None)
+}
+
+// https://pkg.go.dev/os#UserConfigDir
+fn get_user_config_dir(env: &HostEnvironment) -> AnyResult<Option<VirtualPath>> {
+    let dir = match env.os {
+        HostOS::MacOS => Some(env.home_dir.join("Library/Application Support")),
+        HostOS::Windows => match get_host_env_var("AppData")? {
+            Some(value) => RealPath::new(value).to_virtual_path()?,
+            None => None,
+        },
+        _ => match get_host_env_var("XDG_CONFIG_HOME")? {
+            Some(value) => RealPath::new(value).to_virtual_path()?,
+            None => Some(env.home_dir.join(".config")),
+        },
+    };
+
+    Ok(dir)
+}
+
 fn get_base_module(module: &str) -> String {
     let mut parts = module.split('/');
     let mut base = String::new();
@@ -0,0 +1,5 @@
+package main
+
+//go:generate stringer -type=Mode
+
+func main() {}
@@ -0,0 +1,6 @@
+package db
+
+import "embed"
+
+//go:embed schema
+var Schema embed.FS
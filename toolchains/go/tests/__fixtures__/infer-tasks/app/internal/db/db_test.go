@@ -0,0 +1,5 @@
+package db
+
+import "testing"
+
+func TestDb(t *testing.T) {}
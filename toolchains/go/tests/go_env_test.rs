@@ -0,0 +1,93 @@
+use go_toolchain::go_env::*;
+use moon_pdk_api::{HostArch, HostOS};
+use std::collections::BTreeMap;
+
+mod go_env {
+    use super::*;
+
+    fn vars<const N: usize>(list: [(&str, &str); N]) -> BTreeMap<String, String> {
+        BTreeMap::from_iter(list.map(|(k, v)| (k.to_owned(), v.to_owned())))
+    }
+
+    #[test]
+    fn parses_env_file() {
+        let content = r#"
+# Written by go env -w
+GOFLAGS=-tags=integration
+GOPRIVATE=github.com/moonrepo
+"#;
+
+        assert_eq!(
+            parse_go_env_file(content),
+            vars([
+                ("GOFLAGS", "-tags=integration"),
+                ("GOPRIVATE", "github.com/moonrepo")
+            ])
+        );
+    }
+
+    #[test]
+    fn defaults_to_host() {
+        assert_eq!(
+            resolve_go_env(
+                &HostOS::MacOS,
+                &HostArch::Arm64,
+                &BTreeMap::default(),
+                &BTreeMap::default()
+            ),
+            vars([
+                ("CGO_ENABLED", "1"),
+                ("GOARCH", "arm64"),
+                ("GOOS", "darwin")
+            ])
+        );
+    }
+
+    #[test]
+    fn env_vars_take_precedence_over_env_file() {
+        assert_eq!(
+            resolve_go_env(
+                &HostOS::Linux,
+                &HostArch::X64,
+                &vars([("GOFLAGS", "-tags=a"), ("GOEXPERIMENT", "")]),
+                &vars([("GOFLAGS", "-tags=b"), ("GOEXPERIMENT", "rangefunc")]),
+            ),
+            vars([
+                ("CGO_ENABLED", "1"),
+                ("GOARCH", "amd64"),
+                ("GOEXPERIMENT", "rangefunc"),
+                ("GOFLAGS", "-tags=a"),
+                ("GOOS", "linux")
+            ])
+        );
+    }
+
+    #[test]
+    fn disables_cgo_when_cross_compiling() {
+        assert_eq!(
+            resolve_go_env(
+                &HostOS::Linux,
+                &HostArch::X64,
+                &vars([("GOOS", "windows")]),
+                &BTreeMap::default(),
+            ),
+            vars([
+                ("CGO_ENABLED", "0"),
+                ("GOARCH", "amd64"),
+                ("GOOS", "windows")
+            ])
+        );
+
+        assert_eq!(
+            resolve_go_env(
+                &HostOS::Linux,
+                &HostArch::X64,
+                &vars([("GOOS", "windows"), ("CGO_ENABLED", "1")]),
+                &BTreeMap::default(),
+            )
+            .get("CGO_ENABLED")
+            .unwrap(),
+            "1"
+        );
+    }
+}
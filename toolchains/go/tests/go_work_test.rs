@@ -72,4 +72,21 @@ use ./b
         assert_eq!(go_work.version.unwrap(), "1.24.0");
         assert_eq!(go_work.modules, vec!["a", "b"]);
     }
+
+    #[test]
+    fn parses_toolchain_directive() {
+        let content = r#"
+go 1.24.0
+
+toolchain go1.24.2
+
+use ./a
+"#;
+
+        let go_work = GoWork::parse(content).unwrap();
+
+        assert_eq!(go_work.version.unwrap(), "1.24.0");
+        assert_eq!(go_work.toolchain.unwrap(), "go1.24.2");
+        assert_eq!(go_work.modules, vec!["a"]);
+    }
 }
@@ -1,5 +1,5 @@
 use moon_common::Id;
-use moon_config::DependencyScope;
+use moon_config::{DependencyScope, Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig};
 use moon_pdk_api::*;
 use moon_pdk_test_utils::{create_empty_moon_sandbox, create_moon_sandbox};
 use serde_json::json;
@@ -220,6 +220,140 @@ mod go_toolchain_tier2 {
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_tasks_when_disabled() {
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("go").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({ "inferRelationships": false });
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert!(
+                output
+                    .extended_projects
+                    .get("app")
+                    .unwrap()
+                    .tasks
+                    .is_empty()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_tasks_from_packages_when_enabled() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("go").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferRelationships": false,
+                "inferTasksFromPackages": true,
+                "inferTasksTestRace": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                ["build-cli", "build-server", "generate", "test", "vet"]
+            );
+
+            assert_eq!(
+                tasks.get("build-server").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Builds the `server` command.".into()),
+                    command: Some(PartialTaskArgs::List(vec![
+                        "go".into(),
+                        "build".into(),
+                        "-o".into(),
+                        "bin/server".into(),
+                        "./cmd/server".into(),
+                    ])),
+                    inputs: Some(vec![
+                        Input::parse("cmd/server/*").unwrap(),
+                        Input::parse("internal/db/*").unwrap(),
+                        Input::parse("internal/db/schema/**/*").unwrap(),
+                        Input::parse("go.mod").unwrap(),
+                        Input::parse("go.sum").unwrap(),
+                    ]),
+                    outputs: Some(vec![Output::parse("bin/server").unwrap()]),
+                    toolchains: Some(OneOrMany::One(Id::raw("go"))),
+                    ..Default::default()
+                }
+            );
+
+            let test = tasks.get("test").unwrap();
+
+            assert_eq!(
+                test.command,
+                Some(PartialTaskArgs::List(vec![
+                    "go".into(),
+                    "test".into(),
+                    "-race".into(),
+                    "./...".into(),
+                ]))
+            );
+            assert_eq!(
+                test.inputs,
+                Some(vec![
+                    Input::parse("cmd/cli/*").unwrap(),
+                    Input::parse("cmd/server/*").unwrap(),
+                    Input::parse("internal/db/*").unwrap(),
+                    Input::parse("internal/db/schema/**/*").unwrap(),
+                    Input::parse("internal/db/testdata/**/*").unwrap(),
+                    Input::parse("go.mod").unwrap(),
+                    Input::parse("go.sum").unwrap(),
+                ])
+            );
+
+            // Test fixtures are only an input for tests
+            assert!(
+                !tasks
+                    .get("vet")
+                    .unwrap()
+                    .inputs
+                    .as_ref()
+                    .unwrap()
+                    .contains(&Input::parse("internal/db/testdata/**/*").unwrap())
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn includes_go_work_in_inferred_task_inputs() {
+            let sandbox = create_moon_sandbox("projects-workspace");
+            sandbox.create_file("go.work.sum", "");
+
+            let plugin = sandbox.create_toolchain("go").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("a"), "a".into());
+            input.toolchain_config = json!({
+                "inferRelationships": false,
+                "inferTasksFromPackages": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("a").unwrap().tasks;
+
+            assert_eq!(
+                tasks.get("vet").unwrap().inputs,
+                Some(vec![
+                    Input::parse("*").unwrap(),
+                    Input::parse("pkg/*").unwrap(),
+                    Input::parse("go.mod").unwrap(),
+                    Input::parse("go.sum").unwrap(),
+                    Input::parse("/go.work").unwrap(),
+                    Input::parse("/go.work.sum").unwrap(),
+                ])
+            );
+        }
+
         mod go_list {
             use super::*;
 
@@ -1357,4 +1491,45 @@ mod go_toolchain_tier2 {
             );
         }
     }
+
+    mod hash_task_contents {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn includes_build_env_and_toolchains() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "go.work",
+                "go 1.24.0\n\ntoolchain go1.24.3\n\nuse ./project\n",
+            );
+            sandbox.create_file(
+                "project/go.mod",
+                "module example.com/project\n\ngo 1.24.0\n\ntoolchain go1.24.2\n",
+            );
+
+            // `os.UserConfigDir` on Linux and macOS
+            sandbox.create_file(".home/.config/go/env", "GOFLAGS=-tags=integration\n");
+            sandbox.create_file(
+                ".home/Library/Application Support/go/env",
+                "GOFLAGS=-tags=integration\n",
+            );
+
+            let plugin = sandbox.create_toolchain("go").await;
+
+            let output = plugin
+                .hash_task_contents(HashTaskContentsInput::default())
+                .await;
+
+            let contents = &output.contents[0];
+            let go_env = contents.get("env").unwrap();
+
+            #[cfg(unix)]
+            assert_eq!(go_env.get("GOFLAGS").unwrap(), "-tags=integration");
+            assert!(go_env.get("GOOS").is_some());
+            assert!(go_env.get("GOARCH").is_some());
+            assert!(go_env.get("CGO_ENABLED").is_some());
+            assert_eq!(contents.get("modToolchain").unwrap(), "go1.24.2");
+            assert_eq!(contents.get("workToolchain").unwrap(), "go1.24.3");
+        }
+    }
 }
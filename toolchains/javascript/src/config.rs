@@ -33,6 +33,23 @@ impl JavaScriptPackageManager {
         matches!(self, Self::Npm | Self::Nub | Self::Pnpm | Self::Yarn)
     }
 
+    /// Argument that disables `pre` and `post` scripts when running a script,
+    /// which is passed before the script name. Bun, Nub, and Yarn v1 have no
+    /// equivalent.
+    pub fn get_ignore_scripts_arg(&self) -> Option<&'static str> {
+        match self {
+            Self::Npm => Some("--ignore-scripts"),
+            _ => None,
+        }
+    }
+
+    /// Whether `pre` and `post` scripts are automatically ran when running
+    /// a script. pnpm does not run them by default. Yarn v2+ doesn't either,
+    /// but it can't be distinguished from Yarn v1, which does.
+    pub fn runs_lifecycle_scripts(&self) -> bool {
+        !matches!(self, Self::Deno | Self::Pnpm)
+    }
+
     /// Runs as a standalone binary and does not require the
     /// Node.js toolchain to operate.
     pub fn is_standalone(&self) -> bool {
@@ -109,10 +126,23 @@ config_struct!(
         /// as workspace dependencies.
         pub dependency_version_format: JavaScriptDependencyVersionFormat,
 
+        /// When inferring tasks from `package.json` scripts, infer `pre` and
+        /// `post` scripts as separate tasks. The `pre` task is a dependency of
+        /// the main task, while the `post` task depends on the main task, as
+        /// it must run after it.
+        ///
+        /// Only applies when the main task would run the same scripts as before:
+        /// with pnpm (which doesn't run hooks), or with npm when there's no `post`
+        /// script (the main task runs with `--ignore-scripts`). Has no effect
+        /// for Bun, Nub, and Yarn, which can't skip hooks, nor for npm scripts
+        /// with a `post` script, which would be skipped too.
+        pub infer_tasks_from_lifecycle_scripts: bool,
+
         /// Automatically infer moon tasks from `package.json` scripts or
         /// `deno.json` tasks. Some caveats to be aware of:
         ///
-        /// - Lifecycle (pre, post) scripts are not inferred.
+        /// - Lifecycle (pre, post) scripts are not inferred, unless
+        ///   `inferTasksFromLifecycleScripts` is enabled.
         /// - Outputs will be automatically detected (if possible).
         /// - Script names that contain `:` will be converted to `-`.
         /// - Script names that contain `dev`, `start`, `serve`, or `preview`
@@ -1,18 +1,26 @@
 use crate::config::{JavaScriptPackageManager, JavaScriptToolchainConfig};
 use crate::lockfiles::DenoJsonTask;
+use crate::tool_configs::ToolConfigs;
 use moon_common::Id;
 use moon_config::{
-    OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, PartialTaskDependency,
+    Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, PartialTaskDependency,
     PartialTaskOptionsConfig, TaskOptionCache, TaskOptionRunInCI, TaskPreset,
 };
 use moon_pdk::{AnyResult, map_miette_error};
 use moon_target::Target;
 use std::collections::{BTreeMap, HashSet};
 
+fn has_script(scripts: &BTreeMap<&String, &String>, name: &str) -> bool {
+    scripts
+        .iter()
+        .any(|(key, value)| *key == name && !value.is_empty())
+}
+
 pub struct TasksInferrer<'a> {
     config: &'a JavaScriptToolchainConfig,
     tasks: BTreeMap<Id, PartialTaskConfig>,
     life_cycles: HashSet<String>,
+    tool_configs: ToolConfigs,
 }
 
 impl<'a> TasksInferrer<'a> {
@@ -24,9 +32,15 @@ impl<'a> TasksInferrer<'a> {
                 .split('|')
                 .map(|lc| lc.to_string())
                 .collect::<HashSet<_>>(),
+            tool_configs: ToolConfigs::default(),
         }
     }
 
+    pub fn with_tool_configs(mut self, tool_configs: ToolConfigs) -> Self {
+        self.tool_configs = tool_configs;
+        self
+    }
+
     pub fn infer_from_deno_tasks(
         mut self,
         tasks: &BTreeMap<String, DenoJsonTask>,
@@ -36,18 +50,10 @@ impl<'a> TasksInferrer<'a> {
             let deps = deno_task.get_dependencies();
 
             if self.is_valid(name, command) {
-                let mut task = self.create_task(name, command)?;
-
-                if !deps.is_empty() {
-                    for dep_name in deps {
-                        let dep_id = self.create_task_id(dep_name)?;
+                let mut task = self.create_task(name, command, false)?;
 
-                        task.deps
-                            .get_or_insert_default()
-                            .push(PartialTaskDependency::Target(
-                                Target::parse(&format!("~:{dep_id}")).map_err(map_miette_error)?,
-                            ));
-                    }
+                for dep_name in deps {
+                    self.add_task_dep(&mut task, dep_name)?;
                 }
 
                 self.tasks.insert(self.create_task_id(name)?, task);
@@ -61,21 +67,112 @@ impl<'a> TasksInferrer<'a> {
         mut self,
         scripts: BTreeMap<&String, &String>,
     ) -> AnyResult<BTreeMap<Id, PartialTaskConfig>> {
-        for (name, script) in scripts {
-            if self.is_valid(name, script) {
-                self.tasks
-                    .insert(self.create_task_id(name)?, self.create_task(name, script)?);
+        let with_life_cycles = self.config.infer_tasks_from_lifecycle_scripts;
+
+        for (name, script) in &scripts {
+            if with_life_cycles
+                && let Some((hook, main_name)) = self.get_lifecycle_hook(name, &scripts)
+            {
+                if !script.is_empty() && self.get_hook_chaining(main_name, &scripts).is_some() {
+                    let mut task = self.create_task(name, script, false)?;
+
+                    if hook == "post" {
+                        self.add_task_dep(&mut task, main_name)?;
+                    }
+
+                    self.tasks.insert(self.create_task_id(name)?, task);
+                }
+
+                continue;
+            }
+
+            if !self.is_valid(name, script) {
+                continue;
+            }
+
+            let chaining = if with_life_cycles {
+                self.get_hook_chaining(name, &scripts)
+            } else {
+                None
+            };
+
+            let mut task = self.create_task(name, script, chaining.unwrap_or_default())?;
+
+            if chaining.is_some() && has_script(&scripts, &format!("pre{name}")) {
+                self.add_task_dep(&mut task, &format!("pre{name}"))?;
             }
+
+            self.tasks.insert(self.create_task_id(name)?, task);
         }
 
         Ok(self.tasks)
     }
 
+    fn add_task_dep(&self, task: &mut PartialTaskConfig, dep_name: &str) -> AnyResult<()> {
+        let dep_id = self.create_task_id(dep_name)?;
+
+        task.deps
+            .get_or_insert_default()
+            .push(PartialTaskDependency::Target(
+                Target::parse(&format!("~:{dep_id}")).map_err(map_miette_error)?,
+            ));
+
+        Ok(())
+    }
+
+    /// Return the hook type and main script name if the
+    /// script is a `pre` or `post` hook of another script.
+    fn get_lifecycle_hook<'s>(
+        &self,
+        name: &'s str,
+        scripts: &BTreeMap<&String, &String>,
+    ) -> Option<(&'static str, &'s str)> {
+        ["pre", "post"].into_iter().find_map(|hook| {
+            let main_name = name.strip_prefix(hook)?;
+
+            scripts
+                .iter()
+                .any(|(key, value)| *key == main_name && self.is_valid(key, value))
+                .then_some((hook, main_name))
+        })
+    }
+
+    /// Return whether the `pre` and `post` hooks of a script can be inferred as
+    /// separate tasks, without changing the scripts that the main task runs. If so,
+    /// return whether the main task must ignore scripts to not run hooks twice.
+    fn get_hook_chaining(&self, name: &str, scripts: &BTreeMap<&String, &String>) -> Option<bool> {
+        let has_pre = has_script(scripts, &format!("pre{name}"));
+        let has_post = has_script(scripts, &format!("post{name}"));
+
+        if !has_pre && !has_post {
+            return None;
+        }
+
+        let package_manager = self.config.package_manager.unwrap_or_default();
+
+        if !package_manager.runs_lifecycle_scripts() {
+            return Some(false);
+        }
+
+        // Ignoring scripts also skips the `post` hook, but it can't be
+        // ran after the main task when running the main task directly
+        if has_post || package_manager.get_ignore_scripts_arg().is_none() {
+            return None;
+        }
+
+        Some(true)
+    }
+
     fn create_task_id(&self, name: &str) -> AnyResult<Id> {
         Ok(Id::clean(name)?)
     }
 
-    fn create_task(&self, name: &str, script: &str) -> AnyResult<PartialTaskConfig> {
+    fn create_task(
+        &self,
+        name: &str,
+        script: &str,
+        ignore_scripts: bool,
+    ) -> AnyResult<PartialTaskConfig> {
         let package_manager = self.config.package_manager.unwrap_or_default();
         let script_args = shell_words::split(script)?;
 
@@ -98,11 +195,15 @@ impl<'a> TasksInferrer<'a> {
                 config.description = Some(format!("Inherited from `{name}` package.json script."));
 
                 // command + args
-                config.command = Some(PartialTaskArgs::List(vec![
-                    package_manager.to_string(),
-                    "run".to_string(),
-                    name.to_string(),
-                ]));
+                let mut command = vec![package_manager.to_string(), "run".to_string()];
+
+                if ignore_scripts && let Some(arg) = package_manager.get_ignore_scripts_arg() {
+                    command.push(arg.to_string());
+                }
+
+                command.push(name.to_string());
+
+                config.command = Some(PartialTaskArgs::List(command));
             }
         };
 
@@ -125,6 +226,9 @@ impl<'a> TasksInferrer<'a> {
             }
         }
 
+        // inputs and outputs from tool configs
+        self.infer_from_tool_configs(&script_args, &mut config)?;
+
         // preset
         if self.is_dev_script_name(name) {
             if self.has_watch_option(script) {
@@ -165,6 +269,107 @@ impl<'a> TasksInferrer<'a> {
         Ok(config)
     }
 
+    fn infer_from_tool_configs(
+        &self,
+        script_args: &[String],
+        config: &mut PartialTaskConfig,
+    ) -> AnyResult<()> {
+        let configs = &self.tool_configs;
+        let has_arg = |option: &str| script_args.iter().any(|arg| arg == option);
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+
+        for (index, arg) in script_args.iter().enumerate() {
+            let next_arg = script_args.get(index + 1).map(|arg| arg.as_str());
+
+            match arg.as_str() {
+                "vite" if next_arg == Some("build") => {
+                    outputs.extend(configs.vite_out_dir.clone());
+                }
+                "next" if next_arg == Some("build") => {
+                    outputs.extend(configs.next_dist_dir.clone());
+                }
+                "tsc" if !has_arg("--noEmit") => {
+                    let tsconfig = script_args
+                        .iter()
+                        .enumerate()
+                        .find_map(|(i, arg)| {
+                            if arg == "-p" || arg == "--project" {
+                                script_args.get(i + 1).map(|arg| arg.to_owned())
+                            } else {
+                                arg.strip_prefix("--project=").map(|arg| arg.to_owned())
+                            }
+                        })
+                        .map(|path| {
+                            if path.ends_with(".json") {
+                                path
+                            } else {
+                                format!("{}/tsconfig.json", path.trim_end_matches('/'))
+                            }
+                        })
+                        .unwrap_or_else(|| "tsconfig.json".into());
+
+                    outputs.extend(configs.load_tsconfig_out_dir(&tsconfig)?);
+                }
+                "vitest" if has_arg("--coverage") => {
+                    outputs.push(
+                        configs
+                            .vitest_coverage_dir
+                            .clone()
+                            .unwrap_or_else(|| "coverage".into()),
+                    );
+                }
+                "jest" if has_arg("--coverage") => {
+                    outputs.push(
+                        configs
+                            .jest_coverage_dir
+                            .clone()
+                            .unwrap_or_else(|| "coverage".into()),
+                    );
+                }
+                "eslint" => {
+                    inputs.extend(configs.eslint_ignore.iter().cloned());
+                }
+                "prettier" => {
+                    inputs.extend(configs.prettier_ignore.iter().cloned());
+                }
+                _ => {}
+            };
+        }
+
+        // Explicit output options take precedence over config files
+        if config.outputs.is_none() {
+            for output in outputs {
+                if let Some(output_path) = self.clean_output_path(&output) {
+                    let output = Output::parse(output_path)?;
+                    let list = config.outputs.get_or_insert_default();
+
+                    if !list.contains(&output) {
+                        list.push(output);
+                    }
+                }
+            }
+        }
+
+        // Ignored files are excluded from the default inputs
+        if !inputs.is_empty() {
+            let list = match &mut config.inputs {
+                Some(list) => list,
+                None => config.inputs.insert(vec![Input::parse("**/*")?]),
+            };
+
+            for input in inputs {
+                let input = Input::parse(input)?;
+
+                if !list.contains(&input) {
+                    list.push(input);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn clean_output_path(&self, output: &str) -> Option<String> {
         if output.starts_with("..")
             || output.starts_with('/')
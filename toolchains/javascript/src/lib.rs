@@ -8,6 +8,8 @@ pub mod package_json;
 mod tier1;
 #[cfg(feature = "wasm")]
 mod tier2;
+#[cfg(feature = "wasm")]
+mod tool_configs;
 
 #[cfg(feature = "wasm")]
 pub use tier1::*;
@@ -1,5 +1,5 @@
 use super::yarn::parse_yarn_lock_content;
-use super::{parse_name_and_version, parse_version_spec};
+use super::{LockGraph, parse_name_and_version, parse_version_spec};
 use moon_pdk::{AnyResult, ExecCommandInput, VirtualPath, exec};
 use moon_pdk_api::{LockDependency, ParseLockOutput};
 use serde::Deserialize;
@@ -75,72 +75,149 @@ pub struct BunLock {
     pub workspaces: BTreeMap<String, BunLockPackageJson>,
 }
 
+// Nested packages are keyed by their parent's path, e.g. `parent/child`
+// or `@scope/parent/@scope/child`, so split into package names.
+fn split_package_path(path: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut start = 0;
+    let mut in_scope = false;
+
+    for (index, ch) in path.char_indices() {
+        if ch != '/' {
+            continue;
+        }
+
+        if path[start..].starts_with('@') && !in_scope {
+            in_scope = true;
+            continue;
+        }
+
+        names.push(&path[start..index]);
+        start = index + 1;
+        in_scope = false;
+    }
+
+    if start < path.len() {
+        names.push(&path[start..]);
+    }
+
+    names
+}
+
+// Resolve a dependency by looking in the current package,
+// and then each parent, similar to `node_modules`.
+fn resolve_dependency(graph: &LockGraph, path: &str, name: &str) -> Option<String> {
+    let mut parents = split_package_path(path);
+
+    loop {
+        let id = if parents.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}/{name}", parents.join("/"))
+        };
+
+        if graph.contains(&id) {
+            return Some(id);
+        }
+
+        parents.pop()?;
+    }
+}
+
 pub fn parse_bun_lock(path: &VirtualPath, output: &mut ParseLockOutput) -> AnyResult<()> {
     let content = fs::read_file(path)?;
     let lock: BunLock = json::parse(&content)?; // JSON5
+    let mut graph = LockGraph::default();
+    let mut deps = vec![];
 
-    for package in lock.packages.into_values() {
-        let (name, version, integrity) = match &package {
+    for (key, package) in &lock.packages {
+        let (name, version, integrity, data) = match package {
             BunLockPackage::Workspace(values) => {
                 if let Some((name, ref_name)) = values[0].split_once("@workspace:")
                     && let Some(ref_package) = lock.workspaces.get(ref_name)
                 {
-                    output
+                    for dep_name in ref_package
                         .dependencies
-                        .entry(name.to_string())
-                        .or_default()
-                        .push(LockDependency {
+                        .keys()
+                        .chain(ref_package.dev_dependencies.keys())
+                        .chain(ref_package.optional_dependencies.keys())
+                        .chain(ref_package.peer_dependencies.keys())
+                    {
+                        deps.push((key, dep_name));
+                    }
+
+                    graph.add_package(
+                        key,
+                        name,
+                        LockDependency {
                             version: match &ref_package.version {
                                 Some(version) => parse_version_spec(version)?,
                                 None => None,
                             },
                             ..Default::default()
-                        });
+                        },
+                    );
                 }
 
                 continue;
             }
-            BunLockPackage::Dependency1(id, _registry, _data, integrity) => {
+            BunLockPackage::Dependency1(id, _registry, data, integrity) => {
                 let Some((name, version)) = parse_name_and_version(id, "") else {
                     continue;
                 };
 
-                (name, version, Some(integrity))
+                (name, version, Some(integrity), data)
             }
-            BunLockPackage::Dependency2(id, _data, _tag, integrity) => {
+            BunLockPackage::Dependency2(id, data, _tag, integrity) => {
                 let Some((name, version)) = parse_name_and_version(id, "") else {
                     continue;
                 };
 
-                (name, version, Some(integrity))
+                (name, version, Some(integrity), data)
             }
-            BunLockPackage::Dependency3(id, _data, integrity) => {
+            BunLockPackage::Dependency3(id, data, integrity) => {
                 let Some((name, version)) = parse_name_and_version(id, "") else {
                     continue;
                 };
 
-                (name, version, Some(integrity))
+                (name, version, Some(integrity), data)
             }
-            BunLockPackage::Dependency4(id, _data) => {
+            BunLockPackage::Dependency4(id, data) => {
                 let Some((name, version)) = parse_name_and_version(id, "") else {
                     continue;
                 };
 
-                (name, version, None)
+                (name, version, None, data)
             }
         };
 
-        output
-            .dependencies
-            .entry(name.to_string())
-            .or_default()
-            .push(LockDependency {
+        for field in ["dependencies", "optionalDependencies", "peerDependencies"] {
+            if let Some(map) = data.get(field).and_then(|value| value.as_object()) {
+                for dep_name in map.keys() {
+                    deps.push((key, dep_name));
+                }
+            }
+        }
+
+        graph.add_package(
+            key,
+            name,
+            LockDependency {
                 version: parse_version_spec(version)?,
                 hash: integrity.cloned(),
                 ..Default::default()
-            });
+            },
+        );
     }
 
+    for (key, dep_name) in deps {
+        if let Some(dep_id) = resolve_dependency(&graph, key, dep_name) {
+            graph.add_edge(key, dep_id);
+        }
+    }
+
+    graph.apply(output);
+
     Ok(())
 }
 
@@ -158,6 +235,23 @@ mod tests {
         json::parse(content).unwrap()
     }
 
+    #[test]
+    fn splits_package_paths() {
+        assert_eq!(split_package_path("react"), vec!["react"]);
+        assert_eq!(
+            split_package_path("solid-js/seroval"),
+            vec!["solid-js", "seroval"]
+        );
+        assert_eq!(
+            split_package_path("@babel/core/@babel/types"),
+            vec!["@babel/core", "@babel/types"]
+        );
+        assert_eq!(
+            split_package_path("a/@babel/core/debug"),
+            vec!["a", "@babel/core", "debug"]
+        );
+    }
+
     #[test]
     fn parses_npm_entry() {
         assert!(matches!(
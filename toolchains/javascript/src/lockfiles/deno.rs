@@ -1,4 +1,4 @@
-use super::{parse_name_and_version, parse_version_spec};
+use super::{LockGraph, parse_name_and_version, parse_version_spec};
 use crate::config::CatalogsMap;
 use deno_lockfile::LockfileContent;
 use moon_pdk::{AnyResult, VirtualPath};
@@ -13,37 +13,64 @@ use std::collections::BTreeMap;
 pub fn parse_deno_lock(path: &VirtualPath, output: &mut ParseLockOutput) -> AnyResult<()> {
     let lockfile_content: JsonValue = json::read_file(path)?;
     let lockfile = LockfileContent::from_json(lockfile_content)?;
+    let packages = lockfile.packages;
+    let mut graph = LockGraph::default();
+
+    for (key, value) in &packages.jsr {
+        let id = format!("jsr:{key}");
+
+        // Dependencies are requirements, so resolve them through the specifiers
+        for dep in &value.dependencies {
+            if let Some(version) = packages.specifiers.get(dep) {
+                graph.add_edge(
+                    &id,
+                    format!("{}{}@{version}", dep.kind.scheme_with_colon(), dep.req.name),
+                );
+            }
+        }
 
-    for (key, value) in lockfile.packages.jsr {
-        output
-            .dependencies
-            .entry(format!("jsr:{}", key.name))
-            .or_default()
-            .push(LockDependency {
-                hash: Some(value.integrity),
+        graph.add_package(
+            id,
+            format!("jsr:{}", key.name),
+            LockDependency {
+                hash: Some(value.integrity.clone()),
                 // Version is fully qualified
                 version: parse_version_spec(key.version.to_string())?,
                 ..Default::default()
-            });
+            },
+        );
     }
 
-    for (key, value) in lockfile.packages.npm {
-        let Some((name, version)) = parse_name_and_version(&key, "_") else {
+    for (key, value) in &packages.npm {
+        let id = format!("npm:{key}");
+
+        // Dependencies are already resolved to package IDs
+        for dep_id in value
+            .dependencies
+            .values()
+            .chain(value.optional_dependencies.values())
+        {
+            graph.add_edge(&id, format!("npm:{dep_id}"));
+        }
+
+        let Some((name, version)) = parse_name_and_version(key, "_") else {
             continue;
         };
 
-        output
-            .dependencies
-            .entry(format!("npm:{name}"))
-            .or_default()
-            .push(LockDependency {
-                hash: value.integrity,
+        graph.add_package(
+            id,
+            format!("npm:{name}"),
+            LockDependency {
+                hash: value.integrity.clone(),
                 // Version is fully qualified
                 version: parse_version_spec(version)?,
                 ..Default::default()
-            });
+            },
+        );
     }
 
+    graph.apply(output);
+
     Ok(())
 }
 
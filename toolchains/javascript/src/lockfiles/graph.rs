@@ -0,0 +1,336 @@
+use moon_pdk_api::{LockDependency, ParseLockOutput};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Debug, Default)]
+pub struct LockGraphNode {
+    /// The package name and dependency to include in the output.
+    /// Internal nodes (nested copies, snapshots, etc) have none.
+    pub package: Option<(String, LockDependency)>,
+
+    /// IDs of the nodes this node depends on.
+    pub edges: BTreeSet<String>,
+}
+
+/// The resolved dependency graph of a lockfile, where each node is keyed
+/// by a lockfile specific ID (path, descriptor, snapshot, etc).
+#[derive(Debug, Default)]
+pub struct LockGraph {
+    pub nodes: BTreeMap<String, LockGraphNode>,
+}
+
+impl LockGraph {
+    pub fn add_node(&mut self, id: impl Into<String>) -> &mut LockGraphNode {
+        self.nodes.entry(id.into()).or_default()
+    }
+
+    pub fn add_package(
+        &mut self,
+        id: impl Into<String>,
+        name: impl Into<String>,
+        dependency: LockDependency,
+    ) -> &mut LockGraphNode {
+        let node = self.add_node(id);
+        node.package = Some((name.into(), dependency));
+        node
+    }
+
+    pub fn add_edge(&mut self, from: &str, to: impl Into<String>) {
+        let to = to.into();
+
+        if from != to {
+            self.add_node(from).edges.insert(to);
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Group nodes into strongly connected components (cycles) with an iterative
+    /// Tarjan's algorithm. Components are returned in reverse topological order,
+    /// so the components a component depends on always come before it.
+    fn collect_components(&self) -> Vec<Vec<&str>> {
+        struct Visit {
+            index: usize,
+            low: usize,
+            on_stack: bool,
+        }
+
+        let mut visits: HashMap<&str, Visit> = HashMap::new();
+        let mut stack = vec![];
+        let mut components = vec![];
+
+        for root in self.nodes.keys() {
+            if visits.contains_key(root.as_str()) {
+                continue;
+            }
+
+            // Each frame is a node, and the edges that haven't been visited yet
+            let mut frames = vec![(root.as_str(), self.nodes[root].edges.iter())];
+
+            visits.insert(
+                root,
+                Visit {
+                    index: visits.len(),
+                    low: visits.len(),
+                    on_stack: true,
+                },
+            );
+            stack.push(root.as_str());
+
+            while let Some((id, edges)) = frames.last_mut() {
+                let id = *id;
+
+                if let Some(edge) = edges.next() {
+                    let Some(node) = self.nodes.get(edge) else {
+                        continue;
+                    };
+
+                    match visits.get(edge.as_str()) {
+                        Some(visit) if visit.on_stack => {
+                            let index = visit.index;
+
+                            if let Some(current) = visits.get_mut(id) {
+                                current.low = current.low.min(index);
+                            }
+                        }
+                        Some(_) => {}
+                        None => {
+                            visits.insert(
+                                edge,
+                                Visit {
+                                    index: visits.len(),
+                                    low: visits.len(),
+                                    on_stack: true,
+                                },
+                            );
+                            stack.push(edge);
+                            frames.push((edge, node.edges.iter()));
+                        }
+                    };
+
+                    continue;
+                }
+
+                frames.pop();
+
+                let Visit { index, low, .. } = visits[id];
+
+                if let Some((parent, _)) = frames.last()
+                    && let Some(parent) = visits.get_mut(parent)
+                {
+                    parent.low = parent.low.min(low);
+                }
+
+                if low == index {
+                    let mut component = vec![];
+
+                    while let Some(member) = stack.pop() {
+                        if let Some(visit) = visits.get_mut(member) {
+                            visit.on_stack = false;
+                        }
+
+                        component.push(member);
+
+                        if member == id {
+                            break;
+                        }
+                    }
+
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    fn hash_node(&self, hasher: &mut Sha256, id: &str) {
+        hasher.update(id);
+
+        if let Some((name, dep)) = &self.nodes[id].package {
+            hasher.update(name);
+            hasher.update(dep.hash.as_deref().unwrap_or_default());
+
+            if let Some(version) = &dep.version {
+                hasher.update(version.to_string());
+            }
+        }
+
+        hasher.update("\n");
+    }
+
+    /// Create a fingerprint for each package from the resolved versions and hashes
+    /// of all transitive dependencies, so that a change deep in the graph is
+    /// reflected in every package (and workspace member) that pulls it in.
+    ///
+    /// Every node in a cycle depends on the same nodes, so each cycle is hashed
+    /// once and reused by its dependents, instead of walking the graph per node.
+    fn create_fingerprints(&self) -> HashMap<&str, String> {
+        let mut component_indexes: HashMap<&str, usize> = HashMap::new();
+        let mut component_hashes: Vec<String> = vec![];
+        let mut fingerprints = HashMap::new();
+
+        for (index, component) in self.collect_components().into_iter().enumerate() {
+            for id in &component {
+                component_indexes.insert(id, index);
+            }
+
+            let dep_hashes = component
+                .iter()
+                .flat_map(|id| &self.nodes[*id].edges)
+                .filter_map(|edge| component_indexes.get(edge.as_str()))
+                .filter(|dep_index| **dep_index != index)
+                .map(|dep_index| component_hashes[*dep_index].clone())
+                .collect::<BTreeSet<_>>();
+
+            for id in &component {
+                if self.nodes[*id].package.is_none()
+                    || (component.len() == 1 && dep_hashes.is_empty())
+                {
+                    continue;
+                }
+
+                let mut hasher = Sha256::new();
+
+                for other_id in &component {
+                    if other_id != id {
+                        self.hash_node(&mut hasher, other_id);
+                    }
+                }
+
+                for dep_hash in &dep_hashes {
+                    hasher.update(dep_hash);
+                }
+
+                fingerprints.insert(*id, format!("deps:{:x}", hasher.finalize()));
+            }
+
+            let mut hasher = Sha256::new();
+
+            for id in &component {
+                self.hash_node(&mut hasher, id);
+            }
+
+            for dep_hash in &dep_hashes {
+                hasher.update(dep_hash);
+            }
+
+            component_hashes.push(format!("{:x}", hasher.finalize()));
+        }
+
+        fingerprints
+    }
+
+    pub fn apply(self, output: &mut ParseLockOutput) {
+        let mut fingerprints = self.create_fingerprints();
+
+        for (id, node) in &self.nodes {
+            let Some((name, dep)) = &node.package else {
+                continue;
+            };
+
+            let mut dep = dep.to_owned();
+
+            if let Some(fingerprint) = fingerprints.remove(id.as_str()) {
+                dep.meta = Some(match dep.meta {
+                    Some(meta) => format!("{meta} {fingerprint}"),
+                    None => fingerprint,
+                });
+            }
+
+            output
+                .dependencies
+                .entry(name.to_owned())
+                .or_default()
+                .push(dep);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_graph() -> LockGraph {
+        let mut graph = LockGraph::default();
+
+        graph.add_package("packages/a", "a", LockDependency::default());
+        graph.add_edge("packages/a", "react@19.1.1");
+        graph.add_package("packages/b", "b", LockDependency::default());
+        graph.add_edge("packages/b", "solid-js@1.9.9");
+        graph.add_edge("packages/b", "packages/a");
+
+        graph.add_package("react@19.1.1", "react", LockDependency::default());
+        graph.add_package("solid-js@1.9.9", "solid-js", LockDependency::default());
+        graph.add_edge("solid-js@1.9.9", "seroval@1.3.2");
+        graph.add_package("seroval@1.3.2", "seroval", LockDependency::default());
+        graph.add_edge("seroval@1.3.2", "solid-js@1.9.9");
+
+        graph
+    }
+
+    #[test]
+    fn groups_cycles_into_components() {
+        let graph = create_graph();
+
+        assert_eq!(
+            graph.collect_components(),
+            vec![
+                vec!["react@19.1.1"],
+                vec!["packages/a"],
+                vec!["seroval@1.3.2", "solid-js@1.9.9"],
+                vec!["packages/b"],
+            ]
+        );
+    }
+
+    #[test]
+    fn fingerprints_packages_with_dependencies() {
+        let graph = create_graph();
+        let fingerprints = graph.create_fingerprints();
+
+        assert!(fingerprints.contains_key("packages/a"));
+        assert!(fingerprints.contains_key("packages/b"));
+        assert!(!fingerprints.contains_key("react@19.1.1"));
+
+        // Both sides of a cycle depend on each other
+        assert!(fingerprints.contains_key("seroval@1.3.2"));
+        assert!(fingerprints.contains_key("solid-js@1.9.9"));
+        assert_ne!(
+            fingerprints["seroval@1.3.2"],
+            fingerprints["solid-js@1.9.9"]
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_edges() {
+        let mut graph = create_graph();
+        graph.add_edge("react@19.1.1", "unknown@1.0.0");
+
+        assert!(!graph.create_fingerprints().contains_key("react@19.1.1"));
+    }
+
+    #[test]
+    fn fingerprint_changes_with_transitive_deps() {
+        let mut graph = create_graph();
+        let before = graph.create_fingerprints()["packages/b"].clone();
+        let before_a = graph.create_fingerprints()["packages/a"].clone();
+
+        graph.add_package(
+            "seroval@1.3.2",
+            "seroval",
+            LockDependency {
+                hash: Some("sha512-changed".into()),
+                ..Default::default()
+            },
+        );
+
+        let after = graph.create_fingerprints();
+
+        assert_ne!(after["packages/b"], before);
+        assert_eq!(after["packages/a"], before_a);
+    }
+}
@@ -1,16 +1,20 @@
 mod bun;
 mod deno;
+mod graph;
 mod npm;
 mod pnpm;
 mod yarn;
+mod yarn_berry;
 
 pub use bun::*;
 pub use deno::*;
+pub use graph::*;
 pub use npm::*;
 pub use pnpm::*;
 pub use yarn::*;
 
-use moon_pdk::AnyResult;
+use crate::package_json::PackageJson;
+use moon_pdk::{AnyResult, VirtualPath};
 use moon_pdk_api::VersionSpec;
 
 pub(super) fn parse_version_spec<T: AsRef<str>>(version: T) -> AnyResult<Option<VersionSpec>> {
@@ -23,6 +27,26 @@ pub(super) fn parse_version_spec<T: AsRef<str>>(version: T) -> AnyResult<Option<
     }
 }
 
+/// Load the name and version of a workspace member from its `package.json`,
+/// as some lockfiles only record the path to the member.
+pub(super) fn load_workspace_member(
+    root: &VirtualPath,
+    member_path: &str,
+) -> AnyResult<Option<(String, Option<VersionSpec>)>> {
+    let package_json = PackageJson::load(root.join(member_path).join("package.json"))?;
+
+    let Some(name) = &package_json.name else {
+        return Ok(None);
+    };
+
+    let version = match &package_json.version {
+        Some(version) => parse_version_spec(version.to_string())?,
+        None => None,
+    };
+
+    Ok(Some((name.to_owned(), version)))
+}
+
 pub(super) fn parse_name_and_version<'a>(
     value: &'a str,
     delimiter: &str,
@@ -1,77 +1,229 @@
-use super::parse_version_spec;
+use super::{LockGraph, parse_version_spec};
 use moon_pdk::{AnyResult, VirtualPath};
 use moon_pdk_api::{LockDependency, ParseLockOutput};
-use package_lock_json_parser::{PackageLockJson, V1Dependency, parse};
-use starbase_utils::fs;
-use std::collections::HashMap;
+use package_lock_json_parser::{V1Dependency, parse};
+use serde::Deserialize;
+use starbase_utils::{fs, json};
+use std::collections::{BTreeMap, HashMap};
 
-fn parse_v1(lock: PackageLockJson, output: &mut ParseLockOutput) -> AnyResult<()> {
+// Resolve a dependency the same way node does, by looking in the
+// `node_modules` of the current package, and then each parent.
+fn resolve_dependency(graph: &LockGraph, path: &str, name: &str) -> Option<String> {
+    let mut dir = path;
+
+    loop {
+        let id = if dir.is_empty() {
+            format!("node_modules/{name}")
+        } else {
+            format!("{dir}/node_modules/{name}")
+        };
+
+        if graph.contains(&id) {
+            return Some(id);
+        }
+
+        if dir.is_empty() {
+            return None;
+        }
+
+        dir = dir
+            .rsplit_once('/')
+            .map(|(parent, _)| parent)
+            .unwrap_or_default();
+    }
+}
+
+fn parse_v1(content: String, output: &mut ParseLockOutput) -> AnyResult<()> {
     fn add_deps(
+        parent: &str,
         deps: HashMap<String, V1Dependency>,
-        output: &mut ParseLockOutput,
+        graph: &mut LockGraph,
+        requires: &mut Vec<(String, String)>,
     ) -> AnyResult<()> {
         for (name, dep) in deps {
+            let id = if parent.is_empty() {
+                format!("node_modules/{name}")
+            } else {
+                format!("{parent}/node_modules/{name}")
+            };
+
             if let Some(nested_deps) = dep.dependencies {
-                add_deps(nested_deps, output)?;
+                add_deps(&id, nested_deps, graph, requires)?;
+            }
+
+            for dep_name in dep.requires.unwrap_or_default().into_keys() {
+                requires.push((id.clone(), dep_name));
             }
 
-            output
-                .dependencies
-                .entry(name)
-                .or_default()
-                .push(LockDependency {
+            graph.add_package(
+                id,
+                name,
+                LockDependency {
                     version: parse_version_spec(dep.version)?,
                     hash: dep.integrity,
                     ..Default::default()
-                });
+                },
+            );
         }
 
         Ok(())
     }
 
+    let lock = parse(content)?;
+    let mut graph = LockGraph::default();
+    let mut requires = vec![];
+
     if let Some(dependencies) = lock.dependencies {
-        add_deps(dependencies, output)?;
+        add_deps("", dependencies, &mut graph, &mut requires)?;
+    }
+
+    for (id, dep_name) in requires {
+        if let Some(dep_id) = resolve_dependency(&graph, &id, &dep_name) {
+            graph.add_edge(&id, dep_id);
+        }
     }
 
+    graph.apply(output);
+
     Ok(())
 }
 
-fn parse_v2_up(lock: PackageLockJson, output: &mut ParseLockOutput) -> AnyResult<()> {
-    let Some(packages) = lock.packages else {
-        return Ok(());
-    };
+fn parse_v2_up(lock: PackageLock, output: &mut ParseLockOutput) -> AnyResult<()> {
+    let mut graph = LockGraph::default();
 
-    for (name, package) in packages {
+    for (path, package) in &lock.packages {
         // Root package
-        if name.is_empty() {
+        if path.is_empty() {
             continue;
         }
 
-        let name = name.strip_prefix("workspaces/").unwrap_or(&name);
+        // Symlinks to workspace packages
+        if package.link {
+            graph.add_node(path);
 
-        output
-            .dependencies
-            .entry(name.to_string())
-            .or_default()
-            .push(LockDependency {
-                version: parse_version_spec(package.version)?,
-                hash: package.integrity,
+            if let Some(target) = &package.resolved {
+                graph.add_edge(path, target);
+            }
+
+            continue;
+        }
+
+        let Some(version) = &package.version else {
+            graph.add_node(path);
+            continue;
+        };
+
+        let name = match path.rsplit_once("node_modules/") {
+            // Hoisted and nested dependencies, including those
+            // nested in a workspace: `packages/a/node_modules/b`
+            Some((_, name)) => name,
+            // Workspace packages
+            None => package
+                .name
+                .as_deref()
+                .unwrap_or_else(|| path.strip_prefix("workspaces/").unwrap_or(path)),
+        };
+
+        graph.add_package(
+            path,
+            name,
+            LockDependency {
+                version: parse_version_spec(version)?,
+                hash: package.integrity.clone(),
                 ..Default::default()
-            });
+            },
+        );
     }
 
+    for (path, package) in &lock.packages {
+        for dep_name in package
+            .dependencies
+            .keys()
+            .chain(package.dev_dependencies.keys())
+            .chain(package.optional_dependencies.keys())
+            .chain(package.peer_dependencies.keys())
+        {
+            if let Some(dep_id) = resolve_dependency(&graph, path, dep_name) {
+                graph.add_edge(path, dep_id);
+            }
+        }
+    }
+
+    graph.apply(output);
+
     Ok(())
 }
 
 pub fn parse_package_lock_json(path: &VirtualPath, output: &mut ParseLockOutput) -> AnyResult<()> {
     let content = fs::read_file(path)?;
-    let lock = parse(&content)?;
+    let lock: PackageLock = json::parse(&content)?;
 
     match lock.lockfile_version {
-        1 => parse_v1(lock, output)?,
+        1 => parse_v1(content, output)?,
         2 | 3 => parse_v2_up(lock, output)?,
         _ => {}
     };
 
     Ok(())
 }
+
+// https://docs.npmjs.com/cli/v11/configuring-npm/package-lock-json
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PackageLock {
+    pub lockfile_version: u32,
+    pub packages: BTreeMap<String, PackageLockPackage>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PackageLockPackage {
+    pub dependencies: BTreeMap<String, String>,
+    pub dev_dependencies: BTreeMap<String, String>,
+    pub integrity: Option<String>,
+    pub link: bool,
+    pub name: Option<String>,
+    pub optional_dependencies: BTreeMap<String, String>,
+    pub peer_dependencies: BTreeMap<String, String>,
+    pub resolved: Option<String>,
+    pub version: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn includes_nested_dependencies() {
+        let lock: PackageLock = json::parse(
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": {},
+                    "node_modules/a": { "version": "1.0.0", "dependencies": { "b": "^1.0.0" } },
+                    "node_modules/a/node_modules/b": { "version": "1.0.0" },
+                    "node_modules/b": { "version": "2.0.0" },
+                    "packages/c/node_modules/@scope/d": { "version": "3.0.0" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let mut output = ParseLockOutput::default();
+
+        parse_v2_up(lock, &mut output).unwrap();
+
+        let versions = |name: &str| {
+            output.dependencies[name]
+                .iter()
+                .map(|dep| dep.version.as_ref().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(versions("a"), ["1.0.0"]);
+        assert_eq!(versions("b"), ["1.0.0", "2.0.0"]);
+        assert_eq!(versions("@scope/d"), ["3.0.0"]);
+
+        // a depends on its nested copy of b
+        assert!(output.dependencies["a"][0].meta.is_some());
+    }
+}
@@ -1,4 +1,4 @@
-use super::{parse_name_and_version, parse_version_spec};
+use super::{LockGraph, load_workspace_member, parse_name_and_version, parse_version_spec};
 use crate::config::CatalogsMap;
 use moon_pdk::{AnyResult, VirtualPath};
 use moon_pdk_api::{LockDependency, ParseLockOutput};
@@ -7,6 +7,42 @@ use rustc_hash::FxHashMap;
 use serde::Deserialize;
 use starbase_utils::{fs, yaml};
 
+fn resolve_dependency(graph: &LockGraph, name: &str, version: &str) -> String {
+    // pnpm v6-8 prefixed IDs with a slash
+    let id = format!("{name}@{version}");
+    let legacy_id = format!("/{id}");
+
+    if graph.contains(&legacy_id) {
+        legacy_id
+    } else {
+        id
+    }
+}
+
+// Links are relative to the importer: `link:../a` in `c` is `a`
+fn resolve_importer_link(importer_path: &str, link: &str) -> String {
+    let mut parts = importer_path
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>();
+
+    for part in link.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        };
+    }
+
+    if parts.is_empty() {
+        ".".into()
+    } else {
+        parts.join("/")
+    }
+}
+
 pub fn parse_pnpm_lock_yaml(path: &VirtualPath, output: &mut ParseLockOutput) -> AnyResult<()> {
     let content = fs::read_file(path)?;
 
@@ -14,31 +50,110 @@ pub fn parse_pnpm_lock_yaml(path: &VirtualPath, output: &mut ParseLockOutput) ->
     // pnpm-lock.yaml — the package-manager metadata in one document and the
     // project lockfile in another, separated by `---` markers. Merge packages
     // from every document so we don't fail on `more than one document`.
+    let mut importers = FxHashMap::default();
     let mut packages = FxHashMap::default();
+    let mut snapshots = FxHashMap::default();
     for doc in yaml::serde_yaml::Deserializer::from_str(&content) {
         let lock = PnpmLock::deserialize(doc)?;
+        importers.extend(lock.importers);
         packages.extend(lock.packages);
+        snapshots.extend(lock.snapshots);
     }
 
-    for (name, package) in packages {
-        let Some((name, version)) = parse_name_and_version(&name, "(") else {
+    let mut graph = LockGraph::default();
+
+    // Importers are the workspace members, keyed by their path relative
+    // to the lockfile, but their name and version are only available
+    // in their `package.json`
+    for importer_path in importers.keys() {
+        let member = match path.parent() {
+            Some(root) if importer_path != "." => load_workspace_member(&root, importer_path)?,
+            _ => None,
+        };
+
+        match member {
+            Some((name, version)) => {
+                graph.add_package(
+                    importer_path,
+                    name,
+                    LockDependency {
+                        version,
+                        ..Default::default()
+                    },
+                );
+            }
+            None => {
+                graph.add_node(importer_path);
+            }
+        };
+    }
+
+    for (id, package) in &packages {
+        let Some((name, version)) = parse_name_and_version(id, "(") else {
             continue;
         };
 
-        let reso = package.resolution;
+        let reso = &package.resolution;
 
-        output
-            .dependencies
-            .entry(name.to_string())
-            .or_default()
-            .push(LockDependency {
+        graph.add_package(
+            id,
+            name,
+            LockDependency {
                 version: parse_version_spec(version)?,
-                hash: reso.integrity.or(reso.commit),
-                meta: reso.tarball.or(reso.repo).or(reso.url),
+                hash: reso.integrity.clone().or_else(|| reso.commit.clone()),
+                meta: reso
+                    .tarball
+                    .clone()
+                    .or_else(|| reso.repo.clone())
+                    .or_else(|| reso.url.clone()),
                 ..Default::default()
-            });
+            },
+        );
+    }
+
+    // pnpm v9+ moved dependencies into snapshots, which are keyed by the
+    // package ID and any peer dependencies, e.g. `a@1.0.0(b@2.0.0)`
+    for id in snapshots.keys() {
+        let package_id = id.split_once('(').map(|(base, _)| base).unwrap_or(id);
+
+        graph.add_edge(package_id, id);
+        graph.add_edge(id, package_id);
+    }
+
+    for (id, deps) in snapshots.iter().chain(
+        packages
+            .iter()
+            .map(|(id, package)| (id, &package.dependencies)),
+    ) {
+        for (dep_name, dep_version) in deps
+            .dependencies
+            .iter()
+            .chain(deps.optional_dependencies.iter())
+        {
+            let dep_id = resolve_dependency(&graph, dep_name, dep_version);
+
+            graph.add_edge(id, dep_id);
+        }
     }
 
+    for (importer_path, importer) in &importers {
+        for (dep_name, dep) in importer
+            .dependencies
+            .iter()
+            .chain(importer.dev_dependencies.iter())
+            .chain(importer.optional_dependencies.iter())
+        {
+            let dep_id = match dep.version().strip_prefix("link:") {
+                Some(link) => resolve_importer_link(importer_path, link),
+                None => resolve_dependency(&graph, dep_name, dep.version()),
+            };
+
+            graph.add_edge(importer_path, dep_id);
+        }
+    }
+
+    graph.apply(output);
+
     Ok(())
 }
 
@@ -72,7 +187,41 @@ impl PnpmWorkspace {
 #[derive(Default, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct PnpmLock {
+    pub importers: FxHashMap<String, PnpmLockImporter>,
     pub packages: FxHashMap<String, PnpmLockPackage>,
+    pub snapshots: FxHashMap<String, PnpmLockDependencies>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PnpmLockDependencies {
+    pub dependencies: FxHashMap<String, String>,
+    pub optional_dependencies: FxHashMap<String, String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PnpmLockImporter {
+    pub dependencies: FxHashMap<String, PnpmLockImporterDependency>,
+    pub dev_dependencies: FxHashMap<String, PnpmLockImporterDependency>,
+    pub optional_dependencies: FxHashMap<String, PnpmLockImporterDependency>,
+}
+
+// pnpm v6+ records the specifier alongside the version,
+// while earlier versions only record the version
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum PnpmLockImporterDependency {
+    Version(String),
+    Specifier { version: String },
+}
+
+impl PnpmLockImporterDependency {
+    pub fn version(&self) -> &str {
+        match self {
+            Self::Version(version) | Self::Specifier { version } => version,
+        }
+    }
 }
 
 #[derive(Default, Deserialize)]
@@ -91,7 +240,27 @@ pub struct PnpmLockResolution {
 #[derive(Default, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct PnpmLockPackage {
+    // pnpm v6-8
+    #[serde(flatten)]
+    pub dependencies: PnpmLockDependencies,
     pub name: Option<String>,
     pub resolution: PnpmLockResolution,
     pub version: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_importer_links() {
+        assert_eq!(resolve_importer_link("c", "../a"), "a");
+        assert_eq!(resolve_importer_link("packages/c", "../a"), "packages/a");
+        assert_eq!(
+            resolve_importer_link("packages/c", "./lib"),
+            "packages/c/lib"
+        );
+        assert_eq!(resolve_importer_link(".", "packages/a"), "packages/a");
+        assert_eq!(resolve_importer_link("a", ".."), ".");
+    }
+}
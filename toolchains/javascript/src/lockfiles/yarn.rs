@@ -1,65 +1,129 @@
-use super::parse_version_spec;
+use super::yarn_berry::parse_yarn_berry_lock_content;
+use super::{LockGraph, parse_version_spec};
 use crate::config::CatalogsMap;
 use moon_pdk::{AnyResult, VirtualPath};
 use moon_pdk_api::{LockDependency, ParseLockOutput};
 use nodejs_package_json::VersionProtocol;
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
-use starbase_utils::fs;
+use starbase_utils::{fs, yaml};
+use std::collections::BTreeMap;
 use yarn_lock_parser::parse_str;
 
 pub(crate) fn parse_yarn_lock_content<T: AsRef<str>>(
     content: T,
     output: &mut ParseLockOutput,
 ) -> AnyResult<()> {
-    let lock = parse_str(content.as_ref())?;
+    let content = content.as_ref();
+
+    // Yarn Berry (v2+) lockfiles are YAML with a metadata block
+    if content.contains("__metadata:") {
+        return parse_yarn_berry_lock_content(content, &YarnRc::default(), None, output);
+    }
+
+    let lock = parse_str(content)?;
+    let mut graph = LockGraph::default();
+    let mut ids = FxHashMap::default();
+
+    // Dependencies reference the descriptors of an entry,
+    // so map every descriptor to the entry it resolves to
+    for entry in &lock.entries {
+        let Some((name, range)) = entry.descriptors.first() else {
+            continue;
+        };
+
+        let id = format!("{name}@{range}");
+
+        for descriptor in &entry.descriptors {
+            ids.insert(*descriptor, id.clone());
+        }
 
-    for entry in lock.entries {
-        if
         // Root package
-        entry.name.contains("root-workspace") ||
-            // Workspace package
-            entry.resolved.contains("workspace:")
-        {
+        if entry.name.contains("root-workspace") {
+            graph.add_node(id);
             continue;
         }
 
-        if entry.integrity.is_empty() {
-            output
-                .dependencies
-                .entry(entry.name.to_string())
-                .or_default()
-                .push(LockDependency {
-                    version: parse_version_spec(entry.version)?,
-                    ..Default::default()
-                });
-        } else {
-            output
-                .dependencies
-                .entry(entry.name.to_string())
-                .or_default()
-                .push(LockDependency {
+        // Workspace packages are included so that they're fingerprinted
+        // with their dependencies, but they have no integrity
+        if entry.resolved.contains("workspace:") {
+            graph.add_package(
+                id,
+                entry.name,
+                LockDependency {
                     version: parse_version_spec(entry.version)?,
-                    hash: Some(entry.integrity.into()),
                     ..Default::default()
-                });
+                },
+            );
+            continue;
+        }
+
+        graph.add_package(
+            id,
+            entry.name,
+            LockDependency {
+                version: parse_version_spec(entry.version)?,
+                hash: if entry.integrity.is_empty() {
+                    None
+                } else {
+                    Some(entry.integrity.into())
+                },
+                ..Default::default()
+            },
+        );
+    }
+
+    for entry in &lock.entries {
+        let Some(id) = entry.descriptors.first().and_then(|desc| ids.get(desc)) else {
+            continue;
+        };
+
+        for descriptor in entry
+            .dependencies
+            .iter()
+            .chain(entry.optional_dependencies.iter())
+        {
+            if let Some(dep_id) = ids.get(descriptor) {
+                graph.add_edge(id, dep_id.to_owned());
+            }
         }
     }
 
+    graph.apply(output);
+
     Ok(())
 }
 
 pub fn parse_yarn_lock(path: &VirtualPath, output: &mut ParseLockOutput) -> AnyResult<()> {
     let content = fs::read_file(path)?;
 
+    if content.contains("__metadata:") {
+        let yarnrc_file = path.parent().map(|dir| dir.join(".yarnrc.yml"));
+        let yarnrc: YarnRc = match yarnrc_file {
+            Some(file) if file.exists() => yaml::read_file(file)?,
+            _ => YarnRc::default(),
+        };
+
+        return parse_yarn_berry_lock_content(content, &yarnrc, path.parent().as_ref(), output);
+    }
+
     parse_yarn_lock_content(content, output)
 }
 
 #[derive(Default, Deserialize)]
-#[serde(default)]
+#[serde(default, rename_all = "camelCase")]
 pub struct YarnRc {
     pub catalog: Option<FxHashMap<String, VersionProtocol>>,
     pub catalogs: Option<FxHashMap<String, FxHashMap<String, VersionProtocol>>>,
+    pub package_extensions: BTreeMap<String, YarnPackageExtension>,
+}
+
+// https://yarnpkg.com/configuration/yarnrc#packageExtensions
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct YarnPackageExtension {
+    pub dependencies: BTreeMap<String, String>,
+    pub peer_dependencies: BTreeMap<String, String>,
 }
 
 impl YarnRc {
@@ -0,0 +1,284 @@
+use super::yarn::YarnRc;
+use super::{LockGraph, load_workspace_member, parse_version_spec};
+use moon_pdk::{AnyResult, VirtualPath};
+use moon_pdk_api::{LockDependency, ParseLockOutput};
+use nodejs_package_json::{Version, VersionProtocol};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use starbase_utils::yaml::{self, YamlValue};
+use std::collections::BTreeMap;
+use toolchain_common::percent_decode;
+
+// Split a descriptor or locator into its name and range/reference,
+// while preserving the scope: `@scope/name@npm:1.2.3`
+fn split_descriptor(value: &str) -> Option<(&str, &str)> {
+    let index = value.get(1..)?.find('@')? + 1;
+
+    Some((&value[0..index], &value[index + 1..]))
+}
+
+/// A parsed `patch:` reference, for example:
+/// `patch:resolve@npm%3A1.22.8#optional!builtin<compat/resolve>::version=1.22.8&hash=c3c19d`
+#[derive(Debug, PartialEq)]
+pub struct YarnBerryPatch {
+    /// Locator of the package being patched, e.g. `resolve@npm:1.22.8`.
+    pub base: String,
+    /// Hash of the patch, or the path to the patch file if not available.
+    pub id: String,
+}
+
+impl YarnBerryPatch {
+    pub fn parse(reference: &str) -> Option<Self> {
+        let reference = reference.strip_prefix("patch:")?;
+        let (source, params) = reference.split_once("::").unwrap_or((reference, ""));
+        let (base, path) = source.split_once('#')?;
+
+        let hash = params
+            .split('&')
+            .find_map(|param| param.strip_prefix("hash="));
+
+        Some(Self {
+            base: percent_decode(base),
+            id: hash
+                .map(|hash| hash.to_owned())
+                .unwrap_or_else(|| percent_decode(path)),
+        })
+    }
+}
+
+// https://github.com/yarnpkg/berry/blob/master/packages/yarnpkg-core/sources/Project.ts
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct YarnBerryMetadata {
+    pub cache_key: Option<YamlValue>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct YarnBerryEntry {
+    pub checksum: Option<String>,
+    pub dependencies: BTreeMap<String, String>,
+    pub resolution: String,
+    pub version: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct YarnBerryLock {
+    #[serde(rename = "__metadata")]
+    pub metadata: YarnBerryMetadata,
+
+    #[serde(flatten)]
+    pub entries: BTreeMap<String, YarnBerryEntry>,
+}
+
+impl YarnBerryLock {
+    fn get_cache_key(&self) -> Option<String> {
+        match self.metadata.cache_key.as_ref()? {
+            YamlValue::Number(number) => Some(number.to_string()),
+            YamlValue::String(string) => Some(string.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+/// Yarn Berry (v2+) lockfiles are valid YAML, and unlike v1, use protocols
+/// (`npm:`, `patch:`, `portal:`, `workspace:`, etc) for all descriptors.
+pub fn parse_yarn_berry_lock_content<T: AsRef<str>>(
+    content: T,
+    yarnrc: &YarnRc,
+    root: Option<&VirtualPath>,
+    output: &mut ParseLockOutput,
+) -> AnyResult<()> {
+    let lock: YarnBerryLock = yaml::parse(content)?;
+    let cache_key = lock.get_cache_key();
+    let mut graph = LockGraph::default();
+    let mut ids = FxHashMap::default();
+    let mut versions: FxHashMap<&str, Vec<(&str, Version)>> = FxHashMap::default();
+
+    for (key, entry) in &lock.entries {
+        // The resolution is a locator, which uniquely identifies the package
+        let id = entry.resolution.as_str();
+
+        for descriptor in key.split(',') {
+            ids.insert(descriptor.trim(), id);
+        }
+
+        let Some((name, reference)) = split_descriptor(id) else {
+            continue;
+        };
+
+        // Older lockfiles did not prefix the checksum with the cache key
+        let hash = entry.checksum.as_ref().map(|checksum| match &cache_key {
+            Some(key) if !checksum.contains('/') => format!("{key}/{checksum}"),
+            _ => checksum.to_owned(),
+        });
+
+        let dep = match reference.split_once(':').map(|(protocol, _)| protocol) {
+            Some("workspace") => {
+                let workspace_path = reference.trim_start_matches("workspace:");
+
+                // Workspaces have a fake version (`0.0.0-use.local`),
+                // so load the real version from their `package.json`
+                let member = match root {
+                    Some(root) if workspace_path != "." => {
+                        load_workspace_member(root, workspace_path)?
+                    }
+                    _ => None,
+                };
+
+                match member {
+                    Some((_, version)) => {
+                        graph.add_package(
+                            id,
+                            name,
+                            LockDependency {
+                                version,
+                                ..Default::default()
+                            },
+                        );
+                    }
+                    None => {
+                        graph.add_node(id);
+                    }
+                };
+
+                continue;
+            }
+            Some("patch") => {
+                let Some(patch) = YarnBerryPatch::parse(reference) else {
+                    continue;
+                };
+
+                // Link to the base package, so that a change to
+                // the base package is reflected in the patch
+                graph.add_edge(id, patch.base);
+
+                LockDependency {
+                    version: parse_version_spec(&entry.version)?,
+                    hash,
+                    meta: Some(format!("patch:{}", patch.id)),
+                    ..Default::default()
+                }
+            }
+            // Local packages have a fake version: `0.0.0-use.local`
+            Some("portal" | "link") => LockDependency {
+                hash,
+                meta: reference.split("::").next().map(|path| path.to_owned()),
+                ..Default::default()
+            },
+            _ => LockDependency {
+                version: parse_version_spec(&entry.version)?,
+                hash,
+                ..Default::default()
+            },
+        };
+
+        if let Ok(version) = Version::parse(&entry.version) {
+            versions.entry(name).or_default().push((id, version));
+        }
+
+        graph.add_package(id, name, dep);
+    }
+
+    // Dependencies reference the descriptors of an entry
+    let resolve_dependency = |name: &str, range: &str| -> Option<&str> {
+        ids.get(format!("{name}@{range}").as_str())
+            // Older lockfiles omitted the `npm:` protocol in dependencies
+            .or_else(|| ids.get(format!("{name}@npm:{range}").as_str()))
+            .copied()
+    };
+
+    for entry in lock.entries.values() {
+        for (dep_name, dep_range) in &entry.dependencies {
+            if let Some(dep_id) = resolve_dependency(dep_name, dep_range) {
+                graph.add_edge(&entry.resolution, dep_id);
+            }
+        }
+    }
+
+    // Extensions are already applied to the lockfile by yarn, but peer
+    // dependencies are not locked, so link them to the matching packages
+    for (pattern, extension) in &yarnrc.package_extensions {
+        let (name, range) = split_descriptor(pattern).unwrap_or((pattern, "*"));
+        let Ok(protocol) = VersionProtocol::parse(range) else {
+            continue;
+        };
+
+        for (id, version) in versions.get(name).into_iter().flatten() {
+            let matches = match &protocol {
+                VersionProtocol::Requirement(req) => req.matches(version),
+                VersionProtocol::Range(reqs) => reqs.iter().any(|req| req.matches(version)),
+                VersionProtocol::Version(ver) => ver == version,
+                _ => true,
+            };
+
+            if !matches {
+                continue;
+            }
+
+            for (dep_name, dep_range) in extension
+                .dependencies
+                .iter()
+                .chain(extension.peer_dependencies.iter())
+            {
+                if let Some(dep_id) = resolve_dependency(dep_name, dep_range) {
+                    graph.add_edge(id, dep_id);
+                }
+            }
+        }
+    }
+
+    graph.apply(output);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_descriptors() {
+        assert_eq!(
+            split_descriptor("react@npm:^19.1.0"),
+            Some(("react", "npm:^19.1.0"))
+        );
+        assert_eq!(
+            split_descriptor("@babel/core@npm:7.28.3"),
+            Some(("@babel/core", "npm:7.28.3"))
+        );
+        assert_eq!(split_descriptor("react"), None);
+    }
+
+    #[test]
+    fn parses_patch_with_hash() {
+        assert_eq!(
+            YarnBerryPatch::parse(
+                "patch:resolve@npm%3A1.22.8#optional!builtin<compat/resolve>::version=1.22.8&hash=c3c19d"
+            ),
+            Some(YarnBerryPatch {
+                base: "resolve@npm:1.22.8".into(),
+                id: "c3c19d".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_patch_without_hash() {
+        assert_eq!(
+            YarnBerryPatch::parse(
+                "patch:@scope/pkg@npm%3A^1.0.0#~/.yarn/patches/@scope-pkg-npm-1.0.0.patch"
+            ),
+            Some(YarnBerryPatch {
+                base: "@scope/pkg@npm:^1.0.0".into(),
+                id: "~/.yarn/patches/@scope-pkg-npm-1.0.0.patch".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_non_patch() {
+        assert_eq!(YarnBerryPatch::parse("npm:1.0.0"), None);
+    }
+}
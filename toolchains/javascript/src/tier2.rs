@@ -2,6 +2,7 @@ use crate::config::*;
 use crate::infer_tasks::TasksInferrer;
 use crate::lockfiles::*;
 use crate::package_json::PackageJson;
+use crate::tool_configs::ToolConfigs;
 use extism_pdk::*;
 use moon_common::path::paths_are_equal;
 use moon_config::DependencyScope;
@@ -116,11 +117,14 @@ pub fn extend_project_graph(
                 let deno_manifest = DenoJson::load_from(project_root)?;
 
                 project_output.tasks.extend(
-                    TasksInferrer::new(&config).infer_from_deno_tasks(&deno_manifest.tasks)?,
+                    TasksInferrer::new(&config)
+                        .with_tool_configs(ToolConfigs::load(project_root)?)
+                        .infer_from_deno_tasks(&deno_manifest.tasks)?,
                 );
             } else if let Some(scripts) = &manifest.scripts {
                 project_output.tasks.extend(
                     TasksInferrer::new(&config)
+                        .with_tool_configs(ToolConfigs::load(project_root)?)
                         .infer_from_package_scripts(BTreeMap::from_iter(scripts))?,
                 );
             }
@@ -0,0 +1,409 @@
+use moon_pdk::{AnyResult, VirtualPath};
+use starbase_utils::{fs, json, json::JsonValue};
+
+const JS_CONFIG_EXTS: [&str; 6] = ["ts", "mts", "cts", "js", "mjs", "cjs"];
+
+fn find_config_file(root: &VirtualPath, name: &str, exts: &[&str]) -> Option<VirtualPath> {
+    exts.iter()
+        .map(|ext| root.join(format!("{name}.{ext}")))
+        .find(|file| file.exists())
+}
+
+// Find each value of a property in a JavaScript config file,
+// returning the content that follows the colon.
+fn find_js_property_values<'a>(content: &'a str, key: &'a str) -> impl Iterator<Item = &'a str> {
+    content.match_indices(key).filter_map(move |(index, _)| {
+        // Ensure we matched the entire key
+        if content[..index]
+            .chars()
+            .next_back()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+        {
+            return None;
+        }
+
+        let rest = content[index + key.len()..]
+            .trim_start_matches(['"', '\''])
+            .trim_start();
+
+        Some(rest.strip_prefix(':')?.trim_start())
+    })
+}
+
+// Parse a string literal at the start of the content, without its quotes.
+fn parse_js_string(content: &str) -> Option<&str> {
+    let quote = content
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '"' | '\'' | '`'))?;
+
+    content[1..].split_once(quote).map(|(value, _)| value)
+}
+
+/// Extract the string value of a property from a JavaScript config file,
+/// for example `outDir: 'build'`. We don't evaluate the file, so values
+/// that are computed at runtime are not supported.
+pub fn extract_js_string_property(content: &str, key: &str) -> Option<String> {
+    find_js_property_values(content, key).find_map(|rest| {
+        parse_js_string(rest)
+            .filter(|value| !value.is_empty() && !value.contains("${"))
+            .map(|value| value.to_owned())
+    })
+}
+
+/// Extract the string values of an array property from a JavaScript config
+/// file, for example `ignores: ['dist/**']`. Every occurrence of the property
+/// is included, and values that are not string literals are skipped.
+pub fn extract_js_string_array_property(content: &str, key: &str) -> Vec<String> {
+    let mut values = vec![];
+
+    for rest in find_js_property_values(content, key) {
+        let Some(mut rest) = rest.strip_prefix('[') else {
+            continue;
+        };
+
+        loop {
+            rest = rest.trim_start();
+
+            let Some(value) = parse_js_string(rest) else {
+                break;
+            };
+
+            if !value.is_empty() && !value.contains("${") {
+                values.push(value.to_owned());
+            }
+
+            // Skip the quotes and the separator
+            rest = rest[value.len() + 2..].trim_start();
+
+            match rest.strip_prefix(',') {
+                Some(next) => rest = next,
+                None => break,
+            };
+        }
+    }
+
+    values
+}
+
+/// Convert a line from an ignore file (gitignore syntax) into negated input globs.
+pub fn convert_ignore_pattern(line: &str) -> Vec<String> {
+    let line = line.trim();
+
+    // Comments and re-included patterns are not supported
+    if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+        return vec![];
+    }
+
+    let pattern = line.trim_end_matches('/');
+    let anchored = pattern.starts_with('/') || pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    if pattern.is_empty() {
+        return vec![];
+    }
+
+    // Patterns without a slash match at any depth
+    let glob = if anchored || pattern.starts_with("**") {
+        pattern.to_owned()
+    } else {
+        format!("**/{pattern}")
+    };
+
+    vec![format!("!{glob}"), format!("!{glob}/**/*")]
+}
+
+/// Convert a glob from an ESLint flat config `ignores` into negated input globs.
+pub fn convert_ignore_glob(glob: &str) -> Vec<String> {
+    // Re-included patterns are not supported
+    if glob.starts_with('!') {
+        return vec![];
+    }
+
+    let glob = glob.trim_start_matches("./");
+
+    // Patterns ending in a slash only match directories
+    match glob.strip_suffix('/') {
+        Some("") => vec![],
+        Some(dir) => vec![format!("!{dir}"), format!("!{dir}/**/*")],
+        None if glob.is_empty() => vec![],
+        None => vec![format!("!{glob}")],
+    }
+}
+
+fn clean_path(path: &str) -> String {
+    path.trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_owned()
+}
+
+/// Well-known tool configuration files found in a project,
+/// used to infer the inputs and outputs of tasks.
+#[derive(Debug, Default)]
+pub struct ToolConfigs {
+    pub eslint_ignore: Vec<String>,
+    pub jest_coverage_dir: Option<String>,
+    pub next_dist_dir: Option<String>,
+    pub prettier_ignore: Vec<String>,
+    pub root: Option<VirtualPath>,
+    pub vite_out_dir: Option<String>,
+    pub vitest_coverage_dir: Option<String>,
+}
+
+impl ToolConfigs {
+    pub fn load(root: &VirtualPath) -> AnyResult<Self> {
+        let mut configs = Self {
+            root: Some(root.to_owned()),
+            ..Default::default()
+        };
+
+        // https://vite.dev/config/build-options.html#build-outdir
+        let vite_config = find_config_file(root, "vite.config", &JS_CONFIG_EXTS);
+
+        if let Some(file) = &vite_config {
+            let content = fs::read_file(file)?;
+
+            configs.vite_out_dir = Some(clean_path(
+                &extract_js_string_property(&content, "outDir").unwrap_or_else(|| "dist".into()),
+            ));
+        }
+
+        // https://vitest.dev/config/#coverage-reportsdirectory
+        if let Some(file) = find_config_file(root, "vitest.config", &JS_CONFIG_EXTS).or(vite_config)
+        {
+            configs.vitest_coverage_dir =
+                extract_js_string_property(&fs::read_file(file)?, "reportsDirectory")
+                    .map(|dir| clean_path(&dir));
+        }
+
+        // https://jestjs.io/docs/configuration#coveragedirectory-string
+        if let Some(file) = find_config_file(root, "jest.config", &JS_CONFIG_EXTS) {
+            configs.jest_coverage_dir =
+                extract_js_string_property(&fs::read_file(file)?, "coverageDirectory")
+                    .map(|dir| clean_path(&dir));
+        } else if let Some(file) = find_config_file(root, "jest.config", &["json"]) {
+            let config: JsonValue = json::read_file(file)?;
+
+            configs.jest_coverage_dir = config
+                .get("coverageDirectory")
+                .and_then(|dir| dir.as_str())
+                .map(clean_path);
+        }
+
+        // https://nextjs.org/docs/app/api-reference/config/next-config-js/distDir
+        if let Some(file) = find_config_file(root, "next.config", &JS_CONFIG_EXTS) {
+            configs.next_dist_dir = Some(clean_path(
+                &extract_js_string_property(&fs::read_file(file)?, "distDir")
+                    .unwrap_or_else(|| ".next".into()),
+            ));
+        }
+
+        // https://eslint.org/docs/latest/use/configure/ignore#ignoring-files
+        if let Some(file) = find_config_file(root, "eslint.config", &JS_CONFIG_EXTS) {
+            for glob in extract_js_string_array_property(&fs::read_file(file)?, "ignores") {
+                configs.eslint_ignore.extend(convert_ignore_glob(&glob));
+            }
+        }
+
+        // https://eslint.org/docs/latest/use/configure/ignore-deprecated#the-eslintignore-file
+        for (file_name, patterns) in [
+            (".eslintignore", &mut configs.eslint_ignore),
+            (".prettierignore", &mut configs.prettier_ignore),
+        ] {
+            let file = root.join(file_name);
+
+            if file.exists() {
+                for line in fs::read_file(file)?.lines() {
+                    patterns.extend(convert_ignore_pattern(line));
+                }
+            }
+        }
+
+        Ok(configs)
+    }
+
+    /// Load the `outDir` from a tsconfig, relative to the project root.
+    /// Relative configs in `extends` are followed, but packages are not.
+    pub fn load_tsconfig_out_dir(&self, tsconfig_name: &str) -> AnyResult<Option<String>> {
+        let Some(root) = &self.root else {
+            return Ok(None);
+        };
+
+        match join_project_path("", tsconfig_name) {
+            Some(tsconfig_path) => find_tsconfig_out_dir(root, &tsconfig_path, 5),
+            None => Ok(None),
+        }
+    }
+}
+
+// Join a path onto a project relative directory, while resolving `.` and `..`
+// components. Returns `None` if the path is absolute or escapes the project.
+fn join_project_path(dir: &str, path: &str) -> Option<String> {
+    if path.starts_with(['/', '\\']) {
+        return None;
+    }
+
+    let mut parts = dir
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(part),
+        };
+    }
+
+    Some(parts.join("/"))
+}
+
+// An `outDir` is relative to the tsconfig that sets it, which may be
+// an extended config, so each extended config is searched in turn.
+fn find_tsconfig_out_dir(
+    root: &VirtualPath,
+    tsconfig_path: &str,
+    depth: u8,
+) -> AnyResult<Option<String>> {
+    let file = root.join(tsconfig_path);
+
+    if !file.exists() {
+        return Ok(None);
+    }
+
+    let config: JsonValue = json::read_file(file)?;
+    let config_dir = tsconfig_path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or_default();
+
+    if let Some(out_dir) = config
+        .get("compilerOptions")
+        .and_then(|options| options.get("outDir"))
+        .and_then(|dir| dir.as_str())
+    {
+        return Ok(join_project_path(config_dir, out_dir).filter(|dir| !dir.is_empty()));
+    }
+
+    if depth == 0 {
+        return Ok(None);
+    }
+
+    let extends = match config.get("extends") {
+        Some(JsonValue::String(path)) => vec![path.as_str()],
+        Some(JsonValue::Array(paths)) => paths.iter().filter_map(|path| path.as_str()).collect(),
+        _ => vec![],
+    };
+
+    // Later configs override earlier ones
+    for path in extends.into_iter().rev() {
+        if !path.starts_with('.') {
+            continue;
+        }
+
+        let path = if path.ends_with(".json") {
+            path.to_owned()
+        } else {
+            format!("{path}.json")
+        };
+
+        if let Some(extends_path) = join_project_path(config_dir, &path)
+            && let Some(out_dir) = find_tsconfig_out_dir(root, &extends_path, depth - 1)?
+        {
+            return Ok(Some(out_dir));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_project_paths() {
+        assert_eq!(join_project_path("", "./lib/"), Some("lib".into()));
+        assert_eq!(
+            join_project_path("packages/x", "./dist"),
+            Some("packages/x/dist".into())
+        );
+        assert_eq!(
+            join_project_path("packages/x", "../base.json"),
+            Some("packages/base.json".into())
+        );
+        assert_eq!(join_project_path("", "../dist"), None);
+        assert_eq!(join_project_path("", "/dist"), None);
+    }
+
+    #[test]
+    fn extracts_string_properties() {
+        assert_eq!(
+            extract_js_string_property("build: { outDir: './build' }", "outDir"),
+            Some("./build".into())
+        );
+        assert_eq!(
+            extract_js_string_property(r#"{ "distDir": "out" }"#, "distDir"),
+            Some("out".into())
+        );
+        assert_eq!(
+            extract_js_string_property("assetsOutDir: 'a', outDir: `b`", "outDir"),
+            Some("b".into())
+        );
+        assert_eq!(
+            extract_js_string_property("outDir: `${root}/dist`", "outDir"),
+            None
+        );
+        assert_eq!(extract_js_string_property("outDir: dir", "outDir"), None);
+    }
+
+    #[test]
+    fn extracts_string_array_properties() {
+        assert_eq!(
+            extract_js_string_array_property(
+                "[{ ignores: ['dist/**', \"**/*.{js,ts}\"] }, { ignores: [`build/`] }]",
+                "ignores"
+            ),
+            vec!["dist/**", "**/*.{js,ts}", "build/"]
+        );
+        assert_eq!(
+            extract_js_string_array_property("{ ignores: [dir, 'out', `${root}/tmp`] }", "ignores"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            extract_js_string_array_property(
+                "{ ignores: ['out', `${root}/tmp`, 'tmp'] }",
+                "ignores"
+            ),
+            vec!["out", "tmp"]
+        );
+        assert!(extract_js_string_array_property("{ ignores: 'dist' }", "ignores").is_empty());
+    }
+
+    #[test]
+    fn converts_ignore_globs() {
+        assert_eq!(convert_ignore_glob("dist/**"), vec!["!dist/**"]);
+        assert_eq!(
+            convert_ignore_glob("./build/"),
+            vec!["!build", "!build/**/*"]
+        );
+        assert!(convert_ignore_glob("!keep.js").is_empty());
+    }
+
+    #[test]
+    fn converts_ignore_patterns() {
+        assert_eq!(
+            convert_ignore_pattern("dist/"),
+            vec!["!**/dist", "!**/dist/**/*"]
+        );
+        assert_eq!(
+            convert_ignore_pattern("/src/generated"),
+            vec!["!src/generated", "!src/generated/**/*"]
+        );
+        assert!(convert_ignore_pattern("# comment").is_empty());
+        assert!(convert_ignore_pattern("!keep.js").is_empty());
+    }
+}
@@ -0,0 +1,12 @@
+import { defineConfig } from 'vite';
+
+export default defineConfig({
+  build: {
+    outDir: './build/',
+  },
+  test: {
+    coverage: {
+      reportsDirectory: 'reports/coverage',
+    },
+  },
+});
@@ -1,7 +1,8 @@
 use moon_common::Id;
 use moon_config::{
-    DependencyScope, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, PartialTaskDependency,
-    PartialTaskOptionsConfig, TaskOptionCache, TaskOptionRunInCI, TaskPreset,
+    DependencyScope, Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig,
+    PartialTaskDependency, PartialTaskOptionsConfig, TaskOptionCache, TaskOptionRunInCI,
+    TaskPreset,
 };
 use moon_pdk_api::*;
 use moon_pdk_test_utils::{MoonWasmSandbox, create_empty_moon_sandbox, create_moon_sandbox};
@@ -318,6 +319,253 @@ mod javascript_toolchain_tier2 {
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_inputs_and_outputs_from_tool_configs() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_moon_sandbox("tool-configs");
+            let plugin = sandbox.create_toolchain("javascript").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromScripts": true,
+                "packageManager": "npm"
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.get("build").unwrap().outputs,
+                Some(vec![Output::parse("build").unwrap()])
+            );
+            assert_eq!(
+                tasks.get("compile").unwrap().outputs,
+                Some(vec![Output::parse("lib").unwrap()])
+            );
+            assert_eq!(
+                tasks.get("compile-lib").unwrap().outputs,
+                Some(vec![Output::parse("dist/lib").unwrap()])
+            );
+            assert_eq!(
+                tasks.get("compile-types").unwrap().outputs,
+                Some(vec![Output::parse("config/types").unwrap()])
+            );
+            assert_eq!(
+                tasks.get("test").unwrap().outputs,
+                Some(vec![Output::parse("reports/coverage").unwrap()])
+            );
+            assert_eq!(tasks.get("typecheck").unwrap().outputs, None);
+
+            assert_eq!(
+                tasks.get("lint").unwrap().inputs,
+                Some(vec![
+                    Input::parse("**/*").unwrap(),
+                    Input::parse("!**/build").unwrap(),
+                    Input::parse("!**/build/**/*").unwrap(),
+                    Input::parse("!generated").unwrap(),
+                    Input::parse("!generated/**/*").unwrap(),
+                ])
+            );
+            assert_eq!(
+                tasks.get("format").unwrap().inputs,
+                Some(vec![
+                    Input::parse("**/*").unwrap(),
+                    Input::parse("!**/*.min.js").unwrap(),
+                    Input::parse("!**/*.min.js/**/*").unwrap(),
+                ])
+            );
+            assert_eq!(tasks.get("build").unwrap().inputs, None);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_lint_inputs_from_eslint_flat_config() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "app/package.json",
+                r#"{ "scripts": { "lint": "eslint ." } }"#,
+            );
+            sandbox.create_file(
+                "app/eslint.config.mjs",
+                r#"export default [
+  { ignores: ["dist/**", "coverage/"] },
+  { files: ["**/*.ts"], rules: {} },
+];"#,
+            );
+
+            let plugin = sandbox.create_toolchain("javascript").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromScripts": true,
+                "packageManager": "npm"
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.get("lint").unwrap().inputs,
+                Some(vec![
+                    Input::parse("**/*").unwrap(),
+                    Input::parse("!dist/**").unwrap(),
+                    Input::parse("!coverage").unwrap(),
+                    Input::parse("!coverage/**/*").unwrap(),
+                ])
+            );
+        }
+
+        async fn infer_lifecycle_tasks(package_manager: &str) -> BTreeMap<Id, PartialTaskConfig> {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "app/package.json",
+                r#"{
+  "scripts": {
+    "prebuild": "rm -rf dist",
+    "build": "tsc",
+    "postbuild": "cp README.md dist",
+    "pretypecheck": "rm -rf out",
+    "typecheck": "tsc --noEmit",
+    "pretest": "echo orphan",
+    "prepare": "husky",
+    "lint": "eslint ."
+  }
+}"#,
+            );
+
+            let plugin = sandbox.create_toolchain("javascript").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromLifecycleScripts": true,
+                "inferTasksFromScripts": true,
+                "packageManager": package_manager
+            });
+
+            let mut output = plugin.extend_project_graph(input).await;
+
+            output.extended_projects.remove("app").unwrap().tasks
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_lifecycle_scripts_as_tasks_when_enabled() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let tasks = infer_lifecycle_tasks("npm").await;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                ["build", "lint", "pretypecheck", "typecheck"]
+            );
+
+            let typecheck = tasks.get("typecheck").unwrap();
+
+            assert_eq!(
+                typecheck.command,
+                Some(PartialTaskArgs::List(vec![
+                    "npm".into(),
+                    "run".into(),
+                    "--ignore-scripts".into(),
+                    "typecheck".into(),
+                ]))
+            );
+            assert_eq!(
+                typecheck.deps,
+                Some(vec![PartialTaskDependency::Target(
+                    Target::parse("~:pretypecheck").unwrap()
+                )])
+            );
+            assert_eq!(tasks.get("pretypecheck").unwrap().deps, None);
+
+            // Ignoring scripts would skip `postbuild`, so npm runs the hooks
+            for name in ["build", "lint"] {
+                let task = tasks.get(name).unwrap();
+
+                assert_eq!(
+                    task.command,
+                    Some(PartialTaskArgs::List(vec![
+                        "npm".into(),
+                        "run".into(),
+                        name.into(),
+                    ]))
+                );
+                assert_eq!(task.deps, None);
+            }
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_lifecycle_scripts_as_tasks_when_hooks_are_not_ran() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let tasks = infer_lifecycle_tasks("pnpm").await;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                [
+                    "build",
+                    "lint",
+                    "postbuild",
+                    "prebuild",
+                    "pretypecheck",
+                    "typecheck"
+                ]
+            );
+
+            // pnpm doesn't run hooks, so the command is unchanged
+            let build = tasks.get("build").unwrap();
+
+            assert_eq!(
+                build.command,
+                Some(PartialTaskArgs::List(vec![
+                    "pnpm".into(),
+                    "run".into(),
+                    "build".into(),
+                ]))
+            );
+            assert_eq!(
+                build.deps,
+                Some(vec![PartialTaskDependency::Target(
+                    Target::parse("~:prebuild").unwrap()
+                )])
+            );
+            assert_eq!(
+                tasks.get("postbuild").unwrap().deps,
+                Some(vec![PartialTaskDependency::Target(
+                    Target::parse("~:build").unwrap()
+                )])
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_lifecycle_scripts_when_hooks_cant_be_ignored() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            for package_manager in ["bun", "nub", "yarn"] {
+                let tasks = infer_lifecycle_tasks(package_manager).await;
+
+                assert_eq!(
+                    tasks.keys().collect::<Vec<_>>(),
+                    ["build", "lint", "typecheck"],
+                    "{package_manager}"
+                );
+
+                assert_eq!(
+                    tasks.get("typecheck").unwrap().command,
+                    Some(PartialTaskArgs::List(vec![
+                        package_manager.into(),
+                        "run".into(),
+                        "typecheck".into(),
+                    ]))
+                );
+                assert_eq!(tasks.get("typecheck").unwrap().deps, None);
+            }
+        }
+
         #[allow(deprecated)]
         #[tokio::test(flavor = "multi_thread")]
         async fn infers_deno_tasks_when_enabled() {
@@ -2332,6 +2580,64 @@ mod javascript_toolchain_tier2 {
             sandbox
         }
 
+        fn get_fingerprint<'a>(
+            dependencies: &'a BTreeMap<String, Vec<LockDependency>>,
+            name: &str,
+        ) -> Option<&'a str> {
+            dependencies[name][0]
+                .meta
+                .as_deref()?
+                .split(' ')
+                .find(|part| part.starts_with("deps:"))
+        }
+
+        // Fingerprints are hashes of the transitive dependencies,
+        // so they're verified separately from the parsed entries
+        fn strip_fingerprints(
+            mut dependencies: BTreeMap<String, Vec<LockDependency>>,
+        ) -> BTreeMap<String, Vec<LockDependency>> {
+            for deps in dependencies.values_mut() {
+                for dep in deps {
+                    dep.meta = dep.meta.take().and_then(|meta| {
+                        let meta = meta
+                            .split(' ')
+                            .filter(|part| !part.starts_with("deps:"))
+                            .collect::<Vec<_>>()
+                            .join(" ");
+
+                        if meta.is_empty() { None } else { Some(meta) }
+                    });
+                }
+            }
+
+            dependencies
+        }
+
+        fn assert_fingerprints(dependencies: &BTreeMap<String, Vec<LockDependency>>) {
+            // Packages with dependencies
+            assert!(get_fingerprint(dependencies, "solid-js").is_some());
+
+            // Packages without dependencies
+            assert!(get_fingerprint(dependencies, "csstype").is_none());
+            assert!(get_fingerprint(dependencies, "react").is_none());
+            assert!(get_fingerprint(dependencies, "seroval").is_none());
+            assert!(get_fingerprint(dependencies, "typescript").is_none());
+        }
+
+        fn assert_workspace_fingerprints(dependencies: &BTreeMap<String, Vec<LockDependency>>) {
+            let a = get_fingerprint(dependencies, "a");
+            let b = get_fingerprint(dependencies, "b");
+            let c = get_fingerprint(dependencies, "c");
+
+            assert!(a.is_some());
+            assert!(b.is_some());
+            assert!(c.is_some());
+
+            // c depends on a, so it includes a's dependencies as well
+            assert_ne!(a, b);
+            assert_ne!(b, c);
+        }
+
         fn expected_base_dependencies() -> BTreeMap<String, Vec<LockDependency>> {
             BTreeMap::from_iter([
                 (
@@ -2443,6 +2749,9 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
+            assert_fingerprints(&output.dependencies);
+            assert_workspace_fingerprints(&output.dependencies);
+
             // GitHub dependencies only exist in the bun lockfile fixture:
             // with package metadata + integrity, and without either
             let mut expected = expected_dependencies();
@@ -2464,7 +2773,7 @@ mod javascript_toolchain_tier2 {
                 }],
             );
 
-            assert_eq!(output.dependencies, expected);
+            assert_eq!(strip_fingerprints(output.dependencies), expected);
         }
 
         // #[tokio::test(flavor = "multi_thread")]
@@ -2508,8 +2817,13 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
+            assert!(get_fingerprint(&output.dependencies, "jsr:@astral/astral").is_some());
+            assert!(get_fingerprint(&output.dependencies, "jsr:@zip-js/zip-js").is_none());
+            assert!(get_fingerprint(&output.dependencies, "npm:@babel/core").is_none());
+            assert!(get_fingerprint(&output.dependencies, "npm:@babel/preset-react").is_some());
+
             assert_eq!(
-                output.dependencies,
+                strip_fingerprints(output.dependencies),
                 BTreeMap::from_iter([
                     (
                         "jsr:@astral/astral".into(),
@@ -2571,7 +2885,56 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
-            assert_eq!(output.dependencies, expected_dependencies());
+            assert_fingerprints(&output.dependencies);
+            assert_workspace_fingerprints(&output.dependencies);
+
+            assert_eq!(
+                strip_fingerprints(output.dependencies),
+                expected_dependencies()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn changes_fingerprints_when_transitive_dependency_changes() {
+            let sandbox = create_lockfile_sandbox("npm");
+            let plugin = sandbox.create_toolchain("javascript").await;
+            let lockfile = sandbox.path().join("package-lock.json");
+
+            let before = plugin
+                .parse_lock(ParseLockInput {
+                    path: VirtualPath::new(&lockfile),
+                    ..Default::default()
+                })
+                .await;
+
+            // seroval is a dependency of solid-js, which is a dependency of b and c
+            fs::write_file(
+                &lockfile,
+                fs::read_file(&lockfile)
+                    .unwrap()
+                    .replace("sha512-RbcPH1n5cfwKrru7v7", "sha512-changed"),
+            )
+            .unwrap();
+
+            let after = plugin
+                .parse_lock(ParseLockInput {
+                    path: VirtualPath::new(&lockfile),
+                    ..Default::default()
+                })
+                .await;
+
+            for name in ["b", "c", "solid-js", "seroval-plugins"] {
+                assert_ne!(
+                    get_fingerprint(&before.dependencies, name),
+                    get_fingerprint(&after.dependencies, name),
+                    "{name}"
+                );
+            }
+
+            assert_eq!(
+                get_fingerprint(&before.dependencies, "a"),
+                get_fingerprint(&after.dependencies, "a")
+            );
         }
 
         // `nub.lock` uses the pnpm lockfile format
@@ -2587,7 +2950,13 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
-            assert_eq!(output.dependencies, expected_base_dependencies());
+            assert_fingerprints(&output.dependencies);
+            assert_workspace_fingerprints(&output.dependencies);
+
+            assert_eq!(
+                strip_fingerprints(output.dependencies),
+                expected_dependencies()
+            );
         }
 
         #[tokio::test(flavor = "multi_thread")]
@@ -2602,7 +2971,13 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
-            assert_eq!(output.dependencies, expected_base_dependencies());
+            assert_fingerprints(&output.dependencies);
+            assert_workspace_fingerprints(&output.dependencies);
+
+            assert_eq!(
+                strip_fingerprints(output.dependencies),
+                expected_dependencies()
+            );
         }
 
         // pnpm v10 with `managePackageManagerVersions` writes a multi-document
@@ -2621,7 +2996,7 @@ mod javascript_toolchain_tier2 {
                 .await;
 
             assert_eq!(
-                output.dependencies,
+                strip_fingerprints(output.dependencies),
                 BTreeMap::from_iter([
                     (
                         "pnpm".into(),
@@ -2659,9 +3034,33 @@ mod javascript_toolchain_tier2 {
                 .await;
 
             // Yarn has different integrities than other package managers...
+            assert_fingerprints(&output.dependencies);
+            assert_workspace_fingerprints(&output.dependencies);
+
             assert_eq!(
-                output.dependencies,
+                strip_fingerprints(output.dependencies),
                 BTreeMap::from_iter([
+                    (
+                        "a".into(),
+                        vec![LockDependency {
+                            version: Some(VersionSpec::parse("1.0.0").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "b".into(),
+                        vec![LockDependency {
+                            version: Some(VersionSpec::parse("2.0.0").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "c".into(),
+                        vec![LockDependency {
+                            version: Some(VersionSpec::parse("3.0.0").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
                     (
                         "csstype".into(),
                         vec![LockDependency {
@@ -2744,7 +3143,114 @@ mod javascript_toolchain_tier2 {
                 })
                 .await;
 
-            assert_eq!(output.dependencies, expected_base_dependencies());
+            assert_fingerprints(&output.dependencies);
+
+            assert_eq!(
+                strip_fingerprints(output.dependencies),
+                expected_base_dependencies()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn parses_yarn_berry_protocols() {
+            let sandbox = create_lockfile_sandbox("yarn-berry");
+            let plugin = sandbox.create_toolchain("javascript").await;
+
+            let output = plugin
+                .parse_lock(ParseLockInput {
+                    path: VirtualPath::new(sandbox.path().join("yarn.lock")),
+                    ..Default::default()
+                })
+                .await;
+
+            // Patches link to their base package
+            let patched = output.dependencies["resolve"][1].meta.as_deref().unwrap();
+
+            assert!(patched.starts_with("patch:c3c19d deps:"));
+
+            // Peer dependencies from `packageExtensions`
+            assert!(get_fingerprint(&output.dependencies, "seroval-plugins").is_some());
+            assert!(get_fingerprint(&output.dependencies, "local-lib").is_some());
+            assert!(get_fingerprint(&output.dependencies, "seroval").is_none());
+
+            // Workspaces are fingerprinted with their dependencies
+            assert!(get_fingerprint(&output.dependencies, "a").is_some());
+
+            // Checksums are prefixed with the cache key
+            assert_eq!(
+                strip_fingerprints(output.dependencies),
+                BTreeMap::from_iter([
+                    (
+                        "a".into(),
+                        vec![LockDependency {
+                            version: Some(VersionSpec::parse("1.0.0").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "local-lib".into(),
+                        vec![LockDependency {
+                            meta: Some("portal:../local-lib".into()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "path-parse".into(),
+                        vec![LockDependency {
+                            hash: Some(
+                                "8/11ce261f9d294cc7a58d6a574b7f1b935842355ec66fba3c3fd79e0f036462eaf07d0aa95bb74ff432f9afef97ce1926c720988c6a7451d8a584930ae7de86e1"
+                                    .into()
+                            ),
+                            version: Some(VersionSpec::parse("1.0.7").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "resolve".into(),
+                        vec![
+                            LockDependency {
+                                hash: Some(
+                                    "8/f8a26958aa572c9b064562750b52131a37c29d072478ea32e129063e2da7f83e31f7f11e7087a18225a8561cfe8d2f0df9dbea7c9d331a897571c0a2527dbb4c"
+                                        .into()
+                                ),
+                                version: Some(VersionSpec::parse("1.22.8").unwrap()),
+                                ..Default::default()
+                            },
+                            LockDependency {
+                                hash: Some(
+                                    "8/5479b7d431cacd5185f8db64bfcb7286ae5e31eb299f4c4f404ad8aa6098b77599563ac4257cb2c37a42f59dfc06a1bec2bcf283bb448f319e37f0feb9a09847"
+                                        .into()
+                                ),
+                                meta: Some("patch:c3c19d".into()),
+                                version: Some(VersionSpec::parse("1.22.8").unwrap()),
+                                ..Default::default()
+                            }
+                        ]
+                    ),
+                    (
+                        "seroval".into(),
+                        vec![LockDependency {
+                            hash: Some(
+                                "8/19e74825643786d22e5c58054bd28065238de0156545afba82f9a7d3ee70ea4f0249b427f317bc6bf983849dde8e4190264728d90c84620aa163bfbc5971f1bc"
+                                    .into()
+                            ),
+                            version: Some(VersionSpec::parse("1.3.2").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                    (
+                        "seroval-plugins".into(),
+                        vec![LockDependency {
+                            hash: Some(
+                                "8/67b108b3cbc189acca445b512ebd77e11b55c6aa3d1610c3a0b4822b63e5c6d0a4426ac6e50574772cc743257f0a16a8a4d12e5e4f28a2da8e1f583b00a27bbe"
+                                    .into()
+                            ),
+                            version: Some(VersionSpec::parse("1.3.2").unwrap()),
+                            ..Default::default()
+                        }]
+                    ),
+                ])
+            );
         }
     }
 }
@@ -29,13 +29,18 @@ config_struct!(
     /// Docs: https://moonrepo.dev/docs/config/toolchain#python
     #[derive(Config)]
     pub struct PythonToolchainConfig {
+        /// Automatically infer moon tasks from the tools configured in `pyproject.toml`
+        /// (`project.scripts`, pytest, ruff, mypy, poe, and taskipy), and from
+        /// `tox.ini` environments and `noxfile.py` sessions.
+        pub infer_tasks_from_tools: bool,
+
         /// The package manager to use for installing dependencies,
         /// running inferred tasks, and much more.
         pub package_manager: Option<PythonPackageManager>,
 
         /// The location where to create the virtual environment,
         /// in which dependencies will be installed into.
-        // pub venv_location: PythonVenvLocation,
+        pub venv_location: PythonVenvLocation,
 
         /// The name of virtual environment folder name.
         #[setting(default = ".venv")]
@@ -0,0 +1,374 @@
+use crate::config::{PythonPackageManager, PythonToolchainConfig};
+use crate::pyproject_toml::{PyProjectToml, Tool};
+use moon_config::{Input, OneOrMany, PartialTaskArgs, PartialTaskConfig, TaskPreset};
+use moon_pdk_api::{AnyResult, Id, VirtualPath};
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+
+// https://tox.wiki/en/latest/config.html#ini
+pub fn parse_tox_envs(content: impl AsRef<str>) -> Vec<String> {
+    let mut envs = vec![];
+    let mut in_tox_section = false;
+    let mut in_envlist = false;
+
+    let mut add_env = |env: &str| {
+        let env = env.trim();
+
+        // Skip generative environments, like `py{310,311}`
+        if !env.is_empty() && !env.contains(['{', '}']) && !envs.iter().any(|e| e == env) {
+            envs.push(env.to_owned());
+        }
+    };
+
+    for raw_line in content.as_ref().lines() {
+        let line = raw_line.trim();
+
+        // Values can continue on the following indented lines, like
+        // `envlist =\n    py311\n    lint`
+        if in_envlist {
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if raw_line.starts_with([' ', '\t']) {
+                for env in line.split(',') {
+                    add_env(env);
+                }
+
+                continue;
+            }
+
+            in_envlist = false;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_tox_section = section == "tox";
+
+            if let Some(env) = section.strip_prefix("testenv:") {
+                add_env(env);
+            }
+
+            continue;
+        }
+
+        if in_tox_section
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim() == "envlist"
+        {
+            for env in value.split(',') {
+                add_env(env);
+            }
+
+            in_envlist = true;
+        }
+    }
+
+    envs
+}
+
+// https://nox.thea.codes/en/stable/config.html
+pub fn parse_nox_sessions(content: impl AsRef<str>) -> Vec<String> {
+    let mut sessions = vec![];
+    let mut in_session = false;
+    let mut session_name = None;
+
+    for line in content.as_ref().lines() {
+        let line = line.trim();
+
+        if line.starts_with("@nox.session") {
+            in_session = true;
+
+            // Custom name, like `@nox.session(name="lint")`
+            if let Some((_, value)) = line.split_once("name=") {
+                session_name = parse_quoted_string(value).map(|name| name.to_owned());
+            }
+
+            continue;
+        }
+
+        if in_session && let Some(def) = line.strip_prefix("def ") {
+            if let Some(name) = session_name
+                .take()
+                .or_else(|| def.split('(').next().map(|name| name.trim().to_owned()))
+            {
+                sessions.push(name);
+            }
+
+            in_session = false;
+        }
+    }
+
+    sessions
+}
+
+// Only string literals, like `"lint"` or `'lint'`, are supported
+fn parse_quoted_string(value: &str) -> Option<&str> {
+    let quote = if value.starts_with('"') { '"' } else { '\'' };
+
+    value
+        .strip_prefix(quote)
+        .and_then(|rest| rest.split_once(quote))
+        .map(|(inner, _)| inner)
+        .filter(|inner| !inner.is_empty())
+}
+
+pub struct TasksInferrer<'a> {
+    config: &'a PythonToolchainConfig,
+    manifest: &'a PyProjectToml,
+    root: &'a VirtualPath,
+    tool: &'a Tool,
+    tasks: BTreeMap<Id, PartialTaskConfig>,
+    /// Files other than the manifest that tasks were inferred from.
+    input_files: Vec<VirtualPath>,
+}
+
+impl<'a> TasksInferrer<'a> {
+    pub fn new(
+        config: &'a PythonToolchainConfig,
+        manifest: &'a PyProjectToml,
+        tool: &'a Tool,
+        root: &'a VirtualPath,
+    ) -> TasksInferrer<'a> {
+        Self {
+            config,
+            manifest,
+            root,
+            tool,
+            tasks: BTreeMap::default(),
+            input_files: vec![],
+        }
+    }
+
+    /// Infer tasks, and return them with the files they were inferred from.
+    pub fn infer(mut self) -> AnyResult<(BTreeMap<Id, PartialTaskConfig>, Vec<VirtualPath>)> {
+        let package_inputs = self.detect_package_inputs()?;
+
+        // pytest
+        if let Some(pytest) = &self.tool.pytest
+            && let Some(options) = &pytest.ini_options
+        {
+            let mut inputs = package_inputs.clone();
+
+            if options.testpaths.is_empty() {
+                if self.root.join("tests").exists() {
+                    inputs.push("tests/**/*".into());
+                }
+            } else {
+                for path in &options.testpaths {
+                    inputs.push(format!("{}/**/*", path.trim_end_matches('/')));
+                }
+            }
+
+            self.insert_task(
+                "test",
+                self.create_task("Tests with pytest.".into(), &["pytest"], &inputs)?,
+            )?;
+        }
+
+        // ruff
+        if self.tool.ruff.is_some() {
+            self.insert_task(
+                "lint",
+                self.create_task(
+                    "Lints with ruff.".into(),
+                    &["ruff", "check", "."],
+                    &package_inputs,
+                )?,
+            )?;
+
+            self.insert_task(
+                "format-check",
+                self.create_task(
+                    "Checks formatting with ruff.".into(),
+                    &["ruff", "format", "--check", "."],
+                    &package_inputs,
+                )?,
+            )?;
+        }
+
+        // mypy
+        if let Some(mypy) = &self.tool.mypy {
+            // Files to check can be configured, otherwise a target is required
+            let args = if mypy.get("files").is_some() {
+                vec!["mypy"]
+            } else {
+                vec!["mypy", "."]
+            };
+
+            self.insert_task(
+                "typecheck",
+                self.create_task("Type checks with mypy.".into(), &args, &package_inputs)?,
+            )?;
+        }
+
+        // scripts
+        if let Some(scripts) = self
+            .manifest
+            .project
+            .as_ref()
+            .and_then(|project| project.scripts.as_ref())
+        {
+            for name in scripts.keys() {
+                let mut task = self.create_task(
+                    format!("Runs the `{name}` script."),
+                    &[name],
+                    &package_inputs,
+                )?;
+                task.preset = Some(TaskPreset::Utility);
+
+                self.insert_task(&format!("run-{name}"), task)?;
+            }
+        }
+
+        // tox
+        let tox_path = self.root.join("tox.ini");
+
+        if tox_path.exists() {
+            let mut inputs = package_inputs.clone();
+            inputs.push("tox.ini".into());
+
+            for env in parse_tox_envs(fs::read_file(&tox_path)?) {
+                self.insert_task(
+                    &format!("tox-{env}"),
+                    self.create_task(
+                        format!("Runs the `{env}` tox environment."),
+                        &["tox", "-e", &env],
+                        &inputs,
+                    )?,
+                )?;
+            }
+
+            self.input_files.push(tox_path);
+        }
+
+        // nox
+        let nox_path = self.root.join("noxfile.py");
+
+        if nox_path.exists() {
+            let mut inputs = package_inputs.clone();
+            inputs.push("noxfile.py".into());
+
+            for session in parse_nox_sessions(fs::read_file(&nox_path)?) {
+                self.insert_task(
+                    &format!("nox-{session}"),
+                    self.create_task(
+                        format!("Runs the `{session}` nox session."),
+                        &["nox", "-s", &session],
+                        &inputs,
+                    )?,
+                )?;
+            }
+
+            self.input_files.push(nox_path);
+        }
+
+        // Task runners are explicitly defined by the user,
+        // so they take precedence over tasks inferred above
+        for (runner, bin, tasks) in [
+            ("poe", "poe", &self.tool.poe),
+            ("taskipy", "task", &self.tool.taskipy),
+        ] {
+            let Some(tasks) = tasks else {
+                continue;
+            };
+
+            for name in tasks.tasks.keys() {
+                // Private tasks and hooks can't be ran directly
+                if (runner == "poe" && name.starts_with('_'))
+                    || (runner == "taskipy"
+                        && (name.starts_with("pre_") || name.starts_with("post_")))
+                {
+                    continue;
+                }
+
+                self.insert_task(
+                    name,
+                    self.create_task(
+                        format!("Inherited from `{name}` {runner} task."),
+                        &[bin, name],
+                        &package_inputs,
+                    )?,
+                )?;
+            }
+        }
+
+        Ok((self.tasks, self.input_files))
+    }
+
+    /// Detect the directories of importable packages, using either
+    /// the `src` layout, or the flat layout:
+    /// https://packaging.python.org/en/latest/discussions/src-layout-vs-flat-layout/
+    fn detect_package_inputs(&self) -> AnyResult<Vec<String>> {
+        let mut inputs = vec![];
+
+        if self.root.join("src").exists() {
+            inputs.push("src/**/*".into());
+        } else {
+            let mut has_modules = false;
+            let mut dirs = vec![];
+
+            for entry in fs::read_dir(self.root)? {
+                let path = entry.path();
+                let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+
+                if path.is_dir() {
+                    if !file_name.starts_with('.')
+                        && file_name != self.config.venv_name
+                        && path.join("__init__.py").exists()
+                    {
+                        dirs.push(format!("{file_name}/**/*"));
+                    }
+                } else if file_name.ends_with(".py") {
+                    has_modules = true;
+                }
+            }
+
+            dirs.sort();
+            inputs.extend(dirs);
+
+            if has_modules {
+                inputs.push("*.py".into());
+            }
+        }
+
+        inputs.push("pyproject.toml".into());
+
+        Ok(inputs)
+    }
+
+    fn create_task(
+        &self,
+        description: String,
+        args: &[&str],
+        inputs: &[String],
+    ) -> AnyResult<PartialTaskConfig> {
+        // Run through the package manager, so that the virtual environment is used
+        let mut command: Vec<String> = match self.config.package_manager {
+            Some(PythonPackageManager::Poetry) => vec!["poetry".into(), "run".into()],
+            Some(PythonPackageManager::Uv) => vec!["uv".into(), "run".into()],
+            _ => vec![],
+        };
+        command.extend(args.iter().map(|arg| arg.to_string()));
+
+        Ok(PartialTaskConfig {
+            description: Some(description),
+            command: Some(PartialTaskArgs::List(command)),
+            inputs: Some(
+                inputs
+                    .iter()
+                    .map(Input::parse)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            toolchains: Some(OneOrMany::One(Id::raw("python"))),
+            ..Default::default()
+        })
+    }
+
+    fn insert_task(&mut self, name: &str, task: PartialTaskConfig) -> AnyResult<()> {
+        self.tasks.insert(Id::clean(name)?, task);
+
+        Ok(())
+    }
+}
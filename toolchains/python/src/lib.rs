@@ -1,5 +1,7 @@
 pub mod config;
 #[cfg(feature = "wasm")]
+mod infer_tasks;
+#[cfg(feature = "wasm")]
 mod managers;
 mod pyproject_toml;
 #[cfg(feature = "wasm")]
@@ -10,6 +10,7 @@ use pep508_rs::Requirement;
 use pyproject_toml::PyProjectToml as BasePyProjectToml;
 use serde::{Deserialize, Serialize};
 use starbase_utils::toml::{self, TomlValue};
+use std::collections::BTreeMap;
 
 #[cfg(feature = "wasm")]
 #[host_fn]
@@ -124,28 +125,126 @@ impl PyProjectTomlWithTools {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct PyProjectTomlWithToolsInner {
     pub tool: Option<Tool>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct Tool {
+    pub mypy: Option<TomlValue>,
+    pub poe: Option<ToolTasks>,
+    pub poetry: Option<ToolPoetry>,
+    pub pytest: Option<ToolPytest>,
+    pub ruff: Option<TomlValue>,
+    pub taskipy: Option<ToolTasks>,
     pub uv: Option<ToolUv>,
 }
 
+// https://docs.pytest.org/en/stable/reference/customize.html#pyproject-toml
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ToolPytest {
+    pub ini_options: Option<ToolPytestOptions>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ToolPytestOptions {
+    pub testpaths: Vec<String>,
+}
+
+// `[tool.poe.tasks]` and `[tool.taskipy.tasks]`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ToolTasks {
+    pub tasks: BTreeMap<String, TomlValue>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct ToolUv {
     pub dev_dependencies: Vec<Requirement>,
+    pub sources: BTreeMap<String, ToolUvSources>,
     pub workspace: Option<ToolUvWorkspace>,
 }
 
+// https://docs.astral.sh/uv/concepts/projects/dependencies/#dependency-sources
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ToolUvSource {
+    pub editable: Option<bool>,
+    pub git: Option<String>,
+    pub path: Option<String>,
+    pub url: Option<String>,
+    pub workspace: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ToolUvSources {
+    Many(Vec<ToolUvSource>),
+    One(ToolUvSource),
+}
+
+impl ToolUvSources {
+    pub fn to_list(&self) -> Vec<&ToolUvSource> {
+        match self {
+            Self::Many(list) => list.iter().collect(),
+            Self::One(source) => vec![source],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct ToolUvWorkspace {
     pub members: Option<Vec<String>>,
     pub exclude: Option<Vec<String>>,
 }
+
+// https://python-poetry.org/docs/dependency-specification/
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ToolPoetry {
+    pub dependencies: BTreeMap<String, ToolPoetryDependency>,
+    pub dev_dependencies: BTreeMap<String, ToolPoetryDependency>,
+    pub group: BTreeMap<String, ToolPoetryGroup>,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ToolPoetryGroup {
+    pub dependencies: BTreeMap<String, ToolPoetryDependency>,
+    pub optional: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ToolPoetryDependency {
+    Version(String),
+    Detailed(ToolPoetryDependencyDetail),
+    Multiple(Vec<ToolPoetryDependencyDetail>),
+}
+
+impl ToolPoetryDependency {
+    /// Return the local path of a path dependency.
+    pub fn get_path(&self) -> Option<&str> {
+        match self {
+            Self::Version(_) => None,
+            Self::Detailed(detail) => detail.path.as_deref(),
+            Self::Multiple(list) => list.iter().find_map(|detail| detail.path.as_deref()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ToolPoetryDependencyDetail {
+    pub develop: Option<bool>,
+    pub path: Option<String>,
+    pub version: Option<String>,
+}
@@ -1,4 +1,4 @@
-use crate::config::{PythonPackageManager, PythonToolchainConfig};
+use crate::config::{PythonPackageManager, PythonToolchainConfig, PythonVenvLocation};
 use extism_pdk::*;
 use moon_config::LanguageType;
 use moon_pdk::{parse_toolchain_config, parse_toolchain_config_schema};
@@ -149,10 +149,18 @@ pub fn prune_docker(Json(input): Json<PruneDockerInput>) -> FnResult<Json<PruneD
     let mut output = PruneDockerOutput::default();
 
     if input.docker_config.delete_vendor_directories {
-        for dep in input.project_dependencies {
-            let dep_root = input.context.get_project_root(&dep);
-            let venv_dir = dep_root.join(&config.venv_name);
+        let mut venv_dirs = input
+            .project_dependencies
+            .iter()
+            .map(|dep| input.context.get_project_root(dep).join(&config.venv_name))
+            .collect::<Vec<_>>();
 
+        // The shared virtual environment is installed into by all projects
+        if config.venv_location == PythonVenvLocation::Workspace {
+            venv_dirs.push(input.context.workspace_root.join(&config.venv_name));
+        }
+
+        for venv_dir in venv_dirs {
             if venv_dir.exists() {
                 fs::remove_dir_all(&venv_dir)?;
 
@@ -1,17 +1,21 @@
 use crate::config::*;
+use crate::infer_tasks::TasksInferrer;
 use crate::managers::*;
-use crate::pyproject_toml::{PyProjectToml, PyProjectTomlWithTools, normalize_distribution_name};
+use crate::pyproject_toml::{
+    PyProjectToml, PyProjectTomlWithTools, Tool, ToolUvSources, normalize_distribution_name,
+};
 use extism_pdk::*;
 use moon_config::DependencyScope;
 use moon_pdk::VirtualPathExt;
 use moon_pdk::{
     load_project_toolchain_config, load_toolchain_config, locate_root, locate_root_many,
-    locate_root_many_with_check, parse_toolchain_config_schema,
+    locate_root_many_with_check, locate_root_with_check, parse_toolchain_config_schema,
 };
 use moon_pdk_api::*;
 use pep508_rs::Requirement;
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use toolchain_common::{add_project_dependency, normalize_path};
 
 #[plugin_fn]
 pub fn define_requirements(
@@ -38,21 +42,31 @@ pub fn define_requirements(
 pub fn extend_project_graph(
     Json(input): Json<ExtendProjectGraphInput>,
 ) -> FnResult<Json<ExtendProjectGraphOutput>> {
+    let config = parse_toolchain_config_schema::<PythonToolchainConfig>(input.toolchain_config)?;
     let mut output = ExtendProjectGraphOutput::default();
 
+    // Map each project's source directory to its id, so we can resolve
+    // a path dependency back to the project it points at.
+    let mut by_source: HashMap<PathBuf, Id> = HashMap::new();
+
+    for (id, source) in &input.project_sources {
+        by_source.insert(PathBuf::from(source), id.clone());
+    }
+
     // First pass, gather all packages and their manifests.
     let mut packages = BTreeMap::default();
+    let mut tasks = BTreeMap::default();
 
-    for (id, source) in input.project_sources {
-        let project_root = input.context.get_project_root_from_source(&source);
+    for (id, source) in &input.project_sources {
+        let project_root = input.context.get_project_root_from_source(source);
         let manifest_path = project_root.join("pyproject.toml");
 
         if manifest_path.exists() {
-            let mut manifest = PyProjectToml::load(manifest_path)?;
+            let mut manifest = PyProjectToml::load(manifest_path.clone())?;
+            let tool = PyProjectTomlWithTools::load(manifest_path)?.data.tool;
 
             // Remove fields we don't need to avoid eating a ton of memory
             manifest.build_system = None;
-            manifest.dependency_groups = None;
 
             // We need to track all packages, even those without a name
             if let Some(project) = &mut manifest.project {
@@ -61,47 +75,196 @@ pub fn extend_project_graph(
                 project.maintainers = None;
                 project.keywords = None;
                 project.classifiers = None;
+            }
+
+            if config.infer_tasks_from_tools {
+                let (project_tasks, input_files) = TasksInferrer::new(
+                    &config,
+                    &manifest,
+                    tool.as_ref().unwrap_or(&Tool::default()),
+                    &project_root,
+                )
+                .infer()?;
+
+                if !project_tasks.is_empty() {
+                    tasks.insert(id.to_owned(), project_tasks);
+                }
+
+                output.input_files.extend(input_files);
+            }
 
-                packages.insert(normalize_distribution_name(&project.name), (id, manifest));
+            // Poetry (< 2) declares the package name in its own table
+            let name = manifest
+                .project
+                .as_ref()
+                .map(|project| project.name.clone())
+                .or_else(|| {
+                    tool.as_ref()
+                        .and_then(|tool| tool.poetry.as_ref())
+                        .and_then(|poetry| poetry.name.clone())
+                });
+
+            // Named packages report their manifest below, but the manifest
+            // of other projects is still a source of inferred tasks
+            if name.is_none() && config.infer_tasks_from_tools {
+                output.input_files.push(project_root.join("pyproject.toml"));
+            }
+
+            if let Some(name) = name {
+                packages.insert(
+                    normalize_distribution_name(&name),
+                    (
+                        id.to_owned(),
+                        source,
+                        name,
+                        manifest,
+                        tool.unwrap_or_default(),
+                    ),
+                );
             }
         }
     }
 
     // Second pass, extract packages and their relationships
-    for (id, manifest) in packages.values() {
-        let mut project_output = ExtendProjectOutput::default();
-
-        let mut extract_implicit_deps =
-            |reqs: &[Requirement], scope: DependencyScope| -> AnyResult<()> {
-                for req in reqs {
-                    let req_label = req.name.as_ref().to_owned();
-                    let req_name = normalize_distribution_name(req.name.as_ref());
-
-                    if req.version_or_url.is_none()
-                        && req.origin.is_none()
-                        && let Some((dep_id, _)) = packages.get(&req_name)
-                    {
-                        project_output.dependencies.push(ProjectDependency {
-                            id: dep_id.to_owned(),
-                            scope,
-                            via: Some(format!("requirement {req_label}")),
-                        });
-                    }
+    for (id, source, name, manifest, tool) in packages.values() {
+        let mut project_output = ExtendProjectOutput {
+            alias: Some(name.to_owned()),
+            tasks: tasks.remove(id).unwrap_or_default(),
+            ..Default::default()
+        };
+
+        // Sources in the uv workspace root apply to all members,
+        // unless the member defines a source with the same name
+        let root_sources = load_uv_workspace_sources(
+            &input.context,
+            &input.context.get_project_root_from_source(source),
+        )?;
+
+        let resolve_path_dep =
+            |base: &Path, path: &str| by_source.get(&normalize_path(&base.join(path)));
+
+        // https://docs.astral.sh/uv/concepts/projects/dependencies/#dependency-sources
+        let resolve_uv_source = |req_name: &str| {
+            let find_sources = |sources: &BTreeMap<String, ToolUvSources>| {
+                sources
+                    .iter()
+                    .find(|(name, _)| normalize_distribution_name(name) == req_name)
+                    .map(|(_, sources)| sources.to_owned())
+            };
+
+            let (base, sources) = match tool.uv.as_ref().and_then(|uv| find_sources(&uv.sources)) {
+                Some(sources) => (Path::new(source.as_str()), sources),
+                None => {
+                    let (root_source, root_sources) = root_sources.as_ref()?;
+
+                    (root_source.as_path(), find_sources(root_sources)?)
+                }
+            };
+
+            Some(sources.to_list().into_iter().find_map(|source| {
+                if source.workspace {
+                    packages.get(req_name).map(|(dep_id, ..)| dep_id)
+                } else {
+                    source
+                        .path
+                        .as_deref()
+                        .and_then(|path| resolve_path_dep(base, path))
                 }
+            }))
+        };
+
+        // Extras and groups may include each other, so resolve them into flat lists
+        let resolved = manifest.resolve()?;
+        let mut reqs: Vec<(&Requirement, DependencyScope)> = vec![];
+
+        if let Some(deps) = manifest
+            .project
+            .as_ref()
+            .and_then(|project| project.dependencies.as_ref())
+        {
+            reqs.extend(deps.iter().map(|req| (req, DependencyScope::Production)));
+        }
+
+        for deps in resolved.optional_dependencies.values() {
+            reqs.extend(deps.iter().map(|req| (req, DependencyScope::Production)));
+        }
 
-                Ok(())
+        // Groups are never part of the published package,
+        // so they're only required for development
+        for deps in resolved.dependency_groups.values() {
+            reqs.extend(deps.iter().map(|req| (req, DependencyScope::Development)));
+        }
+
+        if let Some(uv) = &tool.uv {
+            reqs.extend(
+                uv.dev_dependencies
+                    .iter()
+                    .map(|req| (req, DependencyScope::Development)),
+            );
+        }
+
+        for (req, scope) in reqs {
+            let req_label = req.name.as_ref().to_owned();
+            let req_name = normalize_distribution_name(req.name.as_ref());
+
+            let dep_id = match resolve_uv_source(&req_name) {
+                Some(dep_id) => dep_id,
+                None if req.version_or_url.is_none() && req.origin.is_none() => {
+                    packages.get(&req_name).map(|(dep_id, ..)| dep_id)
+                }
+                None => None,
             };
 
-        if let Some(project) = &manifest.project {
-            project_output.alias = Some(project.name.clone());
+            if let Some(dep_id) = dep_id {
+                add_project_dependency(
+                    &mut project_output,
+                    id,
+                    dep_id,
+                    scope,
+                    format!("requirement {req_label}"),
+                );
+            }
+        }
 
-            if let Some(deps) = &project.dependencies {
-                extract_implicit_deps(deps, DependencyScope::Production)?;
+        // https://python-poetry.org/docs/dependency-specification/#path-dependencies
+        if let Some(poetry) = &tool.poetry {
+            let mut deps = vec![];
+
+            deps.extend(
+                poetry
+                    .dependencies
+                    .iter()
+                    .map(|dep| (dep, DependencyScope::Production)),
+            );
+            deps.extend(
+                poetry
+                    .dev_dependencies
+                    .iter()
+                    .map(|dep| (dep, DependencyScope::Development)),
+            );
+
+            for (group_name, group) in &poetry.group {
+                let scope = if group_name == "main" {
+                    DependencyScope::Production
+                } else {
+                    DependencyScope::Development
+                };
+
+                deps.extend(group.dependencies.iter().map(|dep| (dep, scope)));
             }
 
-            if let Some(deps) = &project.optional_dependencies {
-                for reqs in deps.values() {
-                    extract_implicit_deps(reqs, DependencyScope::Production)?;
+            for ((dep_name, dep), scope) in deps {
+                if let Some(dep_id) = dep
+                    .get_path()
+                    .and_then(|path| resolve_path_dep(Path::new(source.as_str()), path))
+                {
+                    add_project_dependency(
+                        &mut project_output,
+                        id,
+                        dep_id,
+                        scope,
+                        format!("path dependency {dep_name}"),
+                    );
                 }
             }
         }
@@ -113,15 +276,81 @@ pub fn extend_project_graph(
         output.input_files.push(manifest.path.clone());
     }
 
+    // Projects without a package name can still have tasks
+    for (id, tasks) in tasks {
+        output.extended_projects.insert(
+            id,
+            ExtendProjectOutput {
+                tasks,
+                ..Default::default()
+            },
+        );
+    }
+
     Ok(Json(output))
 }
 
+/// Load `[tool.uv.sources]` from the root of the uv workspace that the project
+/// is a member of, with the root's path relative to the moon workspace root.
+fn load_uv_workspace_sources(
+    context: &MoonContext,
+    project_root: &VirtualPath,
+) -> AnyResult<Option<(PathBuf, BTreeMap<String, ToolUvSources>)>> {
+    let mut sources = None;
+
+    // The project's own sources are already used when it is the root
+    let Some(parent_dir) = project_root.parent() else {
+        return Ok(None);
+    };
+
+    locate_root_with_check(&parent_dir, "pyproject.toml", |root| {
+        let Some(uv) = PyProjectTomlWithTools::load(root.join("pyproject.toml"))?
+            .data
+            .tool
+            .and_then(|tool| tool.uv)
+        else {
+            return Ok(false);
+        };
+
+        if uv.workspace.is_none() {
+            return Ok(false);
+        }
+
+        if let Ok(root_source) = root.strip_prefix(&*context.workspace_root) {
+            sources = Some((root_source.to_path_buf(), uv.sources));
+        }
+
+        Ok(true)
+    })?;
+
+    Ok(sources)
+}
+
+/// Return the directory in which the virtual environment is created,
+/// for the provided dependencies root.
+fn get_venv_parent(
+    config: &PythonToolchainConfig,
+    context: &MoonContext,
+    root: &VirtualPath,
+) -> VirtualPath {
+    match config.venv_location {
+        PythonVenvLocation::Project => root.to_owned(),
+        PythonVenvLocation::Workspace => context.workspace_root.clone(),
+    }
+}
+
 fn gather_shared_paths(
     config: &PythonToolchainConfig,
+    context: &MoonContext,
     current_dir: &VirtualPath,
     paths: &mut Vec<PathBuf>,
 ) -> AnyResult<()> {
-    if let Some(venv_parent) = locate_root(current_dir, &config.venv_name)
+    let venv_parent = match config.venv_location {
+        PythonVenvLocation::Project => locate_root(current_dir, &config.venv_name),
+        PythonVenvLocation::Workspace => Some(context.workspace_root.clone()),
+    };
+
+    if let Some(venv_parent) = venv_parent
         && let Some(venv_root) = venv_parent.join(&config.venv_name).to_real_path()?
     {
         paths.push(venv_root.join("Scripts").to_path_buf());
@@ -138,7 +367,12 @@ pub fn extend_command(
     let config = parse_toolchain_config_schema::<PythonToolchainConfig>(input.toolchain_config)?;
     let mut output = ExtendTaskCommandOutput::default();
 
-    gather_shared_paths(&config, &input.current_dir, &mut output.paths)?;
+    gather_shared_paths(
+        &config,
+        &input.context,
+        &input.current_dir,
+        &mut output.paths,
+    )?;
 
     Ok(Json(output))
 }
@@ -287,6 +521,30 @@ pub fn install_dependencies(
         command.args.extend(package_manager_config.install_args);
     }
 
+    // Target the shared virtual environment, as the dependencies root
+    // may not be where it was created
+    if config.venv_location == PythonVenvLocation::Workspace
+        && let Some(venv_root) = input
+            .context
+            .workspace_root
+            .join(&config.venv_name)
+            .to_real_path()?
+    {
+        let venv_root_str = venv_root.to_string_lossy().to_string();
+
+        // Don't remove packages that were installed by other projects
+        if package_manager == PythonPackageManager::Uv {
+            command.args.push("--inexact".into());
+            command
+                .env
+                .insert("UV_PROJECT_ENVIRONMENT".into(), venv_root_str.clone());
+        }
+
+        command.env.insert("VIRTUAL_ENV".into(), venv_root_str);
+        command.paths.push(venv_root.join("Scripts").to_path_buf());
+        command.paths.push(venv_root.join("bin").to_path_buf());
+    }
+
     command.cwd = Some(input.root.clone());
 
     output.install_command = Some(command.into());
@@ -366,15 +624,17 @@ pub fn setup_environment(
         command.args.extend(package_manager_config.venv_args);
     }
 
+    let venv_parent = get_venv_parent(&config, &input.context, &input.root);
+
     // If the venv already exists, and `--clear` is not passed (the same for all managers),
     // then we can just avoid the overhead of initializing the venv again
     if !command.args.iter().any(|arg| arg == "--clear")
-        && input.root.join(&config.venv_name).exists()
+        && venv_parent.join(&config.venv_name).exists()
     {
         return Ok(Json(output));
     }
 
-    command.cwd = Some(input.root.clone());
+    command.cwd = Some(venv_parent);
 
     output.commands.push(command.into());
 
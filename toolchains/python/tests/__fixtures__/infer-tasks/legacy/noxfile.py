@@ -0,0 +1,11 @@
+import nox
+
+
+@nox.session
+def tests(session):
+    session.run("pytest")
+
+
+@nox.session(name="type-check", python=["3.11"])
+def mypy(session):
+    session.run("mypy")
@@ -219,5 +219,32 @@ mod python_toolchain_tier1 {
                 [VirtualPath::new("/workspace/a/venv")]
             );
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn removes_workspace_venv_dir() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(".venv/pyvenv.cfg", "");
+
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .prune_docker(PruneDockerInput {
+                    docker_config: DockerPruneConfig {
+                        delete_vendor_directories: true,
+                        ..Default::default()
+                    },
+                    project_dependencies: vec![create_project_fragment("a")],
+                    root: VirtualPath::new(sandbox.path()),
+                    toolchain_config: json!({
+                        "venvLocation": "workspace"
+                    }),
+                    ..Default::default()
+                })
+                .await;
+
+            assert!(!sandbox.path().join(".venv").exists());
+
+            assert_eq!(output.changed_files, [VirtualPath::new("/workspace/.venv")]);
+        }
     }
 }
@@ -1,4 +1,6 @@
-use moon_config::DependencyScope;
+use moon_config::{
+    DependencyScope, Input, OneOrMany, PartialTaskArgs, PartialTaskConfig, TaskPreset,
+};
 use moon_pdk_api::*;
 use moon_pdk_test_utils::{create_empty_moon_sandbox, create_moon_sandbox};
 use serde_json::json;
@@ -118,6 +120,325 @@ dependencies = ["internal-lib"]
             );
         }
 
+        fn create_sources_input() -> ExtendProjectGraphInput {
+            let mut input = ExtendProjectGraphInput::default();
+
+            for (id, source) in [
+                ("api", "packages/api"),
+                ("core", "packages/core"),
+                ("legacy", "legacy"),
+                ("testkit", "tools/testkit"),
+                ("utils", "packages/utils"),
+            ] {
+                input.project_sources.insert(Id::raw(id), source.into());
+            }
+
+            input
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_uv_sources_and_dependency_groups() {
+            let sandbox = create_moon_sandbox("sources");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin.extend_project_graph(create_sources_input()).await;
+
+            assert_eq!(
+                output.extended_projects.get("api").unwrap().dependencies,
+                vec![
+                    ProjectDependency {
+                        id: Id::raw("core"),
+                        scope: DependencyScope::Production,
+                        via: Some("requirement core".into()),
+                    },
+                    // From the included `lint` group
+                    ProjectDependency {
+                        id: Id::raw("core"),
+                        scope: DependencyScope::Development,
+                        via: Some("requirement core".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("testkit"),
+                        scope: DependencyScope::Development,
+                        via: Some("requirement testkit".into()),
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_uv_sources_from_workspace_root() {
+            let sandbox = create_moon_sandbox("sources");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin.extend_project_graph(create_sources_input()).await;
+
+            assert_eq!(
+                output.extended_projects.get("core").unwrap().dependencies,
+                vec![
+                    ProjectDependency {
+                        id: Id::raw("utils"),
+                        scope: DependencyScope::Production,
+                        via: Some("requirement utils".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("testkit"),
+                        scope: DependencyScope::Development,
+                        via: Some("requirement testkit".into()),
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_poetry_path_dependencies() {
+            let sandbox = create_moon_sandbox("sources");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin.extend_project_graph(create_sources_input()).await;
+
+            assert_eq!(
+                output.extended_projects.get("legacy").unwrap(),
+                &ExtendProjectOutput {
+                    alias: Some("legacy".into()),
+                    dependencies: vec![
+                        ProjectDependency {
+                            id: Id::raw("core"),
+                            scope: DependencyScope::Production,
+                            via: Some("path dependency core".into()),
+                        },
+                        ProjectDependency {
+                            id: Id::raw("testkit"),
+                            scope: DependencyScope::Development,
+                            via: Some("path dependency testkit".into()),
+                        },
+                    ],
+                    ..Default::default()
+                }
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_tasks_when_disabled() {
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input
+                .project_sources
+                .insert(Id::raw("legacy"), "legacy".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert!(
+                output
+                    .extended_projects
+                    .get("app")
+                    .unwrap()
+                    .tasks
+                    .is_empty()
+            );
+            assert!(!output.extended_projects.contains_key("legacy"));
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_tasks_from_pyproject_tools() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromTools": true,
+                "packageManager": "uv"
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                ["format-check", "lint", "run-serve", "test", "typecheck"]
+            );
+
+            let inputs = vec![
+                Input::parse("src/**/*").unwrap(),
+                Input::parse("pyproject.toml").unwrap(),
+            ];
+
+            // Poe tasks take precedence over ruff
+            assert_eq!(
+                tasks.get("lint").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Inherited from `lint` poe task.".into()),
+                    command: Some(PartialTaskArgs::List(vec![
+                        "uv".into(),
+                        "run".into(),
+                        "poe".into(),
+                        "lint".into(),
+                    ])),
+                    inputs: Some(inputs.clone()),
+                    toolchains: Some(OneOrMany::One(Id::raw("python"))),
+                    ..Default::default()
+                }
+            );
+
+            assert_eq!(
+                tasks.get("typecheck").unwrap().command,
+                Some(PartialTaskArgs::List(vec![
+                    "uv".into(),
+                    "run".into(),
+                    "mypy".into(),
+                    ".".into(),
+                ]))
+            );
+
+            assert_eq!(
+                tasks.get("run-serve").unwrap().preset,
+                Some(TaskPreset::Utility)
+            );
+
+            assert_eq!(
+                tasks.get("test").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Tests with pytest.".into()),
+                    command: Some(PartialTaskArgs::List(vec![
+                        "uv".into(),
+                        "run".into(),
+                        "pytest".into(),
+                    ])),
+                    inputs: Some(vec![
+                        Input::parse("src/**/*").unwrap(),
+                        Input::parse("pyproject.toml").unwrap(),
+                        Input::parse("tests/**/*").unwrap(),
+                        Input::parse("integration/**/*").unwrap(),
+                    ]),
+                    toolchains: Some(OneOrMany::One(Id::raw("python"))),
+                    ..Default::default()
+                }
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_tasks_from_multiline_tox_envlist_and_unquoted_nox_names() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file("app/pyproject.toml", "[project]\nname = \"app\"\n");
+            sandbox.create_file(
+                "app/tox.ini",
+                "[tox]\nenvlist =\n    py{310,311}\n    lint, type\n\n    docs\nskipsdist = true\n",
+            );
+            sandbox.create_file(
+                "app/noxfile.py",
+                "@nox.session(name=\n)\ndef tests(session):\n    pass\n\n@nox.session(name=NAME)\ndef lint(session):\n    pass\n\n@nox.session(name=\"ü\")\ndef unicode(session):\n    pass\n",
+            );
+
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromTools": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                [
+                    "nox-lint",
+                    "nox-tests",
+                    "nox-ü",
+                    "tox-docs",
+                    "tox-lint",
+                    "tox-type"
+                ]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_tasks_from_tox_and_nox() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input
+                .project_sources
+                .insert(Id::raw("legacy"), "legacy".into());
+            input.toolchain_config = json!({
+                "inferTasksFromTools": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("legacy").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                [
+                    "docs",
+                    "nox-tests",
+                    "nox-type-check",
+                    "tox-docs",
+                    "tox-lint",
+                    "typecheck"
+                ]
+            );
+
+            assert_eq!(
+                tasks.get("tox-lint").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Runs the `lint` tox environment.".into()),
+                    command: Some(PartialTaskArgs::List(vec![
+                        "tox".into(),
+                        "-e".into(),
+                        "lint".into(),
+                    ])),
+                    inputs: Some(vec![
+                        Input::parse("pkg/**/*").unwrap(),
+                        Input::parse("*.py").unwrap(),
+                        Input::parse("pyproject.toml").unwrap(),
+                        Input::parse("tox.ini").unwrap(),
+                    ]),
+                    toolchains: Some(OneOrMany::One(Id::raw("python"))),
+                    ..Default::default()
+                }
+            );
+
+            assert_eq!(
+                tasks.get("nox-type-check").unwrap().command,
+                Some(PartialTaskArgs::List(vec![
+                    "nox".into(),
+                    "-s".into(),
+                    "type-check".into(),
+                ]))
+            );
+
+            assert_eq!(
+                tasks.get("typecheck").unwrap().command,
+                Some(PartialTaskArgs::List(vec!["mypy".into()]))
+            );
+
+            assert_eq!(
+                tasks.get("docs").unwrap().command,
+                Some(PartialTaskArgs::List(vec!["task".into(), "docs".into()]))
+            );
+
+            // The project is unnamed, so its manifest is only a source of tasks
+            assert_eq!(
+                output.input_files,
+                [
+                    VirtualPath::new("/workspace/legacy/tox.ini"),
+                    VirtualPath::new("/workspace/legacy/noxfile.py"),
+                    VirtualPath::new("/workspace/legacy/pyproject.toml"),
+                ]
+            );
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn ignores_projects_not_in_sources() {
             let sandbox = create_moon_sandbox("projects");
@@ -234,6 +555,36 @@ dependencies = ["internal-lib"]
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn adds_workspace_venv_paths_when_location_is_workspace() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(".venv/file", "");
+            sandbox.create_file("app/.venv/file", "");
+
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .extend_command(ExtendCommandInput {
+                    command: "python".into(),
+                    toolchain_config: json!({
+                        "venvLocation": "workspace"
+                    }),
+                    current_dir: plugin
+                        .plugin
+                        .to_virtual_path(sandbox.path().join("app/sub")),
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(
+                output.paths,
+                vec![
+                    sandbox.path().join(".venv/Scripts"),
+                    sandbox.path().join(".venv/bin")
+                ]
+            );
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn adds_venv_paths_with_custom_name() {
             let sandbox = create_empty_moon_sandbox();
@@ -817,6 +1168,81 @@ dependencies = ["internal-lib"]
             assert_eq!(actual, expected);
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn targets_workspace_venv_with_pip() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(".venv/bin/activate", "");
+            sandbox.create_file("app/requirements.txt", "");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .install_dependencies(InstallDependenciesInput {
+                    root: VirtualPath::new(sandbox.path().join("app")),
+                    toolchain_config: json!({
+                        "packageManager": "pip",
+                        "venvLocation": "workspace"
+                    }),
+                    project: Some(ProjectFragment {
+                        id: Id::raw("app"),
+                        source: "app".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .await;
+
+            let mut input =
+                ExecCommandInput::new("python", ["-m", "pip", "install", "-r", "requirements.txt"])
+                    .cwd(plugin.plugin.to_virtual_path(sandbox.path().join("app")));
+            input.env.insert(
+                "VIRTUAL_ENV".into(),
+                sandbox.path().join(".venv").to_string_lossy().to_string(),
+            );
+            input.paths = vec![
+                sandbox.path().join(".venv/Scripts"),
+                sandbox.path().join(".venv/bin"),
+            ];
+
+            assert_eq!(output.install_command.unwrap(), ExecCommand::new(input));
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn targets_workspace_venv_with_uv() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(".venv/bin/activate", "");
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .install_dependencies(InstallDependenciesInput {
+                    root: VirtualPath::new(sandbox.path().join("app")),
+                    toolchain_config: json!({
+                        "packageManager": "uv",
+                        "venvLocation": "workspace"
+                    }),
+                    project: Some(ProjectFragment {
+                        id: Id::raw("app"),
+                        source: "app".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .await;
+
+            let venv_root = sandbox.path().join(".venv").to_string_lossy().to_string();
+            let mut input = ExecCommandInput::new("uv", ["sync", "--no-progress", "--inexact"])
+                .cwd(plugin.plugin.to_virtual_path(sandbox.path().join("app")));
+            input
+                .env
+                .insert("UV_PROJECT_ENVIRONMENT".into(), venv_root.clone());
+            input.env.insert("VIRTUAL_ENV".into(), venv_root);
+            input.paths = vec![
+                sandbox.path().join(".venv/Scripts"),
+                sandbox.path().join(".venv/bin"),
+            ];
+
+            assert_eq!(output.install_command.unwrap(), ExecCommand::new(input));
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn supports_uv_production() {
             let sandbox = create_empty_moon_sandbox();
@@ -1776,6 +2202,62 @@ files = []
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn creates_venv_in_workspace_root() {
+            let sandbox = create_empty_moon_sandbox();
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .setup_environment(SetupEnvironmentInput {
+                    root: VirtualPath::new(sandbox.path().join("app")),
+                    toolchain_config: json!({
+                        "packageManager": "pip",
+                        "venvLocation": "workspace"
+                    }),
+                    project: Some(ProjectFragment {
+                        id: Id::raw("app"),
+                        source: "app".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(
+                output.commands,
+                vec![ExecCommand::new(
+                    ExecCommandInput::new("python", ["-m", "venv", ".venv"])
+                        .cwd(plugin.plugin.to_virtual_path(sandbox.path()))
+                )]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn skips_setup_if_workspace_venv_already_exists() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(".venv/pyvenv.cfg", "");
+
+            let plugin = sandbox.create_toolchain("python").await;
+
+            let output = plugin
+                .setup_environment(SetupEnvironmentInput {
+                    root: VirtualPath::new(sandbox.path().join("app")),
+                    toolchain_config: json!({
+                        "packageManager": "uv",
+                        "venvLocation": "workspace"
+                    }),
+                    project: Some(ProjectFragment {
+                        id: Id::raw("app"),
+                        source: "app".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .await;
+
+            assert!(output.commands.is_empty());
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn skips_setup_if_venv_already_exists() {
             let sandbox = create_empty_moon_sandbox();
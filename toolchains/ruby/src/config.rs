@@ -34,6 +34,10 @@ config_struct!(
         /// Run installs in frozen/deployment mode (CI-friendly).
         pub frozen: bool,
 
+        /// Automatically infer moon tasks from the `Rakefile` (and `lib/tasks/*.rake`),
+        /// `bin/rails` commands, and the presence of RSpec or Minitest.
+        pub infer_tasks_from_tools: bool,
+
         /// Gem groups to exclude during a production install, applied via
         /// `BUNDLE_WITHOUT`. Defaults to development + test when empty.
         pub production_without_groups: Vec<String>,
@@ -2,7 +2,8 @@
 //!
 //! A `Gemfile` is executable Ruby script, so this intentionally does not try to
 //! parse dependencies in general. It only extracts literal `gem ..., path: ...`
-//! declarations so moon can infer project-to-project edges in Ruby monorepos.
+//! declarations, gems within `path "..." do` blocks, and `gemspec path: "..."`
+//! calls so moon can infer project-to-project edges in Ruby monorepos.
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PathGem {
@@ -11,11 +12,20 @@ pub struct PathGem {
     pub groups: Vec<String>,
 }
 
+#[derive(Default)]
+struct Block {
+    groups: Vec<String>,
+    path: Option<String>,
+}
+
 /// Parse literal `path:` gem declarations from a `Gemfile`, preserving Bundler
 /// group names verbatim. The caller decides how to map groups onto moon scopes.
+///
+/// Bundler searches a path for gemspecs (`{,*,*/*}.gemspec`), so the returned
+/// path may point to the gem itself, or to a directory containing the gem.
 pub fn parse_path_gems(content: &str) -> Vec<PathGem> {
     let mut gems = vec![];
-    let mut block_stack: Vec<Vec<String>> = vec![];
+    let mut block_stack: Vec<Block> = vec![];
 
     for line in content.lines() {
         let line = strip_comment(line).trim().to_owned();
@@ -30,25 +40,41 @@ pub fn parse_path_gems(content: &str) -> Vec<PathGem> {
 
         if line.ends_with(" do") {
             if let Some(groups) = parse_group_call(&line) {
-                block_stack.push(groups);
+                block_stack.push(Block { groups, path: None });
+            } else if let Some(path) = parse_path_call(&line) {
+                block_stack.push(Block {
+                    groups: vec![],
+                    path: Some(path),
+                });
             } else {
                 // Keep nested non-group blocks balanced so their `end` doesn't
                 // accidentally pop an enclosing group.
-                block_stack.push(vec![]);
+                block_stack.push(Block::default());
             }
             continue;
         }
 
         if starts_ruby_block(&line) {
-            block_stack.push(vec![]);
+            block_stack.push(Block::default());
             continue;
         }
 
-        if let Some(args) = after_bare_call(&line, "gem")
-            && let Some(mut gem) = parse_gem_call(args)
-        {
-            for groups in &block_stack {
-                for group in groups {
+        let gem = if let Some(args) = after_bare_call(&line, "gem") {
+            let block_path = block_stack
+                .iter()
+                .rev()
+                .find_map(|block| block.path.clone());
+
+            parse_gem_call(args, block_path)
+        } else if let Some(args) = after_bare_call(&line, "gemspec") {
+            parse_gemspec_call(args)
+        } else {
+            None
+        };
+
+        if let Some(mut gem) = gem {
+            for block in &block_stack {
+                for group in &block.groups {
                     if !gem.groups.contains(group) {
                         gem.groups.push(group.clone());
                     }
@@ -62,15 +88,25 @@ pub fn parse_path_gems(content: &str) -> Vec<PathGem> {
     gems
 }
 
+fn parse_path_call(line: &str) -> Option<String> {
+    let line = line.strip_suffix(" do")?.trim_end();
+    let args = after_bare_call(line, "path")?;
+
+    split_args(args)
+        .first()
+        .and_then(|arg| quoted(arg))
+        .map(str::to_owned)
+}
+
 fn parse_group_call(line: &str) -> Option<Vec<String>> {
     let line = line.strip_suffix(" do")?.trim_end();
     let args = after_bare_call(line, "group")?;
     Some(parse_groups(args))
 }
 
-fn parse_gem_call(args: &str) -> Option<PathGem> {
+fn parse_gem_call(args: &str, block_path: Option<String>) -> Option<PathGem> {
     let mut name = None;
-    let mut path = None;
+    let mut path = block_path;
     let mut groups = vec![];
 
     for part in split_args(args) {
@@ -86,6 +122,8 @@ fn parse_gem_call(args: &str) -> Option<PathGem> {
         if let Some((key, value)) = split_option(part) {
             match key {
                 "path" => path = quoted(value.trim()).map(str::to_owned),
+                // Remote sources take precedence over an enclosing `path` block
+                "git" | "github" => path = None,
                 "group" | "groups" => {
                     for group in parse_groups(value) {
                         if !groups.contains(&group) {
@@ -105,6 +143,36 @@ fn parse_gem_call(args: &str) -> Option<PathGem> {
     })
 }
 
+/// A `gemspec` call without a `path:` loads the project's own gemspec,
+/// which is not an edge to another gem.
+fn parse_gemspec_call(args: &str) -> Option<PathGem> {
+    let mut name = None;
+    let mut path = None;
+    let mut groups = vec![];
+
+    for part in split_args(args) {
+        if let Some((key, value)) = split_option(part.trim()) {
+            match key {
+                "name" => name = quoted(value.trim()).map(str::to_owned),
+                "path" => path = quoted(value.trim()).map(str::to_owned),
+                "development_group" => groups.extend(group_name(value)),
+                _ => {}
+            }
+        }
+    }
+
+    let path = path?;
+    let name = name.unwrap_or_else(|| {
+        path.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_owned()
+    });
+
+    Some(PathGem { name, path, groups })
+}
+
 fn parse_groups(args: &str) -> Vec<String> {
     let args = args.trim();
     let args = args
@@ -135,7 +203,7 @@ fn group_name(value: &str) -> Option<String> {
     quoted(value).map(str::to_owned)
 }
 
-fn after_bare_call<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+pub(crate) fn after_bare_call<'a>(line: &'a str, name: &str) -> Option<&'a str> {
     let rest = line.strip_prefix(name)?;
 
     if let Some(args) = rest.strip_prefix('(') {
@@ -168,7 +236,7 @@ fn split_option(part: &str) -> Option<(&str, &str)> {
     None
 }
 
-fn quoted(value: &str) -> Option<&str> {
+pub(crate) fn quoted(value: &str) -> Option<&str> {
     let value = value.trim();
     if value.len() < 2 {
         return None;
@@ -184,7 +252,7 @@ fn quoted(value: &str) -> Option<&str> {
         .and_then(|value| value.strip_suffix(quote))
 }
 
-fn strip_comment(line: &str) -> &str {
+pub(crate) fn strip_comment(line: &str) -> &str {
     let mut quote = None;
     let mut escaped = false;
 
@@ -206,7 +274,7 @@ fn strip_comment(line: &str) -> &str {
     line
 }
 
-fn starts_ruby_block(line: &str) -> bool {
+pub(crate) fn starts_ruby_block(line: &str) -> bool {
     [
         "if ", "unless ", "case ", "begin", "while ", "until ", "for ",
     ]
@@ -214,7 +282,7 @@ fn starts_ruby_block(line: &str) -> bool {
     .any(|prefix| line.starts_with(prefix))
 }
 
-fn split_args(args: &str) -> Vec<&str> {
+pub(crate) fn split_args(args: &str) -> Vec<&str> {
     let mut parts = vec![];
     let mut quote = None;
     let mut escaped = false;
@@ -325,6 +393,42 @@ end
         assert_eq!(gems[1].groups, ["test"]);
     }
 
+    #[test]
+    fn captures_gems_within_path_blocks() {
+        let gems = parse_path_gems(
+            r#"
+path "../engines" do
+  gem "admin"
+  gem "billing", path: "../libs/billing"
+end
+gem "rails"
+"#,
+        );
+
+        assert_eq!(gems.len(), 2);
+        assert_eq!(gems[0].name, "admin");
+        assert_eq!(gems[0].path, "../engines");
+        assert_eq!(gems[1].name, "billing");
+        assert_eq!(gems[1].path, "../libs/billing");
+    }
+
+    #[test]
+    fn captures_gemspec_paths() {
+        let gems = parse_path_gems(
+            r#"
+gemspec
+gemspec path: "../core"
+gemspec name: "api-client", path: "../clients/api"
+"#,
+        );
+
+        assert_eq!(gems.len(), 2);
+        assert_eq!(gems[0].name, "core");
+        assert_eq!(gems[0].path, "../core");
+        assert_eq!(gems[1].name, "api-client");
+        assert_eq!(gems[1].path, "../clients/api");
+    }
+
     #[test]
     fn ignores_dynamic_paths() {
         let gems = parse_path_gems(
@@ -0,0 +1,119 @@
+//! Best-effort `.gemspec` dependency scanner.
+//!
+//! Like a `Gemfile`, a gemspec is executable Ruby script. We only extract the
+//! literal gem name and `add_*dependency` calls, so moon can infer edges to
+//! gems that live in the same repository.
+
+use crate::gemfile::{after_bare_call, quoted, split_args, strip_comment};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gemspec {
+    pub name: Option<String>,
+    pub dependencies: Vec<GemspecDependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GemspecDependency {
+    pub name: String,
+    pub development: bool,
+}
+
+pub fn parse_gemspec(content: &str) -> Gemspec {
+    let mut gemspec = Gemspec::default();
+
+    for line in content.lines() {
+        let line = strip_comment(line).trim();
+
+        // Calls are made on the block variable, like `spec.add_dependency`
+        let Some((_, call)) = line.split_once('.') else {
+            continue;
+        };
+
+        if let Some(value) = call
+            .strip_prefix("name")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+        {
+            if gemspec.name.is_none() {
+                gemspec.name = quoted(value.trim()).map(str::to_owned);
+            }
+
+            continue;
+        }
+
+        let (args, development) = if let Some(args) = after_bare_call(call, "add_dependency")
+            .or_else(|| after_bare_call(call, "add_runtime_dependency"))
+        {
+            (args, false)
+        } else if let Some(args) = after_bare_call(call, "add_development_dependency") {
+            (args, true)
+        } else {
+            continue;
+        };
+
+        if let Some(name) = split_args(args).first().and_then(|arg| quoted(arg)) {
+            gemspec.dependencies.push(GemspecDependency {
+                name: name.to_owned(),
+                development,
+            });
+        }
+    }
+
+    gemspec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_name_and_dependencies() {
+        let gemspec = parse_gemspec(
+            r#"
+Gem::Specification.new do |spec|
+  spec.name = "billing"
+  spec.version = Billing::VERSION
+  spec.metadata["homepage_uri"] = spec.homepage
+
+  spec.add_dependency "rails", ">= 7.1"
+  spec.add_runtime_dependency("core")
+  spec.add_development_dependency 'fixtures', '~> 1.0'
+  # spec.add_dependency "ignored"
+end
+"#,
+        );
+
+        assert_eq!(gemspec.name.as_deref(), Some("billing"));
+        assert_eq!(
+            gemspec.dependencies,
+            [
+                GemspecDependency {
+                    name: "rails".into(),
+                    development: false,
+                },
+                GemspecDependency {
+                    name: "core".into(),
+                    development: false,
+                },
+                GemspecDependency {
+                    name: "fixtures".into(),
+                    development: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_dynamic_names() {
+        let gemspec = parse_gemspec(
+            r#"
+Gem::Specification.new do |s|
+  s.name = NAME
+  s.add_dependency DEPENDENCY
+end
+"#,
+        );
+
+        assert!(gemspec.name.is_none());
+        assert!(gemspec.dependencies.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use crate::rakefile::parse_rake_tasks;
+use moon_config::{Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, TaskPreset};
+use moon_pdk_api::{AnyResult, Id, VirtualPath};
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+
+// Directories of a Rails application or engine, and a gem's `lib`
+const SOURCE_DIRS: [&str; 4] = ["app", "config", "db", "lib"];
+
+pub struct TasksInferrer<'a> {
+    root: &'a VirtualPath,
+    tasks: BTreeMap<Id, PartialTaskConfig>,
+    /// Files that tasks were inferred from.
+    input_files: Vec<VirtualPath>,
+}
+
+impl<'a> TasksInferrer<'a> {
+    pub fn new(root: &'a VirtualPath) -> TasksInferrer<'a> {
+        Self {
+            root,
+            tasks: BTreeMap::default(),
+            input_files: vec![],
+        }
+    }
+
+    /// Infer tasks, and return them with the files they were inferred from.
+    pub fn infer(mut self) -> AnyResult<(BTreeMap<Id, PartialTaskConfig>, Vec<VirtualPath>)> {
+        let has_rakefile = self.root.join("Rakefile").exists();
+        let rails_path = self.root.join("bin/rails");
+
+        // rails
+        if rails_path.exists() {
+            self.input_files.push(rails_path);
+
+            if self.root.join("test").exists() {
+                self.insert_task(
+                    "test",
+                    self.create_task(
+                        "Tests with Rails.".into(),
+                        &["bin/rails", "test"],
+                        &["test/**/*"],
+                    )?,
+                )?;
+            }
+
+            if self.root.join("db/migrate").exists() {
+                let mut task = self.create_task(
+                    "Runs database migrations.".into(),
+                    &["bin/rails", "db:migrate"],
+                    &[],
+                )?;
+
+                // Migrations mutate the database, so can't be cached
+                task.preset = Some(TaskPreset::Utility);
+
+                self.insert_task("db:migrate", task)?;
+            }
+
+            if self.root.join("app/assets").exists() {
+                let mut task = self.create_task(
+                    "Precompiles assets.".into(),
+                    &["bin/rails", "assets:precompile"],
+                    &[],
+                )?;
+                task.outputs = Some(vec![Output::parse("public/assets")?]);
+
+                self.insert_task("assets:precompile", task)?;
+            }
+        }
+        // minitest
+        else if has_rakefile && self.root.join("test/test_helper.rb").exists() {
+            self.insert_task(
+                "test",
+                self.create_task(
+                    "Tests with Minitest.".into(),
+                    &["bundle", "exec", "rake", "test"],
+                    &["test/**/*"],
+                )?,
+            )?;
+        }
+
+        // rspec
+        if self.root.join(".rspec").exists() || self.root.join("spec/spec_helper.rb").exists() {
+            self.insert_task(
+                "spec",
+                self.create_task(
+                    "Tests with RSpec.".into(),
+                    &["bundle", "exec", "rspec"],
+                    &["spec/**/*", ".rspec"],
+                )?,
+            )?;
+        }
+
+        // rake
+        if has_rakefile {
+            for name in self.load_rake_tasks()? {
+                let id = Id::clean(&name)?;
+
+                // The `default` task is an alias, and the tasks above
+                // are how these tools are meant to be ran
+                if name == "default" || self.tasks.contains_key(&id) {
+                    continue;
+                }
+
+                self.tasks.insert(
+                    id,
+                    self.create_task(
+                        format!("Inherited from `{name}` Rake task."),
+                        &["bundle", "exec", "rake", &name],
+                        &["Rakefile"],
+                    )?,
+                );
+            }
+        }
+
+        Ok((self.tasks, self.input_files))
+    }
+
+    /// Load tasks from the `Rakefile`, and from `lib/tasks/*.rake`,
+    /// which Rails loads automatically.
+    fn load_rake_tasks(&mut self) -> AnyResult<Vec<String>> {
+        let rakefile_path = self.root.join("Rakefile");
+        let mut tasks = parse_rake_tasks(&fs::read_file(&rakefile_path)?);
+
+        self.input_files.push(rakefile_path);
+
+        let tasks_dir = self.root.join("lib/tasks");
+
+        if tasks_dir.exists() {
+            let mut paths = fs::read_dir(&tasks_dir)?
+                .into_iter()
+                .map(|entry| tasks_dir.join(entry.file_name()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rake"))
+                .collect::<Vec<_>>();
+
+            paths.sort_by(|a, d| a.cmp(d));
+
+            for path in paths {
+                for task in parse_rake_tasks(&fs::read_file(&path)?) {
+                    if !tasks.contains(&task) {
+                        tasks.push(task);
+                    }
+                }
+
+                self.input_files.push(path);
+            }
+        }
+
+        Ok(tasks)
+    }
+
+    fn create_task(
+        &self,
+        description: String,
+        args: &[&str],
+        extra_inputs: &[&str],
+    ) -> AnyResult<PartialTaskConfig> {
+        let mut inputs = vec![];
+
+        for dir in SOURCE_DIRS {
+            if self.root.join(dir).exists() {
+                inputs.push(Input::parse(format!("{dir}/**/*"))?);
+            }
+        }
+
+        for file in ["Gemfile", "Gemfile.lock"] {
+            if self.root.join(file).exists() {
+                inputs.push(Input::parse(file)?);
+            }
+        }
+
+        for input in extra_inputs {
+            inputs.push(Input::parse(input)?);
+        }
+
+        Ok(PartialTaskConfig {
+            description: Some(description),
+            command: Some(PartialTaskArgs::List(
+                args.iter().map(|arg| arg.to_string()).collect(),
+            )),
+            inputs: Some(inputs),
+            toolchains: Some(OneOrMany::One(Id::raw("ruby"))),
+            ..Default::default()
+        })
+    }
+
+    fn insert_task(&mut self, name: &str, task: PartialTaskConfig) -> AnyResult<()> {
+        self.tasks.insert(Id::clean(name)?, task);
+
+        Ok(())
+    }
+}
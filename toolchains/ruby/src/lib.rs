@@ -1,5 +1,9 @@
 pub mod config;
 pub mod gemfile;
+pub mod gemspec;
+#[cfg(feature = "wasm")]
+mod infer_tasks;
+pub mod rakefile;
 #[cfg(feature = "wasm")]
 mod tier1;
 #[cfg(feature = "wasm")]
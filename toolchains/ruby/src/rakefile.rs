@@ -0,0 +1,161 @@
+//! Best-effort `Rakefile` task scanner.
+//!
+//! A `Rakefile` is executable Ruby script, so this only extracts literal
+//! `task` declarations (including those nested in `namespace` blocks), and the
+//! tasks defined by the built-in `Rake::TestTask` and `RSpec::Core::RakeTask`.
+
+use crate::gemfile::{after_bare_call, quoted, split_args, starts_ruby_block, strip_comment};
+
+/// Parse the fully qualified names of the tasks declared in a `Rakefile`
+/// or `.rake` file, like `db:seed`.
+pub fn parse_rake_tasks(content: &str) -> Vec<String> {
+    let mut tasks = vec![];
+    let mut namespaces: Vec<Option<String>> = vec![];
+
+    for line in content.lines() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "end" {
+            namespaces.pop();
+            continue;
+        }
+
+        let name = if let Some(args) =
+            after_bare_call(line, "task").or_else(|| after_bare_call(line, "multitask"))
+        {
+            task_name(args)
+        } else if line.contains("TestTask.new") {
+            Some(task_name_from_new(line).unwrap_or_else(|| "test".into()))
+        } else if line.contains("RakeTask.new") {
+            Some(task_name_from_new(line).unwrap_or_else(|| "spec".into()))
+        } else {
+            None
+        };
+
+        if let Some(name) = name {
+            let mut qualified = namespaces.iter().flatten().cloned().collect::<Vec<_>>();
+            qualified.push(name);
+
+            let qualified = qualified.join(":");
+
+            if !tasks.contains(&qualified) {
+                tasks.push(qualified);
+            }
+        }
+
+        // Keep blocks balanced so their `end` doesn't pop an enclosing namespace
+        if opens_block(line) {
+            namespaces.push(
+                after_bare_call(line, "namespace")
+                    .and_then(|args| task_name(args.trim_end_matches(" do"))),
+            );
+        }
+    }
+
+    tasks
+}
+
+fn opens_block(line: &str) -> bool {
+    line.ends_with(" do")
+        || (line.contains(" do |") && line.ends_with('|'))
+        || starts_ruby_block(line)
+        || ["def ", "class ", "module "]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+}
+
+/// Extract the name from `:name`, `name:`, or `"name"` as the first argument.
+fn task_name(args: &str) -> Option<String> {
+    let first = split_args(args).into_iter().next()?.trim();
+
+    if let Some(name) = quoted(first.split("=>").next().unwrap_or_default()) {
+        return Some(name.to_owned());
+    }
+
+    let name = if let Some(symbol) = first.strip_prefix(':') {
+        symbol
+    } else {
+        // `name: [:deps]` keyword style
+        first.split_once(':')?.0
+    };
+
+    let name = name
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .next()
+        .unwrap_or_default();
+
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Extract the name from `Rake::TestTask.new(:name)`.
+fn task_name_from_new(line: &str) -> Option<String> {
+    let args = line.split_once(".new(")?.1.split(')').next()?;
+
+    task_name(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_tasks_in_all_styles() {
+        let tasks = parse_rake_tasks(
+            r#"
+task :build
+task release: [:build]
+task "docs" => :build
+multitask :parallel, [:arg] => [:build] do |t, args|
+  puts args
+end
+task default: :build
+"#,
+        );
+
+        assert_eq!(tasks, ["build", "release", "docs", "parallel", "default"]);
+    }
+
+    #[test]
+    fn qualifies_namespaced_tasks() {
+        let tasks = parse_rake_tasks(
+            r#"
+namespace :db do
+  desc "Seed the database"
+  task seed: :environment do
+    if ENV["CI"]
+      puts "ci"
+    end
+  end
+
+  namespace :cache do
+    task :clear
+  end
+end
+task :after
+"#,
+        );
+
+        assert_eq!(tasks, ["db:seed", "db:cache:clear", "after"]);
+    }
+
+    #[test]
+    fn captures_built_in_task_classes() {
+        let tasks = parse_rake_tasks(
+            r#"
+require "rake/testtask"
+require "rspec/core/rake_task"
+
+Rake::TestTask.new do |t|
+  t.libs << "test"
+end
+
+RSpec::Core::RakeTask.new(:unit)
+"#,
+        );
+
+        assert_eq!(tasks, ["test", "unit"]);
+    }
+}
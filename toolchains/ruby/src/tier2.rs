@@ -1,5 +1,7 @@
 use crate::config::RubyToolchainConfig;
 use crate::gemfile;
+use crate::gemspec;
+use crate::infer_tasks::TasksInferrer;
 use extism_pdk::*;
 use moon_config::{DependencyScope, VersionSpec};
 use moon_pdk::{VirtualPathExt, locate_root_many, parse_toolchain_config_schema};
@@ -7,7 +9,8 @@ use moon_pdk_api::*;
 use rubund::parser::{SourceType, parse_lockfile};
 use starbase_utils::fs;
 use std::collections::HashMap;
-use std::path::{Component, Path, PathBuf};
+use std::path::{Path, PathBuf};
+use toolchain_common::{add_project_dependency, normalize_path};
 
 // Files that mark a Bundler dependency root, lockfiles first (most precise),
 // then manifests. Includes Bundler's alternative `gems.rb`/`gems.locked` names.
@@ -225,50 +228,127 @@ pub fn extend_project_graph(
         by_source.insert(PathBuf::from(source), id.clone());
     }
 
+    // Map each in-repo gem name to its project, so we can resolve gemspec
+    // dependencies, which are declared by name only.
+    let mut by_gem_name: HashMap<String, Id> = HashMap::new();
+    let mut gemspecs: HashMap<Id, (VirtualPath, gemspec::Gemspec)> = HashMap::new();
+
     for (id, source) in &input.project_sources {
-        let gemfile_path = input
-            .context
-            .get_project_root_from_source(source)
-            .join("Gemfile");
+        let project_root = input.context.get_project_root_from_source(source);
+
+        if let Some((gemspec_path, spec)) = load_gemspec(&project_root)? {
+            let name = spec.name.clone().or_else(|| {
+                gemspec_path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| stem.to_owned())
+            });
+
+            if let Some(name) = name {
+                by_gem_name.insert(name, id.clone());
+            }
 
-        if !gemfile_path.exists() {
-            continue;
+            gemspecs.insert(id.clone(), (gemspec_path, spec));
         }
+    }
 
-        let Ok(content) = fs::read_file(&gemfile_path) else {
-            continue;
-        };
-
+    for (id, source) in &input.project_sources {
+        let project_root = input.context.get_project_root_from_source(source);
+        let gemfile_path = project_root.join("Gemfile");
         let mut project_output = ExtendProjectOutput::default();
 
         // Internal project relationships are expressed via `path:` gems, since
         // Bundler has no workspace concept.
-        for gem in gemfile::parse_path_gems(&content) {
-            // The join still needs normalizing to fold the `../..` in the
-            // gem's relative path down to a workspace-relative source.
-            let resolved = normalize(&Path::new(source).join(&gem.path));
-
-            if let Some(dep_id) = by_source.get(&resolved)
-                && dep_id != id
-            {
-                project_output.dependencies.push(ProjectDependency {
-                    id: dep_id.clone(),
-                    scope: groups_to_scope(&gem.groups, &non_production_groups),
-                    via: Some(format!("path gem {}", gem.name)),
-                });
+        if gemfile_path.exists()
+            && let Ok(content) = fs::read_file(&gemfile_path)
+        {
+            for gem in gemfile::parse_path_gems(&content) {
+                // The join still needs normalizing to fold the `../..` in the
+                // gem's relative path down to a workspace-relative source.
+                // Bundler also finds gems in child directories of the path.
+                let gem_path = Path::new(source).join(&gem.path);
+                let dep_id = by_source
+                    .get(&normalize_path(&gem_path))
+                    .or_else(|| by_source.get(&normalize_path(&gem_path.join(&gem.name))));
+
+                if let Some(dep_id) = dep_id {
+                    add_project_dependency(
+                        &mut project_output,
+                        id,
+                        dep_id,
+                        groups_to_scope(&gem.groups, &non_production_groups),
+                        format!("path gem {}", gem.name),
+                    );
+                }
             }
+
+            output.input_files.push(gemfile_path);
         }
 
-        if !project_output.dependencies.is_empty() {
-            output.extended_projects.insert(id.clone(), project_output);
+        if let Some((gemspec_path, spec)) = gemspecs.remove(id) {
+            for dep in spec.dependencies {
+                if let Some(dep_id) = by_gem_name.get(&dep.name) {
+                    add_project_dependency(
+                        &mut project_output,
+                        id,
+                        dep_id,
+                        if dep.development {
+                            DependencyScope::Development
+                        } else {
+                            DependencyScope::Production
+                        },
+                        format!("gemspec dependency {}", dep.name),
+                    );
+                }
+            }
+
+            output.input_files.push(gemspec_path);
         }
 
-        output.input_files.push(gemfile_path);
+        if config.infer_tasks_from_tools {
+            let (tasks, input_files) = TasksInferrer::new(&project_root).infer()?;
+
+            project_output.tasks = tasks;
+            output.input_files.extend(input_files);
+        }
+
+        if !project_output.dependencies.is_empty() || !project_output.tasks.is_empty() {
+            output.extended_projects.insert(id.clone(), project_output);
+        }
     }
 
     Ok(Json(output))
 }
 
+/// Load the first `*.gemspec` in the project root, if one exists.
+fn load_gemspec(project_root: &VirtualPath) -> AnyResult<Option<(VirtualPath, gemspec::Gemspec)>> {
+    if !project_root.exists() {
+        return Ok(None);
+    }
+
+    let mut paths = fs::read_dir(project_root)?
+        .into_iter()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("gemspec"))
+        })
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    let Some(path) = paths.into_iter().next() else {
+        return Ok(None);
+    };
+
+    let spec = gemspec::parse_gemspec(&fs::read_file(&path)?);
+
+    Ok(Some((
+        project_root.join(path.file_name().unwrap_or_default()),
+        spec,
+    )))
+}
+
 fn non_production_groups(config: &RubyToolchainConfig) -> Vec<&str> {
     if config.production_without_groups.is_empty() {
         DEFAULT_NON_PRODUCTION_GROUPS.to_vec()
@@ -309,21 +389,3 @@ pub fn hash_task_contents(
 
     Ok(Json(HashTaskContentsOutput { contents }))
 }
-
-/// Resolve `.`/`..` components in a relative path without touching the
-/// filesystem, so `apps/web` + `../../libs/billing` -> `libs/billing`.
-fn normalize(path: &Path) -> PathBuf {
-    let mut out = PathBuf::new();
-
-    for comp in path.components() {
-        match comp {
-            Component::ParentDir => {
-                out.pop();
-            }
-            Component::CurDir => {}
-            other => out.push(other.as_os_str()),
-        }
-    }
-
-    out
-}
@@ -1,4 +1,6 @@
-use moon_config::DependencyScope;
+use moon_config::{
+    DependencyScope, Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, TaskPreset,
+};
 use moon_pdk_api::*;
 use moon_pdk_test_utils::create_empty_moon_sandbox;
 use serde_json::json;
@@ -528,6 +530,229 @@ end"#,
                 }]
             );
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_path_block_gem_edges() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "app/Gemfile",
+                r#"path "../engines" do
+  gem "admin"
+end"#,
+            );
+            let plugin = sandbox.create_toolchain("ruby").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input
+                .project_sources
+                .insert(Id::raw("admin"), "engines/admin".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert_eq!(
+                output.extended_projects.get("app").unwrap().dependencies,
+                vec![ProjectDependency {
+                    id: Id::raw("admin"),
+                    scope: DependencyScope::Production,
+                    via: Some("path gem admin".into()),
+                }]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_gemspec_path_edges() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file("app/Gemfile", r#"gemspec path: "../libs/core""#);
+            let plugin = sandbox.create_toolchain("ruby").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input
+                .project_sources
+                .insert(Id::raw("core"), "libs/core".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert_eq!(
+                output.extended_projects.get("app").unwrap().dependencies,
+                vec![ProjectDependency {
+                    id: Id::raw("core"),
+                    scope: DependencyScope::Production,
+                    via: Some("path gem core".into()),
+                }]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_gemspec_dependency_edges() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "libs/billing/billing.gemspec",
+                r#"Gem::Specification.new do |spec|
+  spec.name = "billing"
+  spec.add_dependency "rails"
+  spec.add_dependency "billing-core"
+  spec.add_development_dependency "fixtures"
+end"#,
+            );
+            sandbox.create_file(
+                "libs/core/core.gemspec",
+                r#"Gem::Specification.new do |spec|
+  spec.name = "billing-core"
+end"#,
+            );
+            sandbox.create_file(
+                "libs/fixtures/fixtures.gemspec",
+                "Gem::Specification.new do |spec|\nend",
+            );
+            let plugin = sandbox.create_toolchain("ruby").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input
+                .project_sources
+                .insert(Id::raw("billing"), "libs/billing".into());
+            input
+                .project_sources
+                .insert(Id::raw("core"), "libs/core".into());
+            input
+                .project_sources
+                .insert(Id::raw("fixtures"), "libs/fixtures".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert_eq!(
+                output
+                    .extended_projects
+                    .get("billing")
+                    .unwrap()
+                    .dependencies,
+                vec![
+                    ProjectDependency {
+                        id: Id::raw("core"),
+                        scope: DependencyScope::Production,
+                        via: Some("gemspec dependency billing-core".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("fixtures"),
+                        scope: DependencyScope::Development,
+                        via: Some("gemspec dependency fixtures".into()),
+                    }
+                ]
+            );
+            assert!(
+                output
+                    .input_files
+                    .contains(&VirtualPath::new("/workspace/libs/billing/billing.gemspec"))
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_tasks_when_disabled() {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file("app/Gemfile", "");
+            sandbox.create_file("app/Rakefile", "task :build");
+            let plugin = sandbox.create_toolchain("ruby").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert!(output.extended_projects.is_empty());
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_rails_tasks() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file("app/Gemfile", "");
+            sandbox.create_file("app/app/assets/app.css", "");
+            sandbox.create_file("app/bin/rails", "");
+            sandbox.create_file("app/db/migrate/001_init.rb", "");
+            sandbox.create_file("app/test/test_helper.rb", "");
+            sandbox.create_file("app/.rspec", "");
+            sandbox.create_file(
+                "app/Rakefile",
+                r#"require_relative "config/application"
+Rails.application.load_tasks"#,
+            );
+            sandbox.create_file(
+                "app/lib/tasks/cache.rake",
+                r#"namespace :cache do
+  task warm: :environment do
+  end
+end"#,
+            );
+            let plugin = sandbox.create_toolchain("ruby").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({ "inferTasksFromTools": true });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                [
+                    "assets-precompile",
+                    "cache-warm",
+                    "db-migrate",
+                    "spec",
+                    "test"
+                ]
+            );
+
+            assert_eq!(
+                tasks.get("test").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Tests with Rails.".into()),
+                    command: Some(PartialTaskArgs::List(vec![
+                        "bin/rails".into(),
+                        "test".into()
+                    ])),
+                    inputs: Some(vec![
+                        Input::parse("app/**/*").unwrap(),
+                        Input::parse("db/**/*").unwrap(),
+                        Input::parse("lib/**/*").unwrap(),
+                        Input::parse("Gemfile").unwrap(),
+                        Input::parse("test/**/*").unwrap(),
+                    ]),
+                    toolchains: Some(OneOrMany::One(Id::raw("ruby"))),
+                    ..Default::default()
+                }
+            );
+
+            assert_eq!(
+                tasks.get("db-migrate").unwrap().preset,
+                Some(TaskPreset::Utility)
+            );
+            assert_eq!(
+                tasks.get("assets-precompile").unwrap().outputs,
+                Some(vec![Output::parse("public/assets").unwrap()])
+            );
+            assert_eq!(
+                tasks.get("cache-warm").unwrap().command,
+                Some(PartialTaskArgs::List(vec![
+                    "bundle".into(),
+                    "exec".into(),
+                    "rake".into(),
+                    "cache:warm".into()
+                ]))
+            );
+
+            assert_eq!(
+                output.input_files,
+                [
+                    VirtualPath::new("/workspace/app/Gemfile"),
+                    VirtualPath::new("/workspace/app/bin/rails"),
+                    VirtualPath::new("/workspace/app/Rakefile"),
+                    VirtualPath::new("/workspace/app/lib/tasks/cache.rake"),
+                ]
+            );
+        }
     }
 
     mod hash_task_contents {
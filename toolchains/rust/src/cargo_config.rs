@@ -0,0 +1,43 @@
+// `.cargo/config.toml`
+
+use moon_pdk_api::AnyResult;
+use serde::Deserialize;
+use starbase_utils::toml::{self, TomlValue};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CargoAlias {
+    List(Vec<String>),
+    String(String),
+}
+
+impl CargoAlias {
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::List(list) => list.to_owned(),
+            Self::String(value) => value.split_whitespace().map(|arg| arg.to_owned()).collect(),
+        }
+    }
+}
+
+// https://doc.rust-lang.org/cargo/reference/config.html
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CargoConfig {
+    pub alias: BTreeMap<String, CargoAlias>,
+    pub build: Option<TomlValue>,
+    pub profile: Option<TomlValue>,
+    pub target: Option<TomlValue>,
+}
+
+impl CargoConfig {
+    pub fn load<T: AsRef<Path>>(path: T) -> AnyResult<Self> {
+        Ok(toml::read_file(path.as_ref())?)
+    }
+
+    pub fn get_target_dir(&self) -> Option<&str> {
+        self.build.as_ref()?.get("target-dir")?.as_str()
+    }
+}
@@ -23,6 +23,12 @@ config_struct!(
         /// List of Rust components to automatically install with `rustup`.
         pub components: Vec<String>,
 
+        /// Automatically infer moon tasks from each crate. Will infer `build`,
+        /// `check`, `clippy`, and `test` tasks, a `run-<bin>` task for each binary,
+        /// a `bench-<name>` task for each benchmark, an `example-<name>` task for
+        /// each example, and a task for each `[alias]` in `.cargo/config.toml`.
+        pub infer_tasks_from_crates: bool,
+
         /// When `version` is defined, syncs the version to `rust-toolchain.toml`
         /// under the `toolchain.channel` field.
         pub sync_toolchain_config: bool,
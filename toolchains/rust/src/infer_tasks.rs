@@ -0,0 +1,314 @@
+use crate::cargo_config::CargoAlias;
+use crate::cargo_toml::CargoToml;
+use cargo_toml::{OptionalFile, Product};
+use moon_config::{Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, TaskPreset};
+use moon_pdk_api::{AnyResult, Id, VirtualPath};
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+
+/// A binary, bench, or example target of a crate.
+#[derive(Debug, PartialEq)]
+pub struct CrateTarget {
+    pub name: String,
+    pub required_features: Vec<String>,
+}
+
+pub struct TasksInferrer<'a> {
+    manifest: &'a CargoToml,
+    root: &'a VirtualPath,
+    target_dir: Option<String>,
+    tasks: BTreeMap<Id, PartialTaskConfig>,
+}
+
+impl<'a> TasksInferrer<'a> {
+    pub fn new(
+        manifest: &'a CargoToml,
+        root: &'a VirtualPath,
+        target_dir: Option<String>,
+    ) -> TasksInferrer<'a> {
+        Self {
+            manifest,
+            root,
+            target_dir,
+            tasks: BTreeMap::default(),
+        }
+    }
+
+    pub fn infer(
+        mut self,
+        aliases: &BTreeMap<String, CargoAlias>,
+    ) -> AnyResult<BTreeMap<Id, PartialTaskConfig>> {
+        let Some(package) = &self.manifest.package else {
+            return Ok(self.tasks);
+        };
+
+        let crate_name = package.name();
+        let bins = self.discover_targets(&self.manifest.bin, "bin", package.autobins)?;
+
+        // build
+        let mut task = self.create_task(format!("Builds the `{crate_name}` crate."), ["build"])?;
+
+        // Only when the target directory is within the workspace
+        if let Some(target_dir) = &self.target_dir {
+            for bin in &bins {
+                let outputs = task.outputs.get_or_insert_default();
+                outputs.push(Output::parse(format!("/{target_dir}/debug/{}", bin.name))?);
+                outputs.push(Output::parse(format!(
+                    "/{target_dir}/debug/{}.exe",
+                    bin.name
+                ))?);
+            }
+        }
+
+        self.insert_task("build", task)?;
+
+        // check
+        self.insert_task(
+            "check",
+            self.create_task(format!("Checks the `{crate_name}` crate."), ["check"])?,
+        )?;
+
+        // clippy
+        self.insert_task(
+            "clippy",
+            self.create_task(format!("Lints the `{crate_name}` crate."), ["clippy"])?,
+        )?;
+
+        // test
+        let mut task = self.create_task(format!("Tests the `{crate_name}` crate."), ["test"])?;
+        self.add_inputs(&mut task, ["tests/**/*"])?;
+
+        self.insert_task("test", task)?;
+
+        // bins
+        for bin in bins {
+            let mut task = self.create_task(
+                format!("Runs the `{}` binary.", bin.name),
+                ["run", "--bin", &bin.name],
+            )?;
+            self.add_features(&mut task, &bin);
+
+            self.insert_task(&format!("run-{}", bin.name), task)?;
+        }
+
+        // benches
+        for bench in self.discover_targets(&self.manifest.bench, "benches", package.autobenches)? {
+            let mut task = self.create_task(
+                format!("Runs the `{}` benchmark.", bench.name),
+                ["bench", "--bench", &bench.name],
+            )?;
+            self.add_inputs(&mut task, ["benches/**/*"])?;
+            self.add_features(&mut task, &bench);
+
+            self.insert_task(&format!("bench-{}", bench.name), task)?;
+        }
+
+        // examples
+        for example in
+            self.discover_targets(&self.manifest.example, "examples", package.autoexamples)?
+        {
+            let mut task = self.create_task(
+                format!("Runs the `{}` example.", example.name),
+                ["run", "--example", &example.name],
+            )?;
+            self.add_inputs(&mut task, ["examples/**/*"])?;
+            self.add_features(&mut task, &example);
+
+            self.insert_task(&format!("example-{}", example.name), task)?;
+        }
+
+        // aliases
+        for (name, alias) in aliases {
+            let id = Id::clean(name)?;
+
+            // Built-in commands can't be overridden by aliases
+            if self.tasks.contains_key(&id) {
+                continue;
+            }
+
+            self.tasks.insert(
+                id,
+                self.create_task(
+                    format!(
+                        "Inherited from `{name}` Cargo alias (`cargo {}`).",
+                        alias.to_args().join(" ")
+                    ),
+                    [name.as_str()],
+                )?,
+            );
+        }
+
+        Ok(self.tasks)
+    }
+
+    /// Discover targets of a kind from the manifest, and the file system
+    /// when auto-discovery is enabled, following Cargo's target layout:
+    /// https://doc.rust-lang.org/cargo/guide/project-layout.html
+    pub fn discover_targets(
+        &self,
+        products: &[Product],
+        dir_name: &str,
+        auto_discover: bool,
+    ) -> AnyResult<Vec<CrateTarget>> {
+        let mut targets = vec![];
+        let mut paths = vec![];
+        let package_name = self
+            .manifest
+            .package
+            .as_ref()
+            .map(|package| package.name())
+            .unwrap_or_default();
+
+        for product in products {
+            let Some(name) = product.name.as_deref().or(if dir_name == "bin" {
+                Some(package_name)
+            } else {
+                None
+            }) else {
+                continue;
+            };
+
+            if let Some(path) = &product.path {
+                paths.push(path.trim_start_matches("./").to_owned());
+            }
+
+            targets.push(CrateTarget {
+                name: name.to_owned(),
+                required_features: product.required_features.clone(),
+            });
+        }
+
+        if !auto_discover {
+            return Ok(targets);
+        }
+
+        let mut discovered = vec![];
+
+        // The main binary, `src/main.rs`
+        if dir_name == "bin" && self.root.join("src/main.rs").exists() {
+            discovered.push((package_name.to_owned(), "src/main.rs".to_owned()));
+        }
+
+        let dir_path = if dir_name == "bin" {
+            "src/bin".to_owned()
+        } else {
+            dir_name.to_owned()
+        };
+        let dir = self.root.join(&dir_path);
+
+        if dir.exists() {
+            let mut entries = fs::read_dir(&dir)?
+                .into_iter()
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let file_name = path.file_name()?.to_str()?.to_owned();
+
+                    if path.is_dir() {
+                        // Multi-file targets, `<dir>/<name>/main.rs`
+                        if path.join("main.rs").exists() {
+                            return Some((
+                                file_name.clone(),
+                                format!("{dir_path}/{file_name}/main.rs"),
+                            ));
+                        }
+                    } else if let Some(stem) = file_name.strip_suffix(".rs") {
+                        return Some((stem.to_owned(), format!("{dir_path}/{file_name}")));
+                    }
+
+                    None
+                })
+                .collect::<Vec<_>>();
+
+            entries.sort();
+            discovered.extend(entries);
+        }
+
+        for (name, path) in discovered {
+            if targets.iter().any(|target| target.name == name) || paths.contains(&path) {
+                continue;
+            }
+
+            targets.push(CrateTarget {
+                name,
+                required_features: vec![],
+            });
+        }
+
+        Ok(targets)
+    }
+
+    fn add_features(&self, task: &mut PartialTaskConfig, target: &CrateTarget) {
+        if target.required_features.is_empty() {
+            return;
+        }
+
+        if let Some(PartialTaskArgs::List(args)) = &mut task.command {
+            args.push("--features".into());
+            args.push(target.required_features.join(","));
+        }
+    }
+
+    fn add_inputs<const N: usize>(
+        &self,
+        task: &mut PartialTaskConfig,
+        inputs: [&str; N],
+    ) -> AnyResult<()> {
+        let list = task.inputs.get_or_insert_default();
+
+        for input in inputs {
+            list.push(Input::parse(input)?);
+        }
+
+        Ok(())
+    }
+
+    fn create_task<const N: usize>(
+        &self,
+        description: String,
+        args: [&str; N],
+    ) -> AnyResult<PartialTaskConfig> {
+        let mut command = vec!["cargo".to_owned()];
+        command.extend(args.into_iter().map(|arg| arg.to_owned()));
+
+        let mut inputs = vec![Input::parse("src/**/*")?, Input::parse("Cargo.toml")?];
+
+        if let Some(build_script) = self.get_build_script() {
+            inputs.push(Input::parse(build_script)?);
+        }
+
+        Ok(PartialTaskConfig {
+            description: Some(description),
+            command: Some(PartialTaskArgs::List(command)),
+            inputs: Some(inputs),
+            toolchains: Some(OneOrMany::One(Id::raw("rust"))),
+            ..Default::default()
+        })
+    }
+
+    fn insert_task(&mut self, name: &str, mut task: PartialTaskConfig) -> AnyResult<()> {
+        let id = Id::clean(name)?;
+
+        // Running targets and benchmarks should not be cached
+        if let Some(PartialTaskArgs::List(args)) = &task.command
+            && matches!(args.get(1).map(|arg| arg.as_str()), Some("run" | "bench"))
+        {
+            task.preset = Some(TaskPreset::Utility);
+        }
+
+        self.tasks.insert(id, task);
+
+        Ok(())
+    }
+
+    fn get_build_script(&self) -> Option<String> {
+        match self.manifest.package.as_ref()?.build.as_ref() {
+            Some(OptionalFile::Path(path)) => Some(path.to_string_lossy().replace('\\', "/")),
+            Some(OptionalFile::Flag(false)) => None,
+            _ => self
+                .root
+                .join("build.rs")
+                .exists()
+                .then(|| "build.rs".to_owned()),
+        }
+    }
+}
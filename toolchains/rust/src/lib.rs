@@ -1,8 +1,11 @@
+pub mod cargo_config;
 pub mod cargo_metadata;
 pub mod cargo_toml;
 pub mod config;
 pub mod toolchain_toml;
 
+#[cfg(feature = "wasm")]
+mod infer_tasks;
 #[cfg(feature = "wasm")]
 mod tier1;
 #[cfg(feature = "wasm")]
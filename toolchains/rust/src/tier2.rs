@@ -1,11 +1,18 @@
+use crate::cargo_config::{CargoAlias, CargoConfig};
 use crate::cargo_toml::CargoToml;
+use crate::config::RustToolchainConfig;
+use crate::infer_tasks::TasksInferrer;
+use crate::toolchain_toml::ToolchainToml;
 use cargo_toml::{Dependency, DepsSet, Publish};
 use extism_pdk::*;
 use moon_config::DependencyScope;
 use moon_pdk::{
-    VirtualPathExt, get_host_env_var, get_host_environment, locate_root, locate_root_with_check,
+    RealPathExt, VirtualPathExt, get_host_env_var, get_host_environment, locate_root,
+    locate_root_with_check, parse_toolchain_config_schema,
 };
 use moon_pdk_api::*;
+use starbase_utils::path;
+use starbase_utils::toml::{self, TomlValue};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
@@ -13,46 +20,78 @@ use std::path::PathBuf;
 pub fn extend_project_graph(
     Json(input): Json<ExtendProjectGraphInput>,
 ) -> FnResult<Json<ExtendProjectGraphOutput>> {
+    let config = parse_toolchain_config_schema::<RustToolchainConfig>(input.toolchain_config)?;
     let mut output = ExtendProjectGraphOutput::default();
 
     // First pass, gather all packages and their manifests
     let mut packages = BTreeMap::default();
 
     for (id, source) in input.project_sources {
-        let cargo_toml_path = input.context.workspace_root.join(source).join("Cargo.toml");
+        let project_root = input.context.workspace_root.join(source);
+        let cargo_toml_path = project_root.join("Cargo.toml");
 
         if cargo_toml_path.exists() {
             let manifest = CargoToml::load(cargo_toml_path.clone())?;
 
             if let Some(package) = &manifest.package {
-                packages.insert(package.name().to_owned(), (id, manifest));
+                packages.insert(package.name().to_owned(), (id, manifest, project_root));
             }
         }
     }
 
     // Second pass, extract packages and their relationships
-    for (id, manifest) in packages.values() {
+    for (id, manifest, project_root) in packages.values() {
         let mut project_output = ExtendProjectOutput::default();
 
+        // Inherited dependencies are defined in the workspace manifest
+        let workspace_manifest = if has_inherited_deps(manifest) {
+            load_workspace_manifest(project_root)?
+        } else {
+            None
+        };
+        let workspace_deps = workspace_manifest
+            .as_ref()
+            .and_then(|manifest| manifest.workspace.as_ref())
+            .map(|workspace| &workspace.dependencies);
+
         let mut extract_implicit_deps =
             |package_deps: &DepsSet, scope: DependencyScope| -> AnyResult<()> {
-                for (dep_name, dep) in package_deps {
-                    let Some((dep_id, _)) = packages.get(dep_name) else {
+                for (dep_key, dep) in package_deps {
+                    let detail = match dep {
+                        Dependency::Inherited(_) => workspace_deps
+                            .and_then(|deps| deps.get(dep_key))
+                            .and_then(|dep| dep.detail()),
+                        _ => dep.detail(),
+                    };
+
+                    // Only inherit if the dependency is using the local `path = "..."` syntax
+                    let Some(detail) = detail.filter(|det| det.path.is_some()) else {
                         continue;
                     };
 
-                    // Only inherit if the dependency is using the local `path = "..."` syntax,
-                    // is not a self-dependency and the package name exists in our gathered map
-                    if dep
-                        .detail()
-                        .is_some_and(|det| det.path.as_ref().is_some_and(|path| path != "."))
+                    // Renamed dependencies reference the actual package name
+                    let dep_name = detail.package.as_deref().unwrap_or(dep_key);
+
+                    // And the package name exists in our gathered map,
+                    // and is not a self-dependency
+                    let Some((dep_id, _, _)) = packages.get(dep_name) else {
+                        continue;
+                    };
+
+                    if dep_id == id
+                        || project_output
+                            .dependencies
+                            .iter()
+                            .any(|pd| &pd.id == dep_id && pd.scope == scope)
                     {
-                        project_output.dependencies.push(ProjectDependency {
-                            id: dep_id.to_owned(),
-                            scope,
-                            via: Some(format!("crate {dep_name}")),
-                        });
+                        continue;
                     }
+
+                    project_output.dependencies.push(ProjectDependency {
+                        id: dep_id.to_owned(),
+                        scope,
+                        via: Some(format!("crate {dep_name}")),
+                    });
                 }
 
                 Ok(())
@@ -65,17 +104,181 @@ pub fn extend_project_graph(
             extract_implicit_deps(&manifest.dev_dependencies, DependencyScope::Development)?;
             extract_implicit_deps(&manifest.build_dependencies, DependencyScope::Build)?;
 
+            // Platform specific dependencies: `[target.'cfg(unix)'.dependencies]`
+            for target in manifest.target.values() {
+                extract_implicit_deps(&target.dependencies, DependencyScope::Production)?;
+                extract_implicit_deps(&target.dev_dependencies, DependencyScope::Development)?;
+                extract_implicit_deps(&target.build_dependencies, DependencyScope::Build)?;
+            }
+
+            if let Some(workspace_manifest) = workspace_manifest
+                && !output.input_files.contains(&workspace_manifest.path)
+            {
+                output.input_files.push(workspace_manifest.path.clone());
+            }
+
+            if config.infer_tasks_from_crates {
+                let configs = load_cargo_configs(project_root, &input.context.workspace_root)?;
+                let aliases = load_cargo_aliases(&configs, &mut output.input_files);
+                let target_dir =
+                    resolve_target_dir(project_root, &input.context.workspace_root, &configs)?;
+
+                project_output.tasks =
+                    TasksInferrer::new(manifest, project_root, target_dir).infer(&aliases)?;
+            }
+
             output
                 .extended_projects
                 .insert(id.to_owned(), project_output);
 
-            output.input_files.push(manifest.path.clone());
+            if !output.input_files.contains(&manifest.path) {
+                output.input_files.push(manifest.path.clone());
+            }
         }
     }
 
     Ok(Json(output))
 }
 
+fn has_inherited_deps(manifest: &CargoToml) -> bool {
+    let is_inherited = |deps: &DepsSet| {
+        deps.values()
+            .any(|dep| matches!(dep, Dependency::Inherited(_)))
+    };
+
+    is_inherited(&manifest.dependencies)
+        || is_inherited(&manifest.dev_dependencies)
+        || is_inherited(&manifest.build_dependencies)
+        || manifest.target.values().any(|target| {
+            is_inherited(&target.dependencies)
+                || is_inherited(&target.dev_dependencies)
+                || is_inherited(&target.build_dependencies)
+        })
+}
+
+fn load_workspace_manifest(project_root: &VirtualPath) -> AnyResult<Option<CargoToml>> {
+    let mut workspace_manifest = None;
+
+    locate_root_with_check(project_root, "Cargo.toml", |root| {
+        let manifest = CargoToml::load(root.join("Cargo.toml"))?;
+
+        if manifest.workspace.is_some() {
+            workspace_manifest = Some(manifest);
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    })?;
+
+    Ok(workspace_manifest)
+}
+
+// Cargo searches for config files in the current directory and all of
+// its parents, with configs closer to the current directory taking precedence
+fn load_cargo_configs(
+    project_root: &VirtualPath,
+    workspace_root: &VirtualPath,
+) -> AnyResult<Vec<(VirtualPath, CargoConfig)>> {
+    let mut configs = vec![];
+    let mut current_dir = Some(project_root.to_owned());
+
+    while let Some(dir) = current_dir {
+        for file_name in [".cargo/config.toml", ".cargo/config"] {
+            let config_path = dir.join(file_name);
+
+            if config_path.exists() {
+                configs.push((config_path.clone(), CargoConfig::load(&config_path)?));
+                break;
+            }
+        }
+
+        if &dir == workspace_root {
+            break;
+        }
+
+        current_dir = dir.parent();
+    }
+
+    Ok(configs)
+}
+
+fn load_cargo_aliases(
+    configs: &[(VirtualPath, CargoConfig)],
+    input_files: &mut Vec<VirtualPath>,
+) -> BTreeMap<String, CargoAlias> {
+    let mut aliases = BTreeMap::default();
+
+    for (config_path, config) in configs {
+        for (name, alias) in &config.alias {
+            aliases
+                .entry(name.to_owned())
+                .or_insert_with(|| alias.to_owned());
+        }
+
+        if !input_files.contains(config_path) {
+            input_files.push(config_path.to_owned());
+        }
+    }
+
+    aliases
+}
+
+// Cargo writes artifacts to `CARGO_TARGET_DIR`, `build.target-dir`, or the
+// `target` directory in the Cargo workspace root, in that order. Returns the
+// directory relative to the moon workspace root, or nothing if it's outside of it.
+fn resolve_target_dir(
+    project_root: &VirtualPath,
+    workspace_root: &VirtualPath,
+    configs: &[(VirtualPath, CargoConfig)],
+) -> AnyResult<Option<String>> {
+    let env_value = match get_host_env_var("CARGO_TARGET_DIR")? {
+        Some(value) => Some(value),
+        None => get_host_env_var("CARGO_BUILD_TARGET_DIR")?,
+    };
+
+    let target_dir = if let Some(value) = env_value {
+        // Relative paths are relative to the current directory,
+        // which is the project root when running tasks
+        if PathBuf::from(&value).is_absolute() {
+            RealPath::new(value).to_virtual_path()?
+        } else {
+            Some(project_root.join(value))
+        }
+    } else if let Some((config_path, value)) = configs
+        .iter()
+        .find_map(|(path, config)| config.get_target_dir().map(|value| (path, value)))
+    {
+        // Relative paths are relative to the parent of the `.cargo` directory
+        if PathBuf::from(value).is_absolute() {
+            RealPath::new(value).to_virtual_path()?
+        } else {
+            config_path
+                .parent()
+                .and_then(|dir| dir.parent())
+                .map(|dir| dir.join(value))
+        }
+    } else {
+        Some(
+            load_workspace_manifest(project_root)?
+                .and_then(|manifest| manifest.path.parent())
+                .unwrap_or_else(|| project_root.to_owned())
+                .join("target"),
+        )
+    };
+
+    let Some(target_dir) = target_dir else {
+        return Ok(None);
+    };
+
+    Ok(path::clean(&*target_dir)
+        .strip_prefix(&**workspace_root)
+        .ok()
+        .and_then(|dir| dir.to_str())
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| dir.replace('\\', "/")))
+}
+
 fn gather_shared_paths(
     env: &HostEnvironment,
     globals_dir: Option<&VirtualPath>,
@@ -336,7 +539,7 @@ pub fn parse_manifest(
 
 #[plugin_fn]
 pub fn hash_task_contents(
-    Json(_): Json<HashTaskContentsInput>,
+    Json(input): Json<HashTaskContentsInput>,
 ) -> FnResult<Json<HashTaskContentsOutput>> {
     let env = get_host_environment()?;
 
@@ -345,6 +548,68 @@ pub fn hash_task_contents(
     map.insert("arch".into(), json::Value::String(env.arch.to_string()));
     map.insert("libc".into(), json::Value::String(env.libc.to_string()));
 
+    // Only tasks that run Cargo are affected by its settings
+    if input.task.toolchains.iter().any(|id| id == "rust") {
+        let config = parse_toolchain_config_schema::<RustToolchainConfig>(input.toolchain_config)?;
+        let project_root = input
+            .context
+            .get_project_root_from_source(&input.project.source);
+
+        // Flags passed to the compiler
+        for name in [
+            "CARGO_BUILD_RUSTFLAGS",
+            "CARGO_BUILD_TARGET",
+            "CARGO_ENCODED_RUSTFLAGS",
+            "RUSTFLAGS",
+        ] {
+            if let Some(value) = get_host_env_var(name)? {
+                map.insert(name.into(), json::Value::String(value));
+            }
+        }
+
+        // Profiles are only read from the workspace root manifest
+        let manifest_path = load_workspace_manifest(&project_root)?
+            .map(|manifest| manifest.path.clone())
+            .unwrap_or_else(|| project_root.join("Cargo.toml"));
+
+        if manifest_path.exists() {
+            let manifest: TomlValue = toml::read_file(&manifest_path)?;
+
+            if let Some(profile) = manifest.get("profile") {
+                map.insert("profile".into(), json::to_value(profile)?);
+            }
+        }
+
+        // Settings from each config, with the closest first
+        let mut configs = vec![];
+
+        for (_, cargo_config) in load_cargo_configs(&project_root, &input.context.workspace_root)? {
+            configs.push(json::json!({
+                "build": cargo_config.build,
+                "profile": cargo_config.profile,
+                "target": cargo_config.target,
+            }));
+        }
+
+        if !configs.is_empty() {
+            map.insert("configs".into(), json::Value::Array(configs));
+        }
+
+        // The channel from `rust-toolchain.toml` takes precedence
+        let channel = match locate_root(&project_root, "rust-toolchain.toml") {
+            Some(root) => ToolchainToml::load(root.join("rust-toolchain.toml"))?
+                .toolchain
+                .channel
+                .clone(),
+            None => None,
+        }
+        .or_else(|| config.version.map(|version| version.to_string()));
+
+        if let Some(channel) = channel {
+            map.insert("channel".into(), json::Value::String(channel));
+        }
+    }
+
     Ok(Json(HashTaskContentsOutput {
         contents: vec![json::Value::Object(map)],
     }))
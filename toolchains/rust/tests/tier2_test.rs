@@ -1,7 +1,10 @@
 use moon_common::Id;
-use moon_config::DependencyScope;
+use moon_config::{
+    DependencyScope, Input, OneOrMany, Output, PartialTaskArgs, PartialTaskConfig, TaskPreset,
+};
 use moon_pdk_api::*;
-use moon_pdk_test_utils::{create_empty_moon_sandbox, create_moon_sandbox};
+use moon_pdk_test_utils::{MoonWasmSandbox, create_empty_moon_sandbox, create_moon_sandbox};
+use serde_json::json;
 use std::collections::BTreeMap;
 use std::env;
 
@@ -65,6 +68,7 @@ mod rust_toolchain_tier2 {
             assert_eq!(
                 output.input_files,
                 [
+                    VirtualPath::new("/workspace/Cargo.toml"),
                     VirtualPath::new("/workspace/a/Cargo.toml"),
                     VirtualPath::new("/workspace/b/Cargo.toml"),
                     VirtualPath::new("/workspace/c/Cargo.toml"),
@@ -95,7 +99,10 @@ mod rust_toolchain_tier2 {
 
             assert_eq!(
                 output.input_files,
-                [VirtualPath::new("/workspace/a/Cargo.toml")]
+                [
+                    VirtualPath::new("/workspace/Cargo.toml"),
+                    VirtualPath::new("/workspace/a/Cargo.toml")
+                ]
             );
         }
 
@@ -114,6 +121,213 @@ mod rust_toolchain_tier2 {
             assert!(output.extended_projects.is_empty());
             assert!(output.input_files.is_empty());
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn resolves_target_renamed_and_workspace_deps() {
+            let sandbox = create_moon_sandbox("deps");
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.project_sources.insert(Id::raw("core"), "core".into());
+            input
+                .project_sources
+                .insert(Id::raw("shared"), "shared".into());
+            input.project_sources.insert(Id::raw("unix"), "unix".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert_eq!(
+                output.extended_projects.get("app").unwrap().dependencies,
+                [
+                    ProjectDependency {
+                        id: Id::raw("core"),
+                        scope: DependencyScope::Production,
+                        via: Some("crate core-lib".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("shared"),
+                        scope: DependencyScope::Production,
+                        via: Some("crate shared".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("unix"),
+                        scope: DependencyScope::Production,
+                        via: Some("crate unix".into()),
+                    },
+                    ProjectDependency {
+                        id: Id::raw("shared"),
+                        scope: DependencyScope::Development,
+                        via: Some("crate shared".into()),
+                    },
+                ]
+            );
+
+            assert!(
+                output
+                    .input_files
+                    .contains(&VirtualPath::new("/workspace/Cargo.toml"))
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_tasks_when_disabled() {
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+
+            let output = plugin.extend_project_graph(input).await;
+
+            assert!(
+                output
+                    .extended_projects
+                    .get("app")
+                    .unwrap()
+                    .tasks
+                    .is_empty()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_tasks_from_crates_when_enabled() {
+            use starbase_sandbox::pretty_assertions::assert_eq;
+
+            let sandbox = create_moon_sandbox("infer-tasks");
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.project_sources.insert(Id::raw("lib"), "lib".into());
+            input.toolchain_config = json!({
+                "inferTasksFromCrates": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                [
+                    "bench-speed",
+                    "build",
+                    "check",
+                    "clippy",
+                    "example-demo",
+                    "example-other",
+                    "run-app",
+                    "run-tool",
+                    "test",
+                    "xtask",
+                ]
+            );
+
+            assert_eq!(
+                tasks.get("build").unwrap(),
+                &PartialTaskConfig {
+                    description: Some("Builds the `app` crate.".into()),
+                    command: Some(PartialTaskArgs::List(vec!["cargo".into(), "build".into()])),
+                    inputs: Some(vec![
+                        Input::parse("src/**/*").unwrap(),
+                        Input::parse("Cargo.toml").unwrap(),
+                        Input::parse("build.rs").unwrap(),
+                    ]),
+                    outputs: Some(vec![
+                        Output::parse("/target/debug/app").unwrap(),
+                        Output::parse("/target/debug/app.exe").unwrap(),
+                        Output::parse("/target/debug/tool").unwrap(),
+                        Output::parse("/target/debug/tool.exe").unwrap(),
+                    ]),
+                    toolchains: Some(OneOrMany::One(Id::raw("rust"))),
+                    ..Default::default()
+                }
+            );
+
+            let example = tasks.get("example-demo").unwrap();
+
+            assert_eq!(
+                example.command,
+                Some(PartialTaskArgs::List(vec![
+                    "cargo".into(),
+                    "run".into(),
+                    "--example".into(),
+                    "demo".into(),
+                    "--features".into(),
+                    "cli".into(),
+                ]))
+            );
+            assert_eq!(example.preset, Some(TaskPreset::Utility));
+
+            assert_eq!(
+                tasks.get("xtask").unwrap().command,
+                Some(PartialTaskArgs::List(vec!["cargo".into(), "xtask".into()]))
+            );
+
+            // Libraries don't have outputs or binaries
+            let tasks = &output.extended_projects.get("lib").unwrap().tasks;
+
+            assert_eq!(
+                tasks.keys().collect::<Vec<_>>(),
+                ["build", "check", "clippy", "test", "xtask"]
+            );
+            assert_eq!(tasks.get("build").unwrap().outputs, None);
+
+            assert!(
+                output
+                    .input_files
+                    .contains(&VirtualPath::new("/workspace/.cargo/config.toml"))
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn infers_outputs_from_configured_target_dir() {
+            let sandbox = create_moon_sandbox("infer-tasks");
+            sandbox.create_file("app/.cargo/config.toml", "[build]\ntarget-dir = \"../out\"");
+
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromCrates": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(
+                tasks.get("build").unwrap().outputs,
+                Some(vec![
+                    Output::parse("/out/debug/app").unwrap(),
+                    Output::parse("/out/debug/app.exe").unwrap(),
+                    Output::parse("/out/debug/tool").unwrap(),
+                    Output::parse("/out/debug/tool.exe").unwrap(),
+                ])
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_infer_outputs_if_target_dir_outside_workspace() {
+            let sandbox = create_moon_sandbox("infer-tasks");
+            sandbox.create_file(
+                "app/.cargo/config.toml",
+                "[build]\ntarget-dir = \"../../out\"",
+            );
+
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut input = ExtendProjectGraphInput::default();
+            input.project_sources.insert(Id::raw("app"), "app".into());
+            input.toolchain_config = json!({
+                "inferTasksFromCrates": true
+            });
+
+            let output = plugin.extend_project_graph(input).await;
+            let tasks = &output.extended_projects.get("app").unwrap().tasks;
+
+            assert_eq!(tasks.get("build").unwrap().outputs, None);
+        }
     }
 
     mod extend_task_command {
@@ -575,4 +789,102 @@ mod rust_toolchain_tier2 {
             );
         }
     }
+
+    mod hash_task_contents {
+        use super::*;
+
+        fn create_rust_sandbox() -> MoonWasmSandbox {
+            let sandbox = create_empty_moon_sandbox();
+            sandbox.create_file(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"project\"]\n\n[profile.release]\nlto = true\n",
+            );
+            sandbox.create_file(
+                ".cargo/config.toml",
+                "[alias]\nb = \"build\"\n\n[build]\nrustflags = [\"-Dwarnings\"]\n",
+            );
+            sandbox.create_file("rust-toolchain.toml", "[toolchain]\nchannel = \"1.89.0\"\n");
+            sandbox.create_file(
+                "project/Cargo.toml",
+                "[package]\nname = \"project\"\nversion = \"1.0.0\"\n",
+            );
+            sandbox
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn only_includes_platform_for_other_tasks() {
+            let sandbox = create_rust_sandbox();
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let output = plugin
+                .hash_task_contents(HashTaskContentsInput::default())
+                .await;
+
+            let contents = output.contents[0].as_object().unwrap();
+
+            assert_eq!(contents.keys().collect::<Vec<_>>(), ["os", "arch", "libc"]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn includes_cargo_settings_for_rust_tasks() {
+            let sandbox = create_rust_sandbox();
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut task = plugin.create_task_fragment();
+            task.toolchains = vec![Id::raw("rust")];
+
+            let output = plugin
+                .hash_task_contents(HashTaskContentsInput {
+                    task,
+                    ..Default::default()
+                })
+                .await;
+
+            let contents = &output.contents[0];
+
+            assert_eq!(contents.get("channel").unwrap(), "1.89.0");
+            assert_eq!(
+                contents.get("profile").unwrap(),
+                &json!({ "release": { "lto": true } })
+            );
+            assert_eq!(
+                contents.get("configs").unwrap(),
+                &json!([{
+                    "build": { "rustflags": ["-Dwarnings"] },
+                    "profile": null,
+                    "target": null,
+                }])
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn changes_when_profile_changes() {
+            let sandbox = create_rust_sandbox();
+            let plugin = sandbox.create_toolchain("rust").await;
+
+            let mut task = plugin.create_task_fragment();
+            task.toolchains = vec![Id::raw("rust")];
+
+            let before = plugin
+                .hash_task_contents(HashTaskContentsInput {
+                    task: task.clone(),
+                    ..Default::default()
+                })
+                .await;
+
+            sandbox.create_file(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"project\"]\n\n[profile.release]\nlto = false\n",
+            );
+
+            let after = plugin
+                .hash_task_contents(HashTaskContentsInput {
+                    task,
+                    ..Default::default()
+                })
+                .await;
+
+            assert_ne!(before.contents, after.contents);
+        }
+    }
 }
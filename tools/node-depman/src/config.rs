@@ -6,6 +6,7 @@ pub struct NodeDepmanToolConfig {
     pub registry_url: String,
     pub dist_url: String,
     pub shared_globals_dir: bool,
+    pub verify_integrity: bool,
 }
 
 impl Default for NodeDepmanToolConfig {
@@ -14,6 +15,7 @@ impl Default for NodeDepmanToolConfig {
             registry_url: DEFAULT_REGISTRY.into(),
             dist_url: "{registry}/{package}/-/{package_without_scope}-{version}.tgz".into(),
             shared_globals_dir: false,
+            verify_integrity: true,
         }
     }
 }
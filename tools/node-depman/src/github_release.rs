@@ -0,0 +1,23 @@
+use proto_pdk::Checksum;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct GitHubReleaseAsset {
+    pub digest: Option<String>, // <algo>:<hex>
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct GitHubRelease {
+    pub assets: Vec<GitHubReleaseAsset>,
+}
+
+impl GitHubRelease {
+    pub fn get_asset_checksum(&self, name: &str) -> Option<Checksum> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .and_then(|asset| asset.digest.as_deref())
+            .and_then(|digest| digest.parse().ok())
+    }
+}
@@ -2,7 +2,7 @@
 
 mod config;
 #[cfg(feature = "wasm")]
-mod npm_registry;
+mod github_release;
 #[cfg(feature = "wasm")]
 mod package_manager;
 #[cfg(feature = "wasm")]
@@ -2,9 +2,8 @@
 
 use crate::config::DEFAULT_REGISTRY;
 use crate::yarn_compat::*;
-use npmrc_config_rs::{
-    Credentials, LoadOptions, NpmrcConfig, nerf_dart, registry::parse_registry_url,
-};
+use lang_javascript_common::{create_npm_auth_headers, load_npmrc};
+use npmrc_config_rs::{Credentials, nerf_dart, registry::parse_registry_url};
 use proto_pdk::{
     AnyResult, HostArch, HostEnvironment, HostLibc, HostOS, PluginError, VersionSpec, VirtualPath,
     get_plugin_id,
@@ -239,21 +238,11 @@ impl PackageManager {
         registry_url: &str,
         working_dir: &VirtualPath,
     ) -> AnyResult<FxHashMap<String, String>> {
-        let mut headers = FxHashMap::default();
         let url = parse_registry_url(registry_url)?;
 
         let credentials = match self {
             Self::Npm | Self::Nub | Self::Pnpm | Self::Pnpm11 | Self::Pnpm12 => {
-                let rc = NpmrcConfig::load_with_options(LoadOptions {
-                    cwd: Some(working_dir.into()),
-                    global_prefix: None,
-                    user_config: Some("/userhome/.npmrc".into()),
-                    skip_project: false,
-                    skip_user: false,
-                    skip_global: true,
-                })?;
-
-                rc.credentials_for(&url)
+                load_npmrc(working_dir)?.credentials_for(&url)
             }
             Self::Yarn1 | Self::Yarn2to5 | Self::Yarn6 => {
                 if let Some(rc_path) = find_upwards(".yarnrc.yml", working_dir) {
@@ -295,21 +284,6 @@ impl PackageManager {
             }
         };
 
-        // https://github.com/npm/registry/blob/main/docs/user/authentication.md
-        if let Some(creds) = credentials {
-            match &creds {
-                Credentials::Token { token, .. } => {
-                    headers.insert("Authorization".into(), format!("Bearer {token}"));
-                }
-                Credentials::BasicAuth { .. } | Credentials::LegacyAuth { .. } => {
-                    if let Some(encoded) = creds.basic_auth_header() {
-                        headers.insert("Authorization".into(), format!("Basic {encoded}"));
-                    }
-                }
-                Credentials::ClientCertOnly(_) => {}
-            };
-        }
-
-        Ok(headers)
+        Ok(create_npm_auth_headers(credentials))
     }
 }
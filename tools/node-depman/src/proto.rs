@@ -1,11 +1,11 @@
 use crate::config::NodeDepmanToolConfig;
-use crate::npm_registry::parse_registry_response;
+use crate::github_release::GitHubRelease;
 use crate::package_manager::PackageManager;
 use extism_pdk::*;
 use lang_javascript_common::{
     NodeDistVersion, extract_dev_engine_package_manager_version, extract_engine_version,
     extract_package_manager_version, extract_volta_version, insert_dev_engine_version,
-    remove_dev_engine,
+    parse_registry_response, parse_registry_version, remove_dev_engine,
 };
 use nodejs_package_json::PackageJson;
 use proto_pdk::*;
@@ -401,9 +401,15 @@ pub fn download_prebuilt(
         };
 
         let filename = format!("yarn-{arch}-{os}{libc}.zip");
+        let checksum = if get_tool_config::<NodeDepmanToolConfig>()?.verify_integrity {
+            load_github_release_checksum(&format!("v{version}"), &filename)?
+        } else {
+            None
+        };
 
         return Ok(Json(DownloadPrebuiltOutput {
             archive_prefix: Some(filename.replace(".zip", "")),
+            checksum,
             download_url: format!(
                 "https://github.com/yarnpkg/zpm/releases/download/v{version}/{filename}"
             ),
@@ -437,20 +443,66 @@ pub fn download_prebuilt(
     let registry_url = config.registry_url;
     let dist_url = config.dist_url;
     let filename = format!("{package_without_scope}-{version}.tgz");
+    let http_headers = manager.get_http_headers(&registry_url, &input.context.working_dir)?;
+
+    // Private registries require the same auth as the download itself
+    let checksum = if config.verify_integrity {
+        parse_registry_version(
+            fetch(SendRequestInput {
+                url: format!("{registry_url}/{package_name}/{version}"),
+                headers: http_headers.clone(),
+                ..Default::default()
+            })?
+            .text()?,
+            manager.is_yarn(),
+        )?
+        .dist
+        .get_checksum()
+    } else {
+        None
+    };
 
     Ok(Json(DownloadPrebuiltOutput {
         archive_prefix: Some(get_archive_prefix(&manager, version)),
+        checksum,
         download_url: dist_url
             .replace("{registry}", &registry_url)
             .replace("{package}", &package_name)
             .replace("{package_without_scope}", package_without_scope)
             .replace("{version}", &version.to_string())
             .replace("{file}", &filename),
-        http_headers: manager.get_http_headers(&registry_url, &input.context.working_dir)?,
+        http_headers,
         ..Default::default()
     }))
 }
 
+// GitHub computes a digest for every release asset, but the API
+// is rate limited, so a failed request shouldn't block the install
+fn load_github_release_checksum(tag: &str, asset: &str) -> AnyResult<Option<Checksum>> {
+    let mut headers = FxHashMap::default();
+
+    if let Some(token) = get_host_env_var("GITHUB_TOKEN")? {
+        headers.insert("Authorization".into(), format!("Bearer {token}"));
+    }
+
+    let release = match fetch(SendRequestInput {
+        url: format!("https://api.github.com/repos/yarnpkg/zpm/releases/tags/{tag}"),
+        headers,
+        ..Default::default()
+    })
+    .and_then(|res| res.json::<GitHubRelease>())
+    {
+        Ok(release) => release,
+        Err(error) => {
+            debug!("Unable to load release digests from GitHub: {error}");
+
+            return Ok(None);
+        }
+    };
+
+    Ok(release.get_asset_checksum(asset))
+}
+
 #[plugin_fn]
 pub fn locate_executables(
     Json(input): Json<LocateExecutablesInput>,
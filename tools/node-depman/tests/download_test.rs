@@ -1,10 +1,41 @@
+use lang_javascript_common::serve_npm_registry;
 use proto_pdk_test_utils::*;
 use rustc_hash::FxHashMap;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
 #[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
 pub struct NodeDepmanPluginConfig {
     pub registry_url: String,
+    pub verify_integrity: bool,
+}
+
+impl NodeDepmanPluginConfig {
+    // Integrity is verified against a local registry in dedicated tests
+    fn without_integrity() -> Self {
+        Self {
+            registry_url: "https://registry.npmjs.org".into(),
+            verify_integrity: false,
+        }
+    }
+}
+
+// SHA-512 of "hello world"
+const INTEGRITY: &str = "sha512-MJ7MSJwS1utMxA9QyQLytNDtd+5RGnx6m808qG1M2G+YndNbxf9JlnDaNCVbRbDP2DDoH2Bdz33FVC6TrpzXbw==";
+const INTEGRITY_HEX: &str = "309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f";
+
+// Serves version metadata from a local registry and records each request
+fn serve_registry_version(package: &str, version: &str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let body = serde_json::json!({
+        "name": package,
+        "version": version,
+        "dist": {
+            "integrity": INTEGRITY,
+        },
+    })
+    .to_string();
+
+    serve_npm_registry(move |_| ("200 OK", body.clone()))
 }
 
 mod node_depman_tool {
@@ -20,7 +51,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("npm-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -69,6 +102,57 @@ mod node_depman_tool {
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_integrity_from_registry() {
+            let (registry_url, requests) = serve_registry_version("npm", "9.0.0");
+
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".npmrc",
+                format!(
+                    "{}/:_authToken = abc123",
+                    registry_url.trim_start_matches("http:")
+                ),
+            );
+
+            let plugin = sandbox
+                .create_plugin_with_config("npm-test", |config| {
+                    config.host(HostOS::Linux, HostArch::Arm64).tool_config(
+                        NodeDepmanPluginConfig {
+                            registry_url: registry_url.clone(),
+                            verify_integrity: true,
+                        },
+                    );
+                })
+                .await;
+
+            let output = plugin
+                .download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("9.0.0").unwrap(),
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(
+                output.checksum,
+                Some(Checksum::sha512(INTEGRITY_HEX.into()))
+            );
+
+            let requests = requests.lock().unwrap();
+
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].starts_with("GET /npm/9.0.0 "));
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("authorization: bearer abc123")
+            );
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn downloads_from_custom_registry() {
             let sandbox = create_empty_proto_sandbox();
@@ -78,6 +162,7 @@ mod node_depman_tool {
                         .host(HostOS::MacOS, HostArch::X64)
                         .tool_config(NodeDepmanPluginConfig {
                             registry_url: "https://some-internal-url.example".into(),
+                            verify_integrity: false,
                         });
                 })
                 .await;
@@ -107,7 +192,9 @@ mod node_depman_tool {
 
             let plugin = sandbox
                 .create_plugin_with_config("npm-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -144,6 +231,7 @@ mod node_depman_tool {
                     config.host(HostOS::Linux, HostArch::Arm64).tool_config(
                         NodeDepmanPluginConfig {
                             registry_url: "https://registry.yarnpkg.com".into(),
+                            verify_integrity: false,
                         },
                     );
                 })
@@ -186,20 +274,21 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("nub-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::Arm64);
+                    config
+                        .host(HostOS::MacOS, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("0.7.4").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("0.7.4").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@nubjs/nub-darwin-arm64/-/nub-darwin-arm64-0.7.4.tgz".into(),
@@ -213,11 +302,13 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("nub-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::X64;
-                        host.libc = HostLibc::Gnu;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::X64;
+                            host.libc = HostLibc::Gnu;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -234,7 +325,9 @@ mod node_depman_tool {
                     .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
-                    download_url: "https://registry.npmjs.org/@nubjs/nub-linux-x64/-/nub-linux-x64-0.7.4.tgz".into(),
+                    download_url:
+                        "https://registry.npmjs.org/@nubjs/nub-linux-x64/-/nub-linux-x64-0.7.4.tgz"
+                            .into(),
                     ..Default::default()
                 }
             );
@@ -245,24 +338,25 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("nub-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::Arm64;
-                        host.libc = HostLibc::Musl;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::Arm64;
+                            host.libc = HostLibc::Musl;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("0.7.4").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("0.7.4").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@nubjs/nub-linux-arm64-musl/-/nub-linux-arm64-musl-0.7.4.tgz".into(),
@@ -276,7 +370,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("nub-test", |config| {
-                    config.host(HostOS::Windows, HostArch::X64);
+                    config
+                        .host(HostOS::Windows, HostArch::X64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -292,7 +388,9 @@ mod node_depman_tool {
                     .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
-                    download_url: "https://registry.npmjs.org/@nubjs/nub-win32-x64/-/nub-win32-x64-0.7.4.tgz".into(),
+                    download_url:
+                        "https://registry.npmjs.org/@nubjs/nub-win32-x64/-/nub-win32-x64-0.7.4.tgz"
+                            .into(),
                     ..Default::default()
                 }
             );
@@ -307,20 +405,20 @@ mod node_depman_tool {
                         .host(HostOS::MacOS, HostArch::X64)
                         .tool_config(NodeDepmanPluginConfig {
                             registry_url: "https://some-internal-url.example".into(),
+                            verify_integrity: false,
                         });
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("0.7.4").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("0.7.4").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://some-internal-url.example/@nubjs/nub-darwin-x64/-/nub-darwin-x64-0.7.4.tgz".into(),
@@ -336,21 +434,22 @@ mod node_depman_tool {
 
             let plugin = sandbox
                 .create_plugin_with_config("nub-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("0.7.4").unwrap(),
-                            working_dir: plugin.tool.to_virtual_path(sandbox.path()),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("0.7.4").unwrap(),
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@nubjs/nub-linux-arm64/-/nub-linux-arm64-0.7.4.tgz".into(),
@@ -493,7 +592,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::Windows, HostArch::X64);
+                    config
+                        .host(HostOS::Windows, HostArch::X64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -520,7 +621,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::Arm64);
+                    config
+                        .host(HostOS::MacOS, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -579,6 +682,7 @@ mod node_depman_tool {
                         .host(HostOS::MacOS, HostArch::X64)
                         .tool_config(NodeDepmanPluginConfig {
                             registry_url: "https://some-internal-url.example".into(),
+                            verify_integrity: false,
                         });
                 })
                 .await;
@@ -608,7 +712,9 @@ mod node_depman_tool {
 
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -645,6 +751,7 @@ mod node_depman_tool {
                     config.host(HostOS::Linux, HostArch::Arm64).tool_config(
                         NodeDepmanPluginConfig {
                             registry_url: "https://registry.yarnpkg.com".into(),
+                            verify_integrity: false,
                         },
                     );
                 })
@@ -685,20 +792,21 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::Arm64);
+                    config
+                        .host(HostOS::MacOS, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("12.0.0").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("12.0.0").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@pnpm/exe.darwin-arm64/-/exe.darwin-arm64-12.0.0.tgz".into(),
@@ -712,11 +820,13 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::X64;
-                        host.libc = HostLibc::Gnu;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::X64;
+                            host.libc = HostLibc::Gnu;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -733,7 +843,9 @@ mod node_depman_tool {
                     .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
-                    download_url: "https://registry.npmjs.org/@pnpm/exe.linux-x64/-/exe.linux-x64-12.0.0.tgz".into(),
+                    download_url:
+                        "https://registry.npmjs.org/@pnpm/exe.linux-x64/-/exe.linux-x64-12.0.0.tgz"
+                            .into(),
                     ..Default::default()
                 }
             );
@@ -744,24 +856,25 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::Arm64;
-                        host.libc = HostLibc::Musl;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::Arm64;
+                            host.libc = HostLibc::Musl;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("12.0.0").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("12.0.0").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@pnpm/exe.linux-arm64-musl/-/exe.linux-arm64-musl-12.0.0.tgz".into(),
@@ -775,7 +888,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::Windows, HostArch::X64);
+                    config
+                        .host(HostOS::Windows, HostArch::X64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -792,7 +907,9 @@ mod node_depman_tool {
                     .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
-                    download_url: "https://registry.npmjs.org/@pnpm/exe.win32-x64/-/exe.win32-x64-12.0.0.tgz".into(),
+                    download_url:
+                        "https://registry.npmjs.org/@pnpm/exe.win32-x64/-/exe.win32-x64-12.0.0.tgz"
+                            .into(),
                     ..Default::default()
                 }
             );
@@ -805,21 +922,22 @@ mod node_depman_tool {
 
             let plugin = sandbox
                 .create_plugin_with_config("pnpm-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("12.0.0").unwrap(),
-                            working_dir: plugin.tool.to_virtual_path(sandbox.path()),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("12.0.0").unwrap(),
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("package".into()),
                     download_url: "https://registry.npmjs.org/@pnpm/exe.linux-arm64/-/exe.linux-arm64-12.0.0.tgz".into(),
@@ -955,10 +1073,7 @@ mod node_depman_tool {
                 .exes;
 
             // The .exe extension must not be rewritten to .cmd
-            assert_eq!(
-                exes.get("pnpm").unwrap().exe_path,
-                Some("pnpm.exe".into())
-            );
+            assert_eq!(exes.get("pnpm").unwrap().exe_path, Some("pnpm.exe".into()));
         }
 
         #[tokio::test(flavor = "multi_thread")]
@@ -1000,7 +1115,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::X64);
+                    config
+                        .host(HostOS::MacOS, HostArch::X64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -1049,6 +1166,57 @@ mod node_depman_tool {
             );
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn loads_integrity_from_registry() {
+            let (registry_url, requests) = serve_registry_version("yarn", "1.22.0");
+
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(
+                ".yarnrc.yml",
+                format!(
+                    "npmRegistries:\n  \"{}/\":\n    npmAuthToken: abc123\n",
+                    registry_url.trim_start_matches("http:")
+                ),
+            );
+
+            let plugin = sandbox
+                .create_plugin_with_config("yarn-test", |config| {
+                    config.host(HostOS::Linux, HostArch::Arm64).tool_config(
+                        NodeDepmanPluginConfig {
+                            registry_url: registry_url.clone(),
+                            verify_integrity: true,
+                        },
+                    );
+                })
+                .await;
+
+            let output = plugin
+                .download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("1.22.0").unwrap(),
+                        working_dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(
+                output.checksum,
+                Some(Checksum::sha512(INTEGRITY_HEX.into()))
+            );
+
+            let requests = requests.lock().unwrap();
+
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].starts_with("GET /yarn/1.22.0 "));
+            assert!(
+                requests[0]
+                    .to_lowercase()
+                    .contains("authorization: bearer abc123")
+            );
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         async fn downloads_from_custom_registry() {
             let sandbox = create_empty_proto_sandbox();
@@ -1058,6 +1226,7 @@ mod node_depman_tool {
                         .host(HostOS::MacOS, HostArch::X64)
                         .tool_config(NodeDepmanPluginConfig {
                             registry_url: "https://registry.yarnpkg.com".into(),
+                            verify_integrity: false,
                         });
                 })
                 .await;
@@ -1098,7 +1267,9 @@ mod node_depman_tool {
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::X64);
+                    config
+                        .host(HostOS::MacOS, HostArch::X64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -1155,7 +1326,9 @@ mod node_depman_tool {
 
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host(HostOS::Linux, HostArch::Arm64);
+                    config
+                        .host(HostOS::Linux, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
@@ -1202,6 +1375,7 @@ npmRegistries:
                     config.host(HostOS::Linux, HostArch::Arm64).tool_config(
                         NodeDepmanPluginConfig {
                             registry_url: "https://registry.yarnpkg.com".into(),
+                            verify_integrity: false,
                         },
                     );
                 })
@@ -1250,20 +1424,21 @@ npmRegistries:
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host(HostOS::MacOS, HostArch::Arm64);
+                    config
+                        .host(HostOS::MacOS, HostArch::Arm64)
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("yarn-aarch64-apple-darwin".into()),
                     download_name: Some("yarn-aarch64-apple-darwin.zip".into()),
@@ -1278,24 +1453,25 @@ npmRegistries:
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::Arm64;
-                        host.libc = HostLibc::Musl;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::Arm64;
+                            host.libc = HostLibc::Musl;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("yarn-aarch64-unknown-linux-musl".into()),
                     download_name: Some("yarn-aarch64-unknown-linux-musl.zip".into()),
@@ -1310,24 +1486,25 @@ npmRegistries:
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::X64;
-                        host.libc = HostLibc::Musl;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::X64;
+                            host.libc = HostLibc::Musl;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("yarn-x86_64-unknown-linux-musl".into()),
                     download_name: Some("yarn-x86_64-unknown-linux-musl.zip".into()),
@@ -1388,24 +1565,25 @@ npmRegistries:
             let sandbox = create_empty_proto_sandbox();
             let plugin = sandbox
                 .create_plugin_with_config("yarn-test", |config| {
-                    config.host_with(|host| {
-                        host.os = HostOS::Linux;
-                        host.arch = HostArch::X86;
-                        host.libc = HostLibc::Musl;
-                    });
+                    config
+                        .host_with(|host| {
+                            host.os = HostOS::Linux;
+                            host.arch = HostArch::X86;
+                            host.libc = HostLibc::Musl;
+                        })
+                        .tool_config(NodeDepmanPluginConfig::without_integrity());
                 })
                 .await;
 
             assert_eq!(
-                plugin
-                    .download_prebuilt(DownloadPrebuiltInput {
-                        context: PluginContext {
-                            version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
-                            ..Default::default()
-                        },
+                plugin.download_prebuilt(DownloadPrebuiltInput {
+                    context: PluginContext {
+                        version: VersionSpec::parse("6.0.0-rc.19").unwrap(),
                         ..Default::default()
-                    })
-                    .await,
+                    },
+                    ..Default::default()
+                })
+                .await,
                 DownloadPrebuiltOutput {
                     archive_prefix: Some("yarn-i686-unknown-linux-musl".into()),
                     download_name: Some("yarn-i686-unknown-linux-musl.zip".into()),
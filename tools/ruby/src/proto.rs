@@ -1,6 +1,7 @@
 use crate::releases::*;
 use extism_pdk::*;
 use proto_pdk::*;
+use starbase_utils::fs;
 use std::collections::HashMap;
 use tool_common::enable_tracing;
 
@@ -27,11 +28,194 @@ pub fn register_tool(Json(_): Json<RegisterToolInput>) -> FnResult<Json<Register
 #[plugin_fn]
 pub fn detect_version_files(_: ()) -> FnResult<Json<DetectVersionOutput>> {
     Ok(Json(DetectVersionOutput {
-        files: vec![".ruby-version".into()],
+        files: vec![
+            ".ruby-version".into(),
+            "Gemfile".into(),
+            "Gemfile.lock".into(),
+            ".tool-versions".into(),
+        ],
         ignore: vec!["vendor".into()],
     }))
 }
 
+#[plugin_fn]
+pub fn parse_version_file(
+    Json(input): Json<ParseVersionFileInput>,
+) -> FnResult<Json<ParseVersionFileOutput>> {
+    let mut version = None;
+
+    match input.file.as_str() {
+        ".ruby-version" => {
+            version = parse_ruby_version_file(&input.content)?;
+        }
+        "Gemfile" => {
+            for line in input.content.lines() {
+                let Some(args) = line.trim().strip_prefix("ruby ") else {
+                    continue;
+                };
+
+                // ruby file: ".ruby-version"
+                if let Some(file) = args
+                    .trim()
+                    .strip_prefix("file:")
+                    .and_then(|arg| unquote(arg.trim()))
+                {
+                    let path = input
+                        .path
+                        .parent()
+                        .ok_or_else(|| {
+                            anyhow!("Unable to determine the directory of the Gemfile.")
+                        })?
+                        .join(file);
+
+                    if path.exists() && path.is_file() {
+                        version = parse_ruby_version_file(&fs::read_file(&path)?)?;
+                    }
+
+                    break;
+                }
+
+                // ruby "~> 3.3", engine: "jruby"
+                let constraints = args
+                    .split(',')
+                    .filter_map(|arg| unquote(arg.trim()))
+                    .map(from_ruby_requirement)
+                    .collect::<Vec<_>>();
+
+                if !constraints.is_empty() {
+                    version = Some(UnresolvedVersionSpec::parse(constraints.join(", "))?);
+                }
+
+                break;
+            }
+        }
+        "Gemfile.lock" => {
+            let mut in_section = false;
+
+            for line in input.content.lines() {
+                if line == "RUBY VERSION" {
+                    in_section = true;
+                    continue;
+                }
+
+                if in_section {
+                    // ruby 3.3.4p94
+                    if let Some(v) = line.trim().strip_prefix("ruby ") {
+                        let v = v.trim();
+                        let v = match v.rsplit_once('p') {
+                            Some((base, patch))
+                                if !patch.is_empty()
+                                    && patch.chars().all(|c| c.is_ascii_digit()) =>
+                            {
+                                base
+                            }
+                            _ => v,
+                        };
+
+                        version = Some(UnresolvedVersionSpec::parse(v)?);
+                    }
+
+                    break;
+                }
+            }
+        }
+        ".tool-versions" => {
+            for line in input.content.lines() {
+                if let Some(v) = line
+                    .trim()
+                    .strip_prefix("ruby ")
+                    .and_then(|v| v.split_whitespace().next())
+                {
+                    version = Some(UnresolvedVersionSpec::parse(v)?);
+                    break;
+                }
+            }
+        }
+        _ => {}
+    };
+
+    Ok(Json(ParseVersionFileOutput { version }))
+}
+
+#[plugin_fn]
+pub fn pin_version(Json(input): Json<PinVersionInput>) -> FnResult<Json<PinVersionOutput>> {
+    let mut output = PinVersionOutput::default();
+    let file = input.dir.join(".ruby-version");
+
+    // Version managers can only read fully-qualified versions
+    if let UnresolvedVersionSpec::Version(version) = &input.version {
+        fs::write_file(&file, format!("{version}\n"))?;
+
+        output.pinned = true;
+        output.file = Some(file);
+    } else {
+        output.error = Some(format!(
+            "Only fully-qualified versions can be pinned to <file>.ruby-version</file>, received <hash>{}</hash>.",
+            input.version
+        ));
+    }
+
+    Ok(Json(output))
+}
+
+#[plugin_fn]
+pub fn unpin_version(Json(input): Json<UnpinVersionInput>) -> FnResult<Json<UnpinVersionOutput>> {
+    let mut output = UnpinVersionOutput::default();
+    let file = input.dir.join(".ruby-version");
+
+    if file.exists() {
+        let version = parse_ruby_version_file(&fs::read_file(&file)?)?;
+
+        fs::remove_file(&file)?;
+
+        output.unpinned = true;
+        output.version = version;
+        output.file = Some(file);
+    } else {
+        output.error = Some("No <file>.ruby-version</file> exists in the target directory.".into());
+    }
+
+    Ok(Json(output))
+}
+
+// Version managers like rbenv and chruby allow an engine prefix,
+// like `ruby-3.3.4`, and comments after the version
+fn parse_ruby_version_file(content: &str) -> AnyResult<Option<UnresolvedVersionSpec>> {
+    let Some(line) = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let version = line.strip_prefix("ruby-").unwrap_or(line);
+
+    Ok(Some(UnresolvedVersionSpec::parse(version)?))
+}
+
+// Bundler uses RubyGems requirements, where `~>` is the pessimistic operator
+fn from_ruby_requirement(requirement: &str) -> String {
+    let Some(version) = requirement.strip_prefix("~>") else {
+        return requirement.to_owned();
+    };
+
+    let version = version.trim();
+
+    if version.split('.').count() <= 2 {
+        format!("^{version}")
+    } else {
+        format!("~{version}")
+    }
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+}
+
 #[plugin_fn]
 pub fn load_versions(Json(_): Json<LoadVersionsInput>) -> FnResult<Json<LoadVersionsOutput>> {
     let tags = load_git_tags("https://github.com/ruby/ruby")?
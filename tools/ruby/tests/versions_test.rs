@@ -0,0 +1,274 @@
+use proto_pdk_test_utils::*;
+use std::fs;
+
+mod ruby_tool {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn registers_version_files() {
+        let sandbox = create_empty_proto_sandbox();
+        let plugin = sandbox.create_plugin("ruby-test").await;
+
+        assert_eq!(
+            plugin
+                .detect_version_files(DetectVersionInput::default())
+                .await
+                .files,
+            [".ruby-version", "Gemfile", "Gemfile.lock", ".tool-versions"]
+        );
+    }
+
+    mod parse_version_file {
+        use super::*;
+
+        async fn parse(file: &str, content: &str) -> Option<UnresolvedVersionSpec> {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            plugin
+                .parse_version_file(ParseVersionFileInput {
+                    content: content.into(),
+                    file: file.into(),
+                    ..Default::default()
+                })
+                .await
+                .version
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn ruby_version_file() {
+            assert_eq!(
+                parse(".ruby-version", "3.3.4\n").await.unwrap(),
+                UnresolvedVersionSpec::parse("3.3.4").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn ruby_version_file_with_engine_prefix() {
+            assert_eq!(
+                parse(".ruby-version", "ruby-3.2.1 # comment\n")
+                    .await
+                    .unwrap(),
+                UnresolvedVersionSpec::parse("3.2.1").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_exact() {
+            assert_eq!(
+                parse(
+                    "Gemfile",
+                    r#"
+source "https://rubygems.org"
+
+ruby "3.3.4"
+
+gem "rails"
+"#,
+                )
+                .await
+                .unwrap(),
+                UnresolvedVersionSpec::parse("3.3.4").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_pessimistic() {
+            assert_eq!(
+                parse("Gemfile", "ruby '~> 3.3'\n").await.unwrap(),
+                UnresolvedVersionSpec::parse("^3.3").unwrap()
+            );
+            assert_eq!(
+                parse("Gemfile", "ruby \"~> 3.3.1\"\n").await.unwrap(),
+                UnresolvedVersionSpec::parse("~3.3.1").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_ignores_options() {
+            assert_eq!(
+                parse(
+                    "Gemfile",
+                    "ruby \"3.1.4\", engine: \"jruby\", engine_version: \"9.4\"\n"
+                )
+                .await
+                .unwrap(),
+                UnresolvedVersionSpec::parse("3.1.4").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_without_ruby() {
+            assert_eq!(parse("Gemfile", "gem \"rails\"\n").await, None);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_from_file() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file("Gemfile", "ruby file: \".ruby-version\"\n");
+            sandbox.create_file(".ruby-version", "3.4.1\n");
+
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            let output = plugin
+                .parse_version_file(ParseVersionFileInput {
+                    content: "ruby file: \".ruby-version\"\n".into(),
+                    file: "Gemfile".into(),
+                    path: plugin.tool.to_virtual_path(sandbox.path().join("Gemfile")),
+                    ..Default::default()
+                })
+                .await;
+
+            assert_eq!(
+                output.version.unwrap(),
+                UnresolvedVersionSpec::parse("3.4.1").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn gemfile_lock() {
+            assert_eq!(
+                parse(
+                    "Gemfile.lock",
+                    r#"GEM
+  remote: https://rubygems.org/
+  specs:
+    rake (13.2.1)
+
+PLATFORMS
+  ruby
+
+RUBY VERSION
+   ruby 3.3.4p94
+
+BUNDLED WITH
+   2.5.11
+"#,
+                )
+                .await
+                .unwrap(),
+                UnresolvedVersionSpec::parse("3.3.4").unwrap()
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn tool_versions() {
+            assert_eq!(
+                parse(".tool-versions", "nodejs 20.0.0\nruby 3.2.2 3.1.0\n")
+                    .await
+                    .unwrap(),
+                UnresolvedVersionSpec::parse("3.2.2").unwrap()
+            );
+            assert_eq!(parse(".tool-versions", "nodejs 20.0.0\n").await, None);
+        }
+    }
+
+    mod pin_version {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn writes_ruby_version_file() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            assert_eq!(
+                plugin
+                    .pin_version(PinVersionInput {
+                        dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        version: UnresolvedVersionSpec::parse("3.3.4").unwrap(),
+                        ..Default::default()
+                    })
+                    .await,
+                PinVersionOutput {
+                    file: Some(
+                        plugin
+                            .tool
+                            .to_virtual_path(sandbox.path().join(".ruby-version"))
+                    ),
+                    error: None,
+                    pinned: true,
+                }
+            );
+
+            assert_eq!(
+                fs::read_to_string(sandbox.path().join(".ruby-version")).unwrap(),
+                "3.3.4\n"
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn errors_for_partial_versions_and_aliases() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            for version in ["~3.3", "latest"] {
+                let output = plugin
+                    .pin_version(PinVersionInput {
+                        dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        version: UnresolvedVersionSpec::parse(version).unwrap(),
+                        ..Default::default()
+                    })
+                    .await;
+
+                assert!(!output.pinned);
+                assert!(output.error.unwrap().contains(version));
+            }
+
+            assert!(!sandbox.path().join(".ruby-version").exists());
+        }
+    }
+
+    mod unpin_version {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn errors_if_no_ruby_version_file() {
+            let sandbox = create_empty_proto_sandbox();
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            assert_eq!(
+                plugin
+                    .unpin_version(UnpinVersionInput {
+                        dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    })
+                    .await,
+                UnpinVersionOutput {
+                    error: Some(
+                        "No <file>.ruby-version</file> exists in the target directory.".into()
+                    ),
+                    ..Default::default()
+                }
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn removes_ruby_version_file() {
+            let sandbox = create_empty_proto_sandbox();
+            sandbox.create_file(".ruby-version", "3.3.4\n");
+
+            let plugin = sandbox.create_plugin("ruby-test").await;
+
+            assert_eq!(
+                plugin
+                    .unpin_version(UnpinVersionInput {
+                        dir: plugin.tool.to_virtual_path(sandbox.path()),
+                        ..Default::default()
+                    })
+                    .await,
+                UnpinVersionOutput {
+                    file: Some(
+                        plugin
+                            .tool
+                            .to_virtual_path(sandbox.path().join(".ruby-version"))
+                    ),
+                    error: None,
+                    unpinned: true,
+                    version: Some(UnresolvedVersionSpec::parse("3.3.4").unwrap()),
+                }
+            );
+
+            assert!(!sandbox.path().join(".ruby-version").exists());
+        }
+    }
+}